@@ -0,0 +1,36 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    schema::{load_sdl, read_query},
+    Error,
+};
+
+/// Validates each query document against the schema, reporting any errors to
+/// stderr.
+pub fn check(schema: &Path, queries: &[PathBuf]) -> Result<(), Error> {
+    let schema = load_sdl(schema)?;
+
+    let mut failures = 0;
+    for path in queries {
+        let result = read_query(path).and_then(|query| {
+            cynic_querygen::validate_document(query, &schema).map_err(|source| Error::QueryGen {
+                path: path.display().to_string(),
+                source,
+            })
+        });
+
+        match result {
+            Ok(()) => println!("{}: ok", path.display()),
+            Err(error) => {
+                eprintln!("{}", error);
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(Error::CheckFailed(failures, queries.len()));
+    }
+
+    Ok(())
+}
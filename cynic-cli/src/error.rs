@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("could not access {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("{0}")]
+    SchemaLoad(#[from] cynic_codegen::SchemaLoadError),
+
+    #[error("{path}: {source}")]
+    QueryGen {
+        path: String,
+        source: cynic_querygen::Error,
+    },
+
+    #[error("invalid header `{0}`, expected `Name: Value`")]
+    InvalidHeader(String),
+
+    #[error("error making introspection request: {0}")]
+    Http(#[from] cynic::http::CynicReqwestError),
+
+    #[error("the server returned errors: {}", .0.iter().map(|e| e.message.as_str()).collect::<Vec<_>>().join(", "))]
+    GraphQl(Vec<cynic::GraphQlError>),
+
+    #[error("the server returned no data")]
+    NoData,
+
+    #[error("{0} of {1} documents failed validation")]
+    CheckFailed(usize, usize),
+}
@@ -0,0 +1,54 @@
+use cynic::{http::ReqwestBlockingExt, introspection::IntrospectionQuery, QueryBuilder};
+use std::path::Path;
+
+use crate::{schema::load_sdl, Error};
+
+/// Fetches a schema from a URL or introspection file & returns it as SDL.
+pub fn introspect(source: &str, headers: &[String]) -> Result<String, Error> {
+    if !(source.starts_with("http://") || source.starts_with("https://")) {
+        return load_sdl(Path::new(source));
+    }
+
+    let mut request = reqwest::blocking::Client::new().post(source);
+    for header in headers {
+        let (name, value) = parse_header(header)?;
+        request = request.header(name, value);
+    }
+
+    let response = request.run_graphql(IntrospectionQuery::build(()))?;
+
+    let data = match (response.data, response.errors) {
+        (Some(data), _) => data,
+        (None, Some(errors)) if !errors.is_empty() => return Err(Error::GraphQl(errors)),
+        (None, _) => return Err(Error::NoData),
+    };
+
+    // The introspection types serialize back into the JSON the server sent,
+    // which cynic_codegen knows how to convert.
+    let json = serde_json::to_string(&data).expect("introspection results to serialize");
+
+    Ok(cynic_codegen::parse_introspection_json(&json)?.to_string())
+}
+
+fn parse_header(header: &str) -> Result<(&str, &str), Error> {
+    let mut parts = header.splitn(2, ':');
+    match (parts.next(), parts.next()) {
+        (Some(name), Some(value)) if !name.trim().is_empty() => Ok((name.trim(), value.trim())),
+        _ => Err(Error::InvalidHeader(header.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_header() {
+        assert_eq!(
+            parse_header("Authorization: Bearer abcd").unwrap(),
+            ("Authorization", "Bearer abcd")
+        );
+        assert!(parse_header("Authorization").is_err());
+        assert!(parse_header(": abcd").is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! The cynic command line interface.
+
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+mod check;
+mod error;
+mod introspect;
+mod querygen;
+mod schema;
+
+use error::Error;
+
+#[derive(StructOpt, Debug)]
+#[structopt(
+    name = "cynic",
+    about = "Tools for working with cynic & GraphQL schemas"
+)]
+enum Command {
+    /// Fetches the schema of a GraphQL server and prints it as SDL.
+    ///
+    /// Accepts either the URL of a GraphQL server, or the path to a JSON file
+    /// containing the response to an introspection query.
+    Introspect {
+        /// The URL of a GraphQL server or the path to an introspection.json
+        #[structopt(name = "URL_OR_FILE")]
+        source: String,
+
+        /// Headers to send to the server, in the form `Name: Value`
+        #[structopt(short = "H", long = "header")]
+        headers: Vec<String>,
+
+        /// A file to write the schema to. Defaults to stdout.
+        #[structopt(short, long, parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
+
+    /// Generates cynic QueryFragments from GraphQL query documents.
+    Querygen {
+        /// The schema to generate queries against, as SDL or introspection JSON.
+        #[structopt(long, parse(from_os_str))]
+        schema: PathBuf,
+
+        /// The schema path to use in the generated code.  Defaults to the
+        /// value of --schema.
+        #[structopt(long)]
+        schema_path: Option<String>,
+
+        /// The name of the schema module in the generated code.
+        #[structopt(long, default_value = "schema")]
+        schema_module: String,
+
+        /// The GraphQL documents to generate code for.
+        #[structopt(name = "QUERIES", required = true, parse(from_os_str))]
+        queries: Vec<PathBuf>,
+
+        /// A file to write the generated code to.  Defaults to stdout.
+        #[structopt(short, long, parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
+
+    /// Checks that GraphQL query documents are valid against a schema.
+    Check {
+        /// The schema to check queries against, as SDL or introspection JSON.
+        #[structopt(long, parse(from_os_str))]
+        schema: PathBuf,
+
+        /// The GraphQL documents to check.
+        #[structopt(name = "QUERIES", required = true, parse(from_os_str))]
+        queries: Vec<PathBuf>,
+    },
+}
+
+fn main() {
+    if let Err(error) = run(Command::from_args()) {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+fn run(command: Command) -> Result<(), Error> {
+    match command {
+        Command::Introspect {
+            source,
+            headers,
+            output,
+        } => {
+            let sdl = introspect::introspect(&source, &headers)?;
+            write_output(output, &sdl)
+        }
+        Command::Querygen {
+            schema,
+            schema_path,
+            schema_module,
+            queries,
+            output,
+        } => {
+            let schema_path = schema_path.unwrap_or_else(|| schema.display().to_string());
+            let code = querygen::querygen(&schema, schema_path, schema_module, &queries)?;
+            write_output(output, &code)
+        }
+        Command::Check { schema, queries } => check::check(&schema, &queries),
+    }
+}
+
+fn write_output(output: Option<PathBuf>, contents: &str) -> Result<(), Error> {
+    match output {
+        Some(path) => std::fs::write(&path, contents).map_err(|source| Error::Io { path, source }),
+        None => {
+            print!("{}", contents);
+            Ok(())
+        }
+    }
+}
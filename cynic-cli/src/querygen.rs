@@ -0,0 +1,41 @@
+use std::path::{Path, PathBuf};
+
+use cynic_querygen::{document_to_fragment_structs, QueryGenOptions};
+
+use crate::{
+    schema::{load_sdl, read_query},
+    Error,
+};
+
+/// Generates QueryFragments for a set of query documents.
+///
+/// All the documents are combined into a single document before generating,
+/// so fragments may be shared between files.
+pub fn querygen(
+    schema: &Path,
+    schema_path: String,
+    schema_module: String,
+    queries: &[PathBuf],
+) -> Result<String, Error> {
+    let schema = load_sdl(schema)?;
+
+    let document = queries
+        .iter()
+        .map(|path| read_query(path))
+        .collect::<Result<Vec<_>, _>>()?
+        .join("\n");
+
+    let options = QueryGenOptions {
+        schema_path,
+        query_module: schema_module,
+    };
+
+    document_to_fragment_structs(document, schema, &options).map_err(|source| Error::QueryGen {
+        path: queries
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+        source,
+    })
+}
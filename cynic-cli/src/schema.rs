@@ -0,0 +1,25 @@
+use std::path::Path;
+
+use crate::Error;
+
+/// Loads a schema as SDL, converting it from introspection JSON if the file
+/// has a `.json` extension.
+pub fn load_sdl(path: &Path) -> Result<String, Error> {
+    let contents = std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_owned(),
+        source,
+    })?;
+
+    if path.extension().map(|ext| ext == "json").unwrap_or(false) {
+        return Ok(cynic_codegen::parse_introspection_json(&contents)?.to_string());
+    }
+
+    Ok(contents)
+}
+
+pub fn read_query(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
@@ -0,0 +1,71 @@
+use std::process::{Command, Output};
+
+const STARWARS_SCHEMA: &str = "../schemas/starwars.schema.graphql";
+const BOOKS_INTROSPECTION: &str = "../schemas/books.introspection.json";
+const SANITY_QUERY: &str = "../cynic-querygen/tests/queries/starwars/sanity.graphql";
+
+fn cynic(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cynic"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_introspect_from_file() {
+    let output = cynic(&["introspect", BOOKS_INTROSPECTION]);
+
+    assert!(output.status.success());
+    let sdl = String::from_utf8(output.stdout).unwrap();
+    assert!(sdl.contains("type QueryRoot {\n  books: [Book!]!\n}"));
+}
+
+#[test]
+fn test_querygen() {
+    let output = cynic(&[
+        "querygen",
+        "--schema",
+        STARWARS_SCHEMA,
+        "--schema-path",
+        "schema.graphql",
+        SANITY_QUERY,
+    ]);
+
+    assert!(output.status.success());
+    insta::assert_snapshot!(String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn test_check_valid_query() {
+    let output = cynic(&["check", "--schema", STARWARS_SCHEMA, SANITY_QUERY]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}: ok\n", SANITY_QUERY)
+    );
+}
+
+#[test]
+fn test_check_invalid_query() {
+    let path = std::env::temp_dir().join("cynic-cli-invalid-query.graphql");
+    std::fs::write(&path, "query { film(id: \"1\") { notAField } }").unwrap();
+
+    let output = cynic(&["check", "--schema", STARWARS_SCHEMA, path.to_str().unwrap()]);
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("could not find field `notAField` on `Film`"));
+    assert!(stderr.contains("error: 1 of 1 documents failed validation"));
+}
+
+#[test]
+fn test_missing_schema() {
+    let output = cynic(&["check", "--schema", "nope.graphql", SANITY_QUERY]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("error: could not access nope.graphql"));
+}
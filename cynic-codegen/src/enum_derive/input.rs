@@ -4,7 +4,7 @@ use proc_macro2::Span;
 use crate::ident::RenameAll;
 
 #[derive(darling::FromDeriveInput)]
-#[darling(attributes(cynic), supports(enum_unit))]
+#[darling(attributes(cynic), supports(enum_unit, enum_newtype))]
 pub struct EnumDeriveInput {
     pub(super) ident: proc_macro2::Ident,
     pub(super) data: darling::ast::Data<EnumDeriveVariant, ()>,
@@ -42,8 +42,13 @@ impl EnumDeriveInput {
 pub struct EnumDeriveVariant {
     pub(super) ident: proc_macro2::Ident,
 
+    pub(super) fields: darling::ast::Fields<syn::Type>,
+
     #[darling(default)]
     pub(super) rename: Option<SpannedValue<String>>,
+
+    #[darling(default)]
+    pub(super) fallback: SpannedValue<bool>,
 }
 
 impl EnumDeriveInput {
@@ -68,6 +68,8 @@ pub fn enum_derive_impl(
     let rename_all = input.rename_all.unwrap_or(RenameAll::ScreamingSnakeCase);
 
     if let darling::ast::Data::Enum(variants) = &input.data {
+        let fallback = check_fallback(variants)?;
+
         let pairs = match join_variants(
             variants,
             enum_def,
@@ -89,8 +91,50 @@ pub fn enum_derive_impl(
         let variants: Vec<_> = pairs.iter().map(|(variant, _)| &variant.ident).collect();
 
         let schema_module = Ident::for_module(&input.schema_module());
+        let expecting =
+            proc_macro2::Literal::string(&format!("a {} value", input.graphql_type_name()));
         let ident = input.ident;
 
+        let select_fallback = match &fallback {
+            Some((variant, true)) => quote! {
+                _ => ::cynic::selection_set::succeed(#ident::#variant(s))
+            },
+            Some((variant, false)) => quote! {
+                _ => ::cynic::selection_set::succeed(#ident::#variant)
+            },
+            None => quote! {
+                _ => ::cynic::selection_set::fail(format!("Unknown variant: {}", &s))
+            },
+        };
+
+        let serialize_fallback = fallback.as_ref().map(|(variant, has_value)| {
+            let error = proc_macro2::Literal::string(&format!(
+                "{}::{} is a fallback variant and can't be serialized",
+                ident, variant
+            ));
+            let pattern = if *has_value {
+                quote! { #ident::#variant(_) }
+            } else {
+                quote! { #ident::#variant }
+            };
+
+            quote! {
+                #pattern => Err(<S::Error as ::cynic::serde::ser::Error>::custom(#error)),
+            }
+        });
+
+        let deserialize_fallback = match &fallback {
+            Some((variant, true)) => quote! {
+                _ => Ok(#ident::#variant(value.to_string()))
+            },
+            Some((variant, false)) => quote! {
+                _ => Ok(#ident::#variant)
+            },
+            None => quote! {
+                _ => Err(E::unknown_variant(value, &[#(#string_literals),*]))
+            },
+        };
+
         Ok(quote! {
             #[automatically_derived]
             impl ::cynic::Enum<#schema_module::#enum_marker_ident> for #ident {
@@ -100,7 +144,7 @@ pub fn enum_derive_impl(
                             #(
                                 #string_literals => ::cynic::selection_set::succeed(#ident::#variants),
                             )*
-                            _ => ::cynic::selection_set::fail(format!("Unknown variant: {}", &s))
+                            #select_fallback
                         }
                     })
                 }
@@ -115,7 +159,38 @@ pub fn enum_derive_impl(
                             #(
                                 #ident::#variants => serializer.serialize_str(#string_literals),
                             )*
+                            #serialize_fallback
+                        }
+                    }
+            }
+
+            #[automatically_derived]
+            impl<'de> ::cynic::serde::Deserialize<'de> for #ident {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: ::cynic::serde::Deserializer<'de> {
+                        struct Visitor;
+
+                        impl<'de> ::cynic::serde::de::Visitor<'de> for Visitor {
+                            type Value = #ident;
+
+                            fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                                formatter.write_str(#expecting)
+                            }
+
+                            fn visit_str<E>(self, value: &str) -> Result<#ident, E>
+                            where
+                                E: ::cynic::serde::de::Error {
+                                    match value {
+                                        #(
+                                            #string_literals => Ok(#ident::#variants),
+                                        )*
+                                        #deserialize_fallback
+                                    }
+                                }
                         }
+
+                        deserializer.deserialize_str(Visitor)
                     }
             }
 
@@ -137,7 +212,7 @@ fn join_variants<'a>(
     enum_span: &Span,
 ) -> Result<Vec<(&'a EnumDeriveVariant, &'a EnumValue)>, TokenStream> {
     let mut map = HashMap::new();
-    for variant in variants {
+    for variant in variants.iter().filter(|v| !*v.fallback) {
         let graphql_name = Ident::from_proc_macro2(
             &variant.ident,
             RenameRule::new(rename_all, variant.rename.as_ref()),
@@ -199,6 +274,78 @@ fn join_variants<'a>(
         .collect())
 }
 
+/// Checks the fallback variant of an enum (if any), returning its ident &
+/// whether it has a field to store unknown values in.
+fn check_fallback(
+    variants: &[EnumDeriveVariant],
+) -> Result<Option<(&proc_macro2::Ident, bool)>, syn::Error> {
+    let mut fallback = None;
+
+    for variant in variants {
+        if !*variant.fallback {
+            if !variant.fields.is_empty() {
+                return Err(syn::Error::new(
+                    variant.ident.span(),
+                    "Enum variants can't have fields unless they're the fallback variant",
+                ));
+            }
+            continue;
+        }
+
+        if fallback.is_some() {
+            return Err(syn::Error::new(
+                variant.fallback.span(),
+                "Enums can't have more than one fallback",
+            ));
+        }
+
+        if variant.rename.is_some() {
+            return Err(syn::Error::new(
+                variant.fallback.span(),
+                "A fallback variant can't be renamed, as it's used for any unknown value",
+            ));
+        }
+
+        if !variant.fields.is_empty() {
+            check_fallback_field(variant)?;
+        }
+
+        fallback = Some((&variant.ident, !variant.fields.is_empty()));
+    }
+
+    Ok(fallback)
+}
+
+/// Checks that a fallback variant with fields has a single `String` field to
+/// store unknown values in.
+fn check_fallback_field(variant: &EnumDeriveVariant) -> Result<(), syn::Error> {
+    use syn::spanned::Spanned;
+
+    const MESSAGE: &str =
+        "A fallback variant can only have a single String field, to store unknown values in";
+
+    if !variant.fields.is_newtype() {
+        return Err(syn::Error::new(variant.ident.span(), MESSAGE));
+    }
+
+    let field_type = &variant.fields.fields[0];
+    let is_string = match field_type {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "String" && segment.arguments.is_empty())
+            .unwrap_or(false),
+        _ => false,
+    };
+
+    if !is_string {
+        return Err(syn::Error::new(field_type.span(), MESSAGE));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -232,11 +379,15 @@ mod tests {
         let variants = vec![
             EnumDeriveVariant {
                 ident: proc_macro2::Ident::new(&enum_variant_1, Span::call_site()),
+                fields: darling::ast::Style::Unit.into(),
                 rename: None,
+                fallback: Default::default(),
             },
             EnumDeriveVariant {
                 ident: proc_macro2::Ident::new(&enum_variant_2, Span::call_site()),
+                fields: darling::ast::Style::Unit.into(),
                 rename: None,
+                fallback: Default::default(),
             },
         ];
         let mut gql_enum = EnumType::new("Desserts".into());
@@ -272,11 +423,15 @@ mod tests {
         let variants = vec![
             EnumDeriveVariant {
                 ident: proc_macro2::Ident::new("Cheesecake", Span::call_site()),
+                fields: darling::ast::Style::Unit.into(),
                 rename: None,
+                fallback: Default::default(),
             },
             EnumDeriveVariant {
                 ident: proc_macro2::Ident::new("IceCream", Span::call_site()),
+                fields: darling::ast::Style::Unit.into(),
                 rename: Some(SpannedValue::new("iced-goodness".into(), Span::call_site())),
+                fallback: Default::default(),
             },
         ];
         let mut gql_enum = EnumType::new("Desserts".into());
@@ -311,7 +466,9 @@ mod tests {
     fn join_variants_missing_rust_variant() {
         let variants = vec![EnumDeriveVariant {
             ident: proc_macro2::Ident::new("CHEESECAKE", Span::call_site()),
+            fields: darling::ast::Style::Unit.into(),
             rename: None,
+            fallback: Default::default(),
         }];
         let mut gql_enum = EnumType::new("Desserts".into());
         gql_enum.values.push(EnumValue::new("CHEESECAKE".into()));
@@ -332,7 +489,9 @@ mod tests {
     fn join_variants_missing_gql_variant() {
         let variants = vec![EnumDeriveVariant {
             ident: proc_macro2::Ident::new("CHEESECAKE", Span::call_site()),
+            fields: darling::ast::Style::Unit.into(),
             rename: None,
+            fallback: Default::default(),
         }];
         let mut gql_enum = EnumType::new("Desserts".into());
         gql_enum.values.push(EnumValue::new("ICE_CREAM".into()));
@@ -347,4 +506,35 @@ mod tests {
 
         assert_matches!(result, Err(_));
     }
+
+    #[test]
+    fn join_variants_fallback_doesnt_count_as_a_variant() {
+        let variants = vec![
+            EnumDeriveVariant {
+                ident: proc_macro2::Ident::new("CHEESECAKE", Span::call_site()),
+                fields: darling::ast::Style::Unit.into(),
+                rename: None,
+                fallback: Default::default(),
+            },
+            EnumDeriveVariant {
+                ident: proc_macro2::Ident::new("ICE_CREAM", Span::call_site()),
+                fields: darling::ast::Style::Unit.into(),
+                rename: None,
+                fallback: SpannedValue::new(true, Span::call_site()),
+            },
+        ];
+        let mut gql_enum = EnumType::new("Desserts".into());
+        gql_enum.values.push(EnumValue::new("CHEESECAKE".into()));
+        gql_enum.values.push(EnumValue::new("ICE_CREAM".into()));
+
+        let result = join_variants(
+            &variants,
+            &gql_enum,
+            "Desserts",
+            RenameAll::None,
+            &Span::call_site(),
+        );
+
+        assert_matches!(result, Err(_));
+    }
 }
@@ -204,6 +204,16 @@ impl FieldType {
         }
     }
 
+    pub fn as_nullable(&self) -> Self {
+        match self {
+            FieldType::List(inner, _) => FieldType::List(inner.clone(), true),
+            FieldType::Scalar(type_path, _) => FieldType::Scalar(type_path.clone(), true),
+            FieldType::Enum(type_path, _) => FieldType::Enum(type_path.clone(), true),
+            FieldType::InputObject(type_path, _) => FieldType::InputObject(type_path.clone(), true),
+            FieldType::Other(type_path, _) => FieldType::Other(type_path.clone(), true),
+        }
+    }
+
     /// Generates a call to selection set functions for this type.
     ///
     /// Where inner_select is a call to the sub-fields to select (or the scalar
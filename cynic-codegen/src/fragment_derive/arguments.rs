@@ -34,6 +34,105 @@ impl Parse for CynicArguments {
 pub struct FieldArgument {
     pub argument_name: Ident,
     pub expr: syn::Expr,
+    /// The name of this argument in the schema.  This is filled in once the
+    /// argument has been validated against the schema.
+    pub graphql_name: Option<String>,
+}
+
+impl FieldArgument {
+    pub fn source(&self) -> ArgumentSource {
+        ArgumentSource::from_expr(&self.expr)
+    }
+}
+
+/// Where the value of an argument (or directive argument) comes from.
+///
+/// This determines how cynic provides the value in the resulting query.
+#[derive(PartialEq, Debug, Clone)]
+pub enum ArgumentSource {
+    /// A field of the arguments struct, e.g. `args.film_id`.  These become
+    /// variables named after the field.
+    ArgumentsField(Ident),
+    /// A literal, e.g. `1` or `"hello"`.  These are written straight into the
+    /// query.
+    Literal,
+    /// Any other expression.
+    Expression,
+}
+
+impl ArgumentSource {
+    pub fn from_expr(expr: &Expr) -> ArgumentSource {
+        match expr {
+            Expr::Reference(reference) => ArgumentSource::from_expr(&reference.expr),
+            Expr::Paren(paren) => ArgumentSource::from_expr(&paren.expr),
+            Expr::Group(group) => ArgumentSource::from_expr(&group.expr),
+            Expr::MethodCall(call)
+                if call.args.is_empty()
+                    && ["clone", "into", "to_owned", "to_string"]
+                        .iter()
+                        .any(|method| call.method == method) =>
+            {
+                ArgumentSource::from_expr(&call.receiver)
+            }
+            Expr::Call(call) if call.args.len() == 1 && is_path(&call.func, "Some") => {
+                ArgumentSource::from_expr(&call.args[0])
+            }
+            Expr::Field(field) if is_path(&field.base, "args") => match &field.member {
+                syn::Member::Named(name) => ArgumentSource::ArgumentsField(name.clone()),
+                syn::Member::Unnamed(_) => ArgumentSource::Expression,
+            },
+            Expr::Lit(lit) if is_literal(&lit.lit) => ArgumentSource::Literal,
+            Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Neg(_),
+                expr,
+                ..
+            }) => match expr.as_ref() {
+                Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(_),
+                    ..
+                })
+                | Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Float(_),
+                    ..
+                }) => ArgumentSource::Literal,
+                _ => ArgumentSource::Expression,
+            },
+            _ => ArgumentSource::Expression,
+        }
+    }
+}
+
+impl quote::ToTokens for ArgumentSource {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        use inflector::Inflector;
+        use quote::{quote, TokenStreamExt};
+
+        tokens.append_all(match self {
+            ArgumentSource::ArgumentsField(field) => {
+                let field = field.to_string();
+                let variable_name = field.strip_prefix("r#").unwrap_or(&field).to_camel_case();
+                quote! {
+                    ::cynic::ArgumentSource::Variable(#variable_name.to_string())
+                }
+            }
+            ArgumentSource::Literal => quote! { ::cynic::ArgumentSource::Literal },
+            ArgumentSource::Expression => quote! { ::cynic::ArgumentSource::ArgumentName },
+        });
+    }
+}
+
+fn is_path(expr: &Expr, ident: &str) -> bool {
+    match expr {
+        Expr::Path(path) => path.qself.is_none() && path.path.is_ident(ident),
+        _ => false,
+    }
+}
+
+fn is_literal(lit: &syn::Lit) -> bool {
+    matches!(
+        lit,
+        syn::Lit::Str(_) | syn::Lit::Int(_) | syn::Lit::Float(_) | syn::Lit::Bool(_)
+    )
 }
 
 impl Parse for FieldArgument {
@@ -45,6 +144,7 @@ impl Parse for FieldArgument {
         Ok(FieldArgument {
             argument_name,
             expr, //expr.try_into()?,
+            graphql_name: None,
         })
     }
 }
@@ -96,4 +196,43 @@ mod test {
         assert_matches!(arguments[1].expr, ArgumentExpression::FieldAccess(_));
         */
     }
+
+    #[test]
+    fn test_argument_sources() {
+        let parsed: CynicArguments = parse_quote! {
+            a = args.film_id,
+            b = &args.film_id,
+            c = args.film_id.clone(),
+            d = Some(args.film_id),
+            e = 1,
+            f = -1.5,
+            g = "hello".to_string(),
+            h = Some(true),
+            i = cynic::Id::new("1"),
+            j = args.film_id.unwrap_or(1)
+        };
+
+        let sources = parsed
+            .arguments
+            .iter()
+            .map(FieldArgument::source)
+            .collect::<Vec<_>>();
+
+        let film_id = ArgumentSource::ArgumentsField(parse_quote! { film_id });
+        assert_eq!(
+            sources,
+            vec![
+                film_id.clone(),
+                film_id.clone(),
+                film_id.clone(),
+                film_id,
+                ArgumentSource::Literal,
+                ArgumentSource::Literal,
+                ArgumentSource::Literal,
+                ArgumentSource::Literal,
+                ArgumentSource::Expression,
+                ArgumentSource::Expression,
+            ]
+        );
+    }
 }
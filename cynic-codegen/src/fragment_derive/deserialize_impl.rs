@@ -0,0 +1,390 @@
+use proc_macro2::TokenStream;
+
+use super::{input::FragmentDeriveField, schema_parsing::Object, type_ext::SynTypeExt};
+use crate::{Ident, TypePath};
+
+/// Generates a `serde::Deserialize` impl for a QueryFragment, which decodes
+/// the response to the fragments selection set.
+pub struct DeserializeImpl {
+    target_struct: Ident,
+    graphql_type_name: String,
+    fields: Vec<DeserializeField>,
+}
+
+struct DeserializeField {
+    ident: proc_macro2::Ident,
+    ty: syn::Type,
+    kind: FieldKind,
+}
+
+enum FieldKind {
+    /// A field that's found under `response_key` in the response.
+    Field {
+        response_key: String,
+        /// Whether the field can be missing from the response, which is the
+        /// case for fields with directives & recursive fields.
+        omittable: bool,
+        /// The type to deserialize from the response, which `conversion`
+        /// turns into the type of the field.
+        value_type: TokenStream,
+        conversion: Conversion,
+    },
+    /// A field that's spread into this fragment, and is deserialized from
+    /// the same object.
+    Spread,
+}
+
+/// How to get from the type we deserialize to the type of a field.
+enum Conversion {
+    None,
+    /// The field is flattened from `value_type`.
+    Flatten,
+    /// The field contains scalars, which are deserialized through
+    /// `DeserializeScalar` so they go through `Scalar::from_deserialize`.
+    Scalars,
+    /// The field contains scalars & is flattened from `unflattened_type`.
+    ScalarsThenFlatten {
+        unflattened_type: TokenStream,
+    },
+}
+
+impl Conversion {
+    fn convert(&self, value_type: &TokenStream, value: TokenStream) -> TokenStream {
+        use quote::quote;
+
+        match self {
+            Conversion::None => value,
+            Conversion::Flatten => quote! {
+                ::cynic::utils::FlattenInto::flatten_into(#value)
+            },
+            Conversion::Scalars => quote! {
+                ::cynic::private::IntoScalars::into_scalars(#value)
+            },
+            Conversion::ScalarsThenFlatten { unflattened_type } => quote! {
+                ::cynic::utils::FlattenInto::flatten_into(
+                    <#value_type as ::cynic::private::IntoScalars<#unflattened_type>>::into_scalars(
+                        #value
+                    )
+                )
+            },
+        }
+    }
+}
+
+impl DeserializeImpl {
+    /// Creates a `DeserializeImpl` for some fields that have already been
+    /// validated against `object`.
+    pub fn new(
+        target_struct: Ident,
+        graphql_type_name: &str,
+        fields: &[FragmentDeriveField],
+        object: &Object,
+        schema_module_path: &TypePath,
+    ) -> Self {
+        use quote::quote;
+
+        let fields = fields
+            .iter()
+            .filter_map(|field| {
+                let (ident, graphql_ident) = field.ident.as_ref().zip(field.graphql_ident())?;
+
+                let kind = if *field.spread {
+                    FieldKind::Spread
+                } else {
+                    let gql_field = object.fields.get(&graphql_ident)?;
+                    let field_type = &gql_field.field_type;
+                    let inner_type = field.ty.inner_type();
+                    let ty = &field.ty;
+
+                    let deserialize_scalar = field_type.contains_scalar().then(|| {
+                        let type_lock = field_type.as_type_lock(schema_module_path.clone());
+                        quote! { ::cynic::private::DeserializeScalar<#inner_type, #type_lock> }
+                    });
+
+                    let (value_type, conversion) = match (*field.flatten, deserialize_scalar) {
+                        (false, None) => (quote! { #ty }, Conversion::None),
+                        (true, None) => (
+                            field_type.decodes_to(quote! { #inner_type }),
+                            Conversion::Flatten,
+                        ),
+                        (false, Some(deserialize_scalar)) => {
+                            let value_type = field.ty.replace_inner_type(deserialize_scalar);
+                            (quote! { #value_type }, Conversion::Scalars)
+                        }
+                        (true, Some(deserialize_scalar)) => (
+                            field_type.decodes_to(deserialize_scalar),
+                            Conversion::ScalarsThenFlatten {
+                                unflattened_type: field_type.decodes_to(quote! { #inner_type }),
+                            },
+                        ),
+                    };
+
+                    FieldKind::Field {
+                        response_key: field
+                            .alias()
+                            .unwrap_or_else(|| gql_field.graphql_name.clone()),
+                        omittable: field.recurse.is_some() || has_directives(field),
+                        value_type,
+                        conversion,
+                    }
+                };
+
+                Some(DeserializeField {
+                    ident: ident.clone(),
+                    ty: field.ty.clone(),
+                    kind,
+                })
+            })
+            .collect();
+
+        DeserializeImpl {
+            target_struct,
+            graphql_type_name: graphql_type_name.to_string(),
+            fields,
+        }
+    }
+
+    fn has_spreads(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|field| matches!(field.kind, FieldKind::Spread))
+    }
+
+    /// The body of `deserialize` for fragments without any spreads, which
+    /// visits each entry of the response object in turn.
+    fn visitor_body(&self) -> TokenStream {
+        use quote::{format_ident, quote};
+
+        let target_struct = &self.target_struct;
+        let expecting =
+            proc_macro2::Literal::string(&format!("a {} object", self.graphql_type_name));
+
+        let idents = self.fields.iter().map(|f| &f.ident).collect::<Vec<_>>();
+        let types = self.fields.iter().map(|f| &f.ty);
+
+        let mut variants = vec![];
+        let mut response_keys = vec![];
+        let mut next_values = vec![];
+        let mut finishers = vec![];
+        for (i, field) in self.fields.iter().enumerate() {
+            if let FieldKind::Field {
+                response_key,
+                omittable,
+                value_type,
+                conversion,
+            } = &field.kind
+            {
+                let ident = &field.ident;
+                let variant = format_ident!("__field{}", i);
+                let key = proc_macro2::Literal::string(response_key);
+
+                next_values.push(
+                    conversion.convert(value_type, quote! { __map.next_value::<#value_type>()? }),
+                );
+                finishers.push(if *omittable {
+                    quote! { let #ident = #ident.unwrap_or_default(); }
+                } else {
+                    quote! {
+                        let #ident = #ident.ok_or_else(|| {
+                            <__A::Error as ::cynic::serde::de::Error>::missing_field(#key)
+                        })?;
+                    }
+                });
+                variants.push(variant);
+                response_keys.push(key);
+            }
+        }
+
+        quote! {
+            #[allow(non_camel_case_types)]
+            enum __Field {
+                #(#variants,)*
+                __ignore,
+            }
+
+            struct __FieldVisitor;
+
+            impl<'de> ::cynic::serde::de::Visitor<'de> for __FieldVisitor {
+                type Value = __Field;
+
+                fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    formatter.write_str("a field name")
+                }
+
+                fn visit_str<__E>(self, value: &str) -> Result<__Field, __E>
+                where
+                    __E: ::cynic::serde::de::Error,
+                {
+                    Ok(match value {
+                        #(#response_keys => __Field::#variants,)*
+                        _ => __Field::__ignore,
+                    })
+                }
+            }
+
+            impl<'de> ::cynic::serde::Deserialize<'de> for __Field {
+                fn deserialize<__D>(deserializer: __D) -> Result<Self, __D::Error>
+                where
+                    __D: ::cynic::serde::Deserializer<'de>,
+                {
+                    deserializer.deserialize_identifier(__FieldVisitor)
+                }
+            }
+
+            struct __Visitor;
+
+            impl<'de> ::cynic::serde::de::Visitor<'de> for __Visitor {
+                type Value = #target_struct;
+
+                fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    formatter.write_str(#expecting)
+                }
+
+                fn visit_map<__A>(self, mut __map: __A) -> Result<#target_struct, __A::Error>
+                where
+                    __A: ::cynic::serde::de::MapAccess<'de>,
+                {
+                    #(
+                        let mut #idents: Option<#types> = None;
+                    )*
+
+                    while let Some(key) = __map.next_key::<__Field>()? {
+                        match key {
+                            #(
+                                __Field::#variants => {
+                                    if #idents.is_some() {
+                                        return Err(
+                                            <__A::Error as ::cynic::serde::de::Error>::duplicate_field(
+                                                #response_keys
+                                            )
+                                        );
+                                    }
+                                    #idents = Some(#next_values);
+                                }
+                            )*
+                            __Field::__ignore => {
+                                __map.next_value::<::cynic::serde::de::IgnoredAny>()?;
+                            }
+                        }
+                    }
+
+                    #(#finishers)*
+
+                    Ok(#target_struct {
+                        #(#idents),*
+                    })
+                }
+            }
+
+            deserializer.deserialize_map(__Visitor)
+        }
+    }
+
+    /// The body of `deserialize` for fragments with spreads.  Spread fields
+    /// are deserialized from the same object as this fragment, so we need to
+    /// buffer the object before deserializing anything.
+    fn buffered_body(&self) -> TokenStream {
+        use quote::quote;
+
+        let target_struct = &self.target_struct;
+        let expecting =
+            proc_macro2::Literal::string(&format!("a {} object", self.graphql_type_name));
+
+        let idents = self.fields.iter().map(|f| &f.ident);
+        let field_values = self.fields.iter().map(|field| {
+            let ident = &field.ident;
+            let ty = &field.ty;
+
+            match &field.kind {
+                FieldKind::Spread => quote! {
+                    let #ident = <#ty as ::cynic::serde::Deserialize>::deserialize(
+                        ::cynic::private::ContentRefDeserializer::<__D::Error>::new(&__content)
+                    )?;
+                },
+                FieldKind::Field {
+                    response_key,
+                    omittable,
+                    value_type,
+                    conversion,
+                } => {
+                    let key = proc_macro2::Literal::string(response_key);
+                    let value = conversion.convert(
+                        value_type,
+                        quote! {
+                            <#value_type as ::cynic::serde::Deserialize>::deserialize(
+                                ::cynic::private::ContentRefDeserializer::<__D::Error>::new(value)
+                            )?
+                        },
+                    );
+                    let missing = if *omittable {
+                        quote! { Default::default() }
+                    } else {
+                        quote! {
+                            return Err(
+                                <__D::Error as ::cynic::serde::de::Error>::missing_field(#key)
+                            )
+                        }
+                    };
+
+                    quote! {
+                        let #ident: #ty = match __content.get(#key) {
+                            Some(value) => #value,
+                            None => #missing,
+                        };
+                    }
+                }
+            }
+        });
+
+        quote! {
+            let __content = <::cynic::private::Content<'de> as ::cynic::serde::Deserialize>::deserialize(
+                deserializer
+            )?;
+
+            if !__content.is_map() {
+                return Err(<__D::Error as ::cynic::serde::de::Error>::invalid_type(
+                    ::cynic::serde::de::Unexpected::Other("non-object value"),
+                    &#expecting,
+                ));
+            }
+
+            #(#field_values)*
+
+            Ok(#target_struct {
+                #(#idents),*
+            })
+        }
+    }
+}
+
+fn has_directives(field: &FragmentDeriveField) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|attr| attr.path.is_ident("directives"))
+}
+
+impl quote::ToTokens for DeserializeImpl {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        use quote::{quote, TokenStreamExt};
+
+        let target_struct = &self.target_struct;
+        let body = if self.has_spreads() {
+            self.buffered_body()
+        } else {
+            self.visitor_body()
+        };
+
+        tokens.append_all(quote! {
+            #[automatically_derived]
+            impl<'de> ::cynic::serde::Deserialize<'de> for #target_struct {
+                fn deserialize<__D>(deserializer: __D) -> Result<Self, __D::Error>
+                where
+                    __D: ::cynic::serde::Deserializer<'de>,
+                {
+                    #body
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,118 @@
+use super::arguments::ArgumentSource;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Expr, Ident, Result, Token,
+};
+
+pub fn directives_from_field_attrs(attrs: &[syn::Attribute]) -> Result<Vec<FieldDirective>> {
+    let mut directives = vec![];
+    for attr in attrs {
+        if attr.path.is_ident("directives") {
+            let parsed: CynicDirectives = attr.parse_args()?;
+            directives.extend(parsed.directives);
+        }
+    }
+    Ok(directives)
+}
+
+/// Implements syn::Parse to parse out directives from the directives
+/// attribute.
+#[derive(PartialEq, Debug)]
+struct CynicDirectives {
+    directives: Punctuated<FieldDirective, Token![,]>,
+}
+
+impl Parse for CynicDirectives {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(CynicDirectives {
+            directives: Punctuated::parse_terminated(input)?,
+        })
+    }
+}
+
+/// A directive that should be applied to a field, e.g. `skip(if = args.skip)`
+#[derive(PartialEq, Debug, Clone)]
+pub enum FieldDirective {
+    Skip(Expr),
+    Include(Expr),
+}
+
+impl Parse for FieldDirective {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+
+        let content;
+        syn::parenthesized!(content in input);
+        content.parse::<Token![if]>()?;
+        content.parse::<Token![=]>()?;
+        let condition: Expr = content.parse()?;
+
+        if name == "skip" {
+            Ok(FieldDirective::Skip(condition))
+        } else if name == "include" {
+            Ok(FieldDirective::Include(condition))
+        } else {
+            Err(syn::Error::new(
+                name.span(),
+                format!(
+                    "Unknown directive: {}.  Only skip & include are supported",
+                    name
+                ),
+            ))
+        }
+    }
+}
+
+impl FieldDirective {
+    /// Wraps a call to a field selector in a call that applies this directive.
+    pub fn wrap_call(&self, inner_call: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        use quote::quote;
+
+        match self {
+            FieldDirective::Skip(condition) => {
+                let source = ArgumentSource::from_expr(condition);
+                quote! {
+                    ::cynic::selection_set::skip_with_source(#condition, #source, #inner_call)
+                }
+            }
+            FieldDirective::Include(condition) => {
+                let source = ArgumentSource::from_expr(condition);
+                quote! {
+                    ::cynic::selection_set::include_with_source(#condition, #source, #inner_call)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parsing_directives() {
+        let parsed: CynicDirectives = parse_quote! { skip(if = args.hide), include(if = true) };
+
+        let directives = parsed.directives.into_iter().collect::<Vec<_>>();
+
+        assert_eq!(
+            directives,
+            vec![
+                FieldDirective::Skip(parse_quote! { args.hide }),
+                FieldDirective::Include(parse_quote! { true })
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parsing_unknown_directive() {
+        let result = syn::parse2::<CynicDirectives>(quote::quote! { deprecated(if = true) });
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Unknown directive: deprecated.  Only skip & include are supported"
+        );
+    }
+}
@@ -23,6 +23,10 @@ pub struct FragmentDeriveInput {
     pub graphql_type: Option<SpannedValue<String>>,
     #[darling(default)]
     pub argument_struct: Option<syn::Ident>,
+    #[darling(default)]
+    pub operation_name: Option<SpannedValue<String>>,
+    #[darling(default)]
+    pub max_depth: Option<SpannedValue<usize>>,
 }
 
 impl FragmentDeriveInput {
@@ -44,6 +48,13 @@ impl FragmentDeriveInput {
             .unwrap_or_else(|| self.ident.to_string())
     }
 
+    pub fn operation_name(&self) -> String {
+        self.operation_name
+            .as_ref()
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| self.ident.to_string())
+    }
+
     pub fn graphql_type_span(&self) -> Span {
         self.graphql_type
             .as_ref()
@@ -64,6 +75,24 @@ impl FragmentDeriveInput {
             .into());
         }
 
+        if let Some(operation_name) = &self.operation_name {
+            if !is_valid_graphql_name(operation_name) {
+                return Err(syn::Error::new(
+                    operation_name.span(),
+                    format!("{} is not a valid GraphQL name", operation_name.as_str()),
+                )
+                .into());
+            }
+        }
+
+        if let Some(max_depth) = &self.max_depth {
+            if **max_depth == 0 {
+                return Err(
+                    syn::Error::new(max_depth.span(), "max_depth must be at least 1").into(),
+                );
+            }
+        }
+
         let errors = self
             .data
             .clone()
@@ -98,8 +127,18 @@ impl FragmentDeriveInput {
     }
 }
 
+fn is_valid_graphql_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c == '_' || c.is_ascii_alphabetic() => {}
+        _ => return false,
+    }
+
+    chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
 #[derive(darling::FromField, Clone)]
-#[darling(attributes(cynic), forward_attrs(arguments))]
+#[darling(attributes(cynic), forward_attrs(arguments, directives))]
 pub struct FragmentDeriveField {
     pub(super) ident: Option<proc_macro2::Ident>,
     pub(super) ty: syn::Type,
@@ -246,6 +285,8 @@ mod tests {
             schema_module_: None,
             graphql_type: Some("abcd".to_string().into()),
             argument_struct: None,
+            operation_name: None,
+            max_depth: None,
         };
 
         assert_matches!(input.validate(), Ok(()));
@@ -325,6 +366,8 @@ mod tests {
             schema_module_: Some("abcd".to_string().into()),
             graphql_type: Some("abcd".to_string().into()),
             argument_struct: None,
+            operation_name: None,
+            max_depth: None,
         };
 
         let errors = input.validate().unwrap_err();
@@ -344,6 +387,8 @@ mod tests {
             schema_module_: Some("abcd".to_string().into()),
             graphql_type: Some("abcd".to_string().into()),
             argument_struct: None,
+            operation_name: None,
+            max_depth: None,
         };
         let errors = input.validate().unwrap_err();
         assert_eq!(
@@ -397,8 +442,48 @@ mod tests {
             schema_module_: Some("abcd".to_string().into()),
             graphql_type: None,
             argument_struct: None,
+            operation_name: None,
+            max_depth: None,
         };
 
         assert_matches!(input.validate(), Ok(()));
     }
+
+    #[test]
+    fn test_fragment_derive_validate_operation_name() {
+        let input = |operation_name: &str| FragmentDeriveInput {
+            ident: format_ident!("TestInput"),
+            data: darling::ast::Data::Struct(darling::ast::Fields::new(
+                darling::ast::Style::Struct,
+                vec![FragmentDeriveField {
+                    ident: Some(format_ident!("field_one")),
+                    ty: syn::parse_quote! { String },
+                    attrs: vec![],
+                    flatten: false.into(),
+                    recurse: None,
+                    spread: false.into(),
+                    rename: None,
+                    alias: false.into(),
+                }],
+            )),
+            schema_path: "abcd".to_string().into(),
+            query_module: None,
+            schema_module_: None,
+            graphql_type: None,
+            argument_struct: None,
+            operation_name: Some(operation_name.to_string().into()),
+            max_depth: None,
+        };
+
+        assert_matches!(input("GetFilm_2").validate(), Ok(()));
+        assert_matches!(input("_getFilm").validate(), Ok(()));
+
+        let errors = input("2Films").validate().unwrap_err();
+        assert_eq!(
+            errors.to_compile_errors().to_string(),
+            r#"compile_error ! { "2Films is not a valid GraphQL name" }"#.to_string()
+        );
+        assert!(input("Get Film").validate().is_err());
+        assert!(input("").validate().is_err());
+    }
 }
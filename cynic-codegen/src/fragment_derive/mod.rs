@@ -1,21 +1,28 @@
 use std::collections::{HashMap, HashSet};
 
+use darling::util::SpannedValue;
 use proc_macro2::{Span, TokenStream};
 use syn::spanned::Spanned;
 
 use crate::{
     load_schema,
-    type_validation::{check_spread_type, check_types_are_compatible, CheckMode},
+    type_validation::{
+        check_omittable_type, check_spread_type, check_types_are_compatible, CheckMode,
+    },
     Errors, FieldType, Ident, TypePath,
 };
 
 mod arguments;
+mod deserialize_impl;
+mod directives;
 mod schema_parsing;
 mod type_ext;
 
 pub(crate) mod input;
 
-use arguments::{arguments_from_field_attrs, FieldArgument};
+use arguments::{arguments_from_field_attrs, ArgumentSource, FieldArgument};
+use deserialize_impl::DeserializeImpl;
+use directives::{directives_from_field_attrs, FieldDirective};
 use schema_parsing::{Field, Object};
 use type_ext::SynTypeExt;
 
@@ -75,6 +82,8 @@ pub fn fragment_derive_impl(
     };
 
     let graphql_name = &(input.graphql_type_name());
+    let operation_name = input.operation_name();
+    let max_depth = input.max_depth.clone();
     let schema_module = input.schema_module();
     let ident = input.ident;
     if let darling::ast::Data::Struct(fields) = input.data {
@@ -84,6 +93,8 @@ pub fn fragment_derive_impl(
             &object,
             Ident::new_spanned(&*schema_module, schema_module.span()).into(),
             graphql_name,
+            operation_name,
+            max_depth,
             argument_struct,
         )?;
         Ok(quote::quote! {
@@ -196,6 +207,7 @@ impl FieldTypeSelectorCall {
 
         match self {
             FieldTypeSelectorCall::Field(type_path) => {
+                let argument_sources = argument_sources(required_arguments, optional_arguments);
                 let required_arguments = required_arguments.iter().map(|arg| &arg.expr);
                 let optional_arg_names = optional_arguments.iter().map(|arg| &arg.argument_name);
                 let optional_arg_exprs = optional_arguments.iter().map(|arg| &arg.expr);
@@ -208,9 +220,11 @@ impl FieldTypeSelectorCall {
                         .#optional_arg_names(#optional_arg_exprs)
                     )*
                     .select(#inner_selection_tokens)
+                    #argument_sources
                 }
             }
             FieldTypeSelectorCall::AliasedField(alias, type_path) => {
+                let argument_sources = argument_sources(required_arguments, optional_arguments);
                 let required_arguments = required_arguments.iter().map(|arg| &arg.expr);
                 let optional_arg_names = optional_arguments.iter().map(|arg| &arg.argument_name);
                 let optional_arg_exprs = optional_arguments.iter().map(|arg| &arg.expr);
@@ -224,6 +238,7 @@ impl FieldTypeSelectorCall {
                         .#optional_arg_names(#optional_arg_exprs)
                     )*
                     .select_aliased(#alias, #inner_selection_tokens)
+                    #argument_sources
                 }
             }
             FieldTypeSelectorCall::Opt(inner) => inner.to_call(
@@ -293,6 +308,34 @@ impl FieldTypeSelectorCall {
     }
 }
 
+/// Generates calls that tell cynic where the values of any arguments came
+/// from, so it can name their variables or write them into the query as
+/// literals.
+fn argument_sources(
+    required_arguments: &[FieldArgument],
+    optional_arguments: &[FieldArgument],
+) -> TokenStream {
+    use quote::quote;
+
+    let (names, sources): (Vec<_>, Vec<_>) = required_arguments
+        .iter()
+        .chain(optional_arguments)
+        .filter_map(|arg| {
+            let source = arg.source();
+            if source == ArgumentSource::Expression {
+                return None;
+            }
+            Some((arg.graphql_name.clone()?, source))
+        })
+        .unzip();
+
+    quote! {
+        #(
+            .with_argument_source(#names, #sources)
+        )*
+    }
+}
+
 /// The call style to use for a particular named type selector function
 enum NamedTypeSelectorStyle {
     QueryFragment(syn::Type),
@@ -305,10 +348,66 @@ struct FieldSelectorCall {
     style: NamedTypeSelectorStyle,
     required_arguments: Vec<FieldArgument>,
     optional_arguments: Vec<FieldArgument>,
+    directives: Vec<FieldDirective>,
+    field_nullable: bool,
     recurse_limit: Option<u8>,
     span: proc_macro2::Span,
 }
 
+impl FieldSelectorCall {
+    /// The depth of the query selected by this field, for use in a const
+    /// expression.
+    fn depth(&self) -> TokenStream {
+        use quote::{quote, quote_spanned};
+
+        let span = self.span;
+
+        match (&self.style, self.recurse_limit) {
+            // Recursive fields can't refer to the DEPTH of their own type
+            // without creating a cycle.  Instead we count each time the field
+            // can recurse as one level, and then add the depth of the
+            // innermost fragment with its recursive fields counted once.
+            (NamedTypeSelectorStyle::QueryFragment(field_type), Some(limit)) => {
+                let limit = proc_macro2::Literal::usize_unsuffixed(limit as usize);
+                quote_spanned! {span =>
+                    (#limit + <#field_type as ::cynic::QueryFragment>::NON_RECURSIVE_DEPTH)
+                        .saturating_sub(1)
+                }
+            }
+            _ => self.depth_from(quote! { DEPTH }),
+        }
+    }
+
+    /// The depth of the query selected by this field with recursive fields
+    /// counted as a single level, for use in a const expression.
+    fn non_recursive_depth(&self) -> TokenStream {
+        use quote::{quote, quote_spanned};
+
+        let span = self.span;
+
+        match self.recurse_limit {
+            Some(_) => quote_spanned! {span => 1 },
+            None => self.depth_from(quote! { NON_RECURSIVE_DEPTH }),
+        }
+    }
+
+    fn depth_from(&self, depth_const: TokenStream) -> TokenStream {
+        use quote::quote_spanned;
+
+        let span = self.span;
+
+        match (&self.style, &self.selector_function) {
+            (NamedTypeSelectorStyle::QueryFragment(field_type), FieldTypeSelectorCall::Spread) => {
+                quote_spanned! {span => <#field_type as ::cynic::QueryFragment>::#depth_const }
+            }
+            (NamedTypeSelectorStyle::QueryFragment(field_type), _) => {
+                quote_spanned! {span => 1 + <#field_type as ::cynic::QueryFragment>::#depth_const }
+            }
+            _ => quote_spanned! {span => 1 },
+        }
+    }
+}
+
 impl quote::ToTokens for FieldSelectorCall {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         use quote::{quote, quote_spanned, TokenStreamExt};
@@ -323,19 +422,32 @@ impl quote::ToTokens for FieldSelectorCall {
                 #enum_type::select()
             },
             (NamedTypeSelectorStyle::QueryFragment(field_type), None) => quote_spanned! {span =>
-                #field_type::fragment(context.with_args(FromArguments::from_arguments(args)))
+                <#field_type>::fragment(context.with_args(FromArguments::from_arguments(args)))
             },
             (NamedTypeSelectorStyle::QueryFragment(field_type), Some(_)) => quote_spanned! {span =>
-                #field_type::fragment(context.recurse().with_args(FromArguments::from_arguments(args)))
+                <#field_type>::fragment(context.recurse().with_args(FromArguments::from_arguments(args)))
             },
         };
 
-        let selector_function_call = &self.selector_function.to_call(
+        let mut selector_function_call = self.selector_function.to_call(
             &self.required_arguments,
             &self.optional_arguments,
             inner_selection_tokens,
         );
 
+        if !self.directives.is_empty() {
+            for directive in &self.directives {
+                selector_function_call = directive.wrap_call(selector_function_call);
+            }
+
+            // Each directive wraps the field in another Option, as does the
+            // field itself if it's nullable.  We flatten these all into one.
+            let flatten_count = self.directives.len() - 1 + usize::from(self.field_nullable);
+            for _ in 0..flatten_count {
+                selector_function_call = quote! { #selector_function_call.map(Option::flatten) };
+            }
+        }
+
         tokens.append_all(quote! {
             #selector_function_call
         });
@@ -367,20 +479,28 @@ struct FragmentImpl {
     constructor_params: Vec<ConstructorParameter>,
     argument_struct: syn::Type,
     graphql_type_name: String,
+    operation_name: String,
+    max_depth: Option<SpannedValue<usize>>,
+    deserialize_impl: DeserializeImpl,
 }
 
 impl FragmentImpl {
+    #[allow(clippy::too_many_arguments)]
     fn new_for(
         fields: &darling::ast::Fields<FragmentDeriveField>,
         name: &syn::Ident,
         object: &Object,
         schema_module_path: TypePath,
         graphql_type_name: &str,
+        operation_name: String,
+        max_depth: Option<SpannedValue<usize>>,
         argument_struct: syn::Type,
     ) -> Result<Self, syn::Error> {
         let target_struct = Ident::new_spanned(&name.to_string(), name.span());
-        let selector_struct_path =
-            TypePath::concat(&[schema_module_path, object.selector_struct.clone().into()]);
+        let selector_struct_path = TypePath::concat(&[
+            schema_module_path.clone(),
+            object.selector_struct.clone().into(),
+        ]);
 
         let (constructor_params, field_selectors) = fields
             .fields
@@ -390,6 +510,14 @@ impl FragmentImpl {
             .into_iter()
             .unzip();
 
+        let deserialize_impl = DeserializeImpl::new(
+            target_struct.clone(),
+            graphql_type_name,
+            &fields.fields,
+            object,
+            &schema_module_path,
+        );
+
         Ok(FragmentImpl {
             fields: field_selectors,
             target_struct,
@@ -397,6 +525,9 @@ impl FragmentImpl {
             constructor_params,
             argument_struct,
             graphql_type_name: graphql_type_name.to_string(),
+            operation_name,
+            max_depth,
+            deserialize_impl,
         })
     }
 }
@@ -419,6 +550,14 @@ fn process_field(
     };
 
     let arguments = arguments_from_field_attrs(&field.attrs)?;
+    let directives = directives_from_field_attrs(&field.attrs)?;
+
+    if !directives.is_empty() && field.type_check_mode() != CheckMode::Normal {
+        return Err(syn::Error::new(
+            field_name_span,
+            "Directives can't be used on fields that are flattened, recursed or spread",
+        ));
+    }
 
     if field.type_check_mode() == CheckMode::Spreading {
         check_spread_type(&field.ty)?;
@@ -428,13 +567,19 @@ fn process_field(
             style: NamedTypeSelectorStyle::QueryFragment(field.ty.clone()),
             required_arguments: vec![],
             optional_arguments: vec![],
+            directives: vec![],
+            field_nullable: false,
             recurse_limit: None,
             span: field.ty.span(),
         };
 
         Ok((constructor_param, field_selector))
     } else if let Some(gql_field) = object.fields.get(&graphql_ident) {
-        check_types_are_compatible(&gql_field.field_type, &field.ty, field.type_check_mode())?;
+        if directives.is_empty() {
+            check_types_are_compatible(&gql_field.field_type, &field.ty, field.type_check_mode())?;
+        } else {
+            check_omittable_type(&gql_field.field_type, &field.ty)?;
+        }
 
         let (required_arguments, optional_arguments) =
             validate_and_group_args(arguments, gql_field, field_name_span)?;
@@ -456,6 +601,8 @@ fn process_field(
             },
             required_arguments,
             optional_arguments,
+            directives,
+            field_nullable: gql_field.field_type.is_nullable(),
             recurse_limit: field.recurse.as_ref().map(|limit| **limit),
             span: field.ty.span(),
         };
@@ -479,7 +626,7 @@ fn process_field(
 
 impl quote::ToTokens for FragmentImpl {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        use quote::{quote, TokenStreamExt};
+        use quote::{quote, quote_spanned, TokenStreamExt};
 
         let argument_struct = &self.argument_struct;
         let target_struct = &self.target_struct;
@@ -487,6 +634,9 @@ impl quote::ToTokens for FragmentImpl {
         let fields = &self.fields;
         let constructor_params = &self.constructor_params;
         let graphql_type = proc_macro2::Literal::string(&self.graphql_type_name);
+        let operation_name = proc_macro2::Literal::string(&self.operation_name);
+        let fragment_name = proc_macro2::Literal::string(self.target_struct.graphql_name());
+        let deserialize_impl = &self.deserialize_impl;
         let constructor_param_names = self
             .constructor_params
             .iter()
@@ -494,6 +644,24 @@ impl quote::ToTokens for FragmentImpl {
             .collect::<Vec<_>>();
 
         let map_function = quote::format_ident!("map{}", fields.len());
+        let field_depths = fields.iter().map(FieldSelectorCall::depth);
+        let non_recursive_field_depths = fields.iter().map(FieldSelectorCall::non_recursive_depth);
+
+        let max_depth_assertion = self.max_depth.as_ref().map(|max_depth| {
+            let span = max_depth.span();
+            let limit = **max_depth;
+            let message = format!(
+                "The query selected by {} is deeper than its max_depth of {}",
+                self.target_struct.rust_name(),
+                limit
+            );
+            quote_spanned! {span =>
+                const _: () = assert!(
+                    <#target_struct as ::cynic::QueryFragment>::DEPTH <= #limit,
+                    #message
+                );
+            }
+        });
 
         tokens.append_all(quote! {
             #[automatically_derived]
@@ -501,6 +669,10 @@ impl quote::ToTokens for FragmentImpl {
                 type SelectionSet = ::cynic::SelectionSet<'static, Self, #selector_struct>;
                 type Arguments = #argument_struct;
 
+                const DEPTH: usize = ::cynic::analysis::deepest(&[#(#field_depths),*]);
+                const NON_RECURSIVE_DEPTH: usize =
+                    ::cynic::analysis::deepest(&[#(#non_recursive_field_depths),*]);
+
                 fn fragment(context: ::cynic::FragmentContext<Self::Arguments>) -> Self::SelectionSet {
                     use ::cynic::{QueryFragment, FromArguments, Enum};
 
@@ -510,18 +682,30 @@ impl quote::ToTokens for FragmentImpl {
                         #(#constructor_param_names),*
                     };
 
-                    ::cynic::selection_set::#map_function(
-                        new,
-                        #(
-                            #fields
-                        ),*
+                    ::cynic::selection_set::named_fragment(
+                        #fragment_name,
+                        #graphql_type,
+                        ::cynic::selection_set::#map_function(
+                            new,
+                            #(
+                                #fields
+                            ),*
+                        )
                     )
                 }
 
                 fn graphql_type() -> String {
                     #graphql_type.to_string()
                 }
+
+                fn operation_name() -> Option<&'static str> {
+                    Some(#operation_name)
+                }
             }
+
+            #max_depth_assertion
+
+            #deserialize_impl
         })
     }
 }
@@ -585,6 +769,10 @@ fn validate_and_group_args(
     let mut optionals = vec![];
     for schema_arg in &field.arguments {
         if let Some(provided_arg) = provided_arguments.get(&schema_arg.name) {
+            let provided_arg = FieldArgument {
+                graphql_name: Some(schema_arg.graphql_name.clone()),
+                ..(*provided_arg).clone()
+            };
             if schema_arg.required {
                 required.push(provided_arg);
             } else {
@@ -593,8 +781,5 @@ fn validate_and_group_args(
         }
     }
 
-    Ok((
-        required.into_iter().cloned().collect(),
-        optionals.into_iter().cloned().collect(),
-    ))
+    Ok((required, optionals))
 }
@@ -70,6 +70,7 @@ impl Object {
 pub struct Field {
     pub arguments: Vec<Argument>,
     pub name: Ident,
+    pub graphql_name: String,
     pub field_type: FieldType,
 }
 
@@ -77,6 +78,7 @@ impl Field {
     fn from_field(field: &schema::Field, type_index: &TypeIndex) -> Field {
         Field {
             name: Ident::for_field(&field.name),
+            graphql_name: field.name.clone(),
             field_type: FieldType::from_schema_type(&field.field_type, type_index),
             arguments: field
                 .arguments
@@ -90,6 +92,7 @@ impl Field {
 #[derive(Debug)]
 pub struct Argument {
     pub name: Ident,
+    pub graphql_name: String,
     pub required: bool,
 }
 
@@ -98,6 +101,7 @@ impl Argument {
         let argument_type = FieldType::from_schema_type(&value.value_type, type_index);
         Argument {
             name: Ident::for_field(&value.name),
+            graphql_name: value.name.clone(),
             required: !argument_type.is_nullable(),
         }
     }
@@ -7,6 +7,10 @@ pub trait SynTypeExt {
     /// This is useful when deriving QueryFragment as we need to know which
     /// type to call `QueryFragment::fragment` on for any nested fields.
     fn inner_type(&self) -> syn::Type;
+
+    /// Replaces the inner type (as returned by `inner_type`) of a syn::Type,
+    /// keeping any Options, Vectors & Boxes around it.
+    fn replace_inner_type(&self, new_inner: proc_macro2::TokenStream) -> syn::Type;
 }
 
 impl SynTypeExt for syn::Type {
@@ -28,6 +32,31 @@ impl SynTypeExt for syn::Type {
 
         self.clone()
     }
+
+    fn replace_inner_type(&self, new_inner: proc_macro2::TokenStream) -> syn::Type {
+        use syn::{GenericArgument, PathArguments, Type};
+
+        if let Type::Path(expr) = self {
+            if let Some(segment) = expr.path.segments.first() {
+                let ident_string = segment.ident.to_string();
+                if ident_string == "Option" || ident_string == "Vec" || ident_string == "Box" {
+                    if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                        if let Some(GenericArgument::Type(ty)) = args.args.first() {
+                            let mut expr = expr.clone();
+                            let segment = expr.path.segments.first_mut().unwrap();
+                            if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                                args.args[0] =
+                                    GenericArgument::Type(ty.replace_inner_type(new_inner));
+                            }
+                            return Type::Path(expr);
+                        }
+                    }
+                }
+            }
+        }
+
+        syn::parse2(new_inner).expect("replace_inner_type to be given a valid type")
+    }
 }
 
 #[cfg(test)]
@@ -46,4 +75,14 @@ mod tests {
     fn test_inner_type(outer: syn::Type, inner: syn::Type) {
         assert_eq!(outer.inner_type(), inner);
     }
+
+    #[rstest(outer, replaced,
+        case(parse_quote!{ Option<Vec<Option<T>>> }, parse_quote!{ Option<Vec<Option<U>>> }),
+        case(parse_quote!{ Vec<Box<T>> }, parse_quote!{ Vec<Box<U>> }),
+        case(parse_quote!{ Option<T> }, parse_quote!{ Option<U> }),
+        case(parse_quote!{ T }, parse_quote!{ U }),
+    )]
+    fn test_replace_inner_type(outer: syn::Type, replaced: syn::Type) {
+        assert_eq!(outer.replace_inner_type(quote::quote! { U }), replaced);
+    }
 }
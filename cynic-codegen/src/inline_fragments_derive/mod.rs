@@ -283,6 +283,12 @@ impl quote::ToTokens for InlineFragmentsImpl {
         let variants: Vec<_> = self.possible_types.iter().map(|(v, _)| v).collect();
         let graphql_type = proc_macro2::Literal::string(&self.graphql_type_name);
 
+        let depth_types = internal_types
+            .iter()
+            .copied()
+            .chain(self.fallback.as_ref().and_then(|(_, ty)| ty.as_ref()))
+            .collect::<Vec<_>>();
+
         let fallback_selection = if let Some((fallback_variant, fallback_type)) = &self.fallback {
             if let Some(fallback_type) = fallback_type {
                 quote! {
@@ -311,12 +317,39 @@ impl quote::ToTokens for InlineFragmentsImpl {
             quote! { None }
         };
 
+        let fallback_deserialize = match &self.fallback {
+            Some((fallback_variant, Some(fallback_type))) => quote! {
+                <#fallback_type as ::cynic::serde::Deserialize>::deserialize(deserializer)
+                    .map(#target_struct::#fallback_variant)
+            },
+            Some((fallback_variant, None)) => quote! {
+                <::cynic::serde::de::IgnoredAny as ::cynic::serde::Deserialize>::deserialize(
+                    deserializer
+                )?;
+                Ok(#target_struct::#fallback_variant)
+            },
+            None => quote! {
+                Err(<D::Error as ::cynic::serde::de::Error>::custom(
+                    format!("Unknown type: {}", typename)
+                ))
+            },
+        };
+
         tokens.append_all(quote! {
             #[automatically_derived]
             impl ::cynic::InlineFragments for #target_struct {
                 type TypeLock = #type_lock;
                 type Arguments = #arguments;
 
+                const DEPTH: usize = ::cynic::analysis::deepest(&[
+                    1,
+                    #(<#depth_types as ::cynic::QueryFragment>::DEPTH),*
+                ]);
+                const NON_RECURSIVE_DEPTH: usize = ::cynic::analysis::deepest(&[
+                    1,
+                    #(<#depth_types as ::cynic::QueryFragment>::NON_RECURSIVE_DEPTH),*
+                ]);
+
                 fn fragments(context: ::cynic::FragmentContext<'_, Self::Arguments>) ->
                     Vec<(String, ::cynic::SelectionSet<'static, Self, Self::TypeLock>)>
                 {
@@ -347,6 +380,35 @@ impl quote::ToTokens for InlineFragmentsImpl {
                     #fallback_selection
                 }
             }
+
+            #[automatically_derived]
+            impl<'de> ::cynic::serde::Deserialize<'de> for #target_struct {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: ::cynic::serde::Deserializer<'de>,
+                {
+                    ::cynic::private::deserialize_inline_fragments(deserializer)
+                }
+            }
+
+            #[automatically_derived]
+            impl<'de> ::cynic::private::DeserializeInlineFragment<'de> for #target_struct {
+                fn deserialize_variant<D>(typename: &str, deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: ::cynic::serde::Deserializer<'de>,
+                {
+                    use ::cynic::QueryFragment;
+
+                    #(
+                        if typename == #internal_types::graphql_type() {
+                            return <#internal_types as ::cynic::serde::Deserialize>::deserialize(deserializer)
+                                .map(#target_struct::#variants);
+                        }
+                    )*
+
+                    #fallback_deserialize
+                }
+            }
         });
     }
 }
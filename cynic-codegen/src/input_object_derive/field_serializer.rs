@@ -14,6 +14,7 @@ pub struct FieldSerializer<'a> {
     graphql_field: &'a InputValue,
     graphql_field_type: FieldType,
     query_module: &'a Ident,
+    one_of: bool,
 }
 
 impl<'a> FieldSerializer<'a> {
@@ -28,14 +29,51 @@ impl<'a> FieldSerializer<'a> {
             graphql_field,
             graphql_field_type: FieldType::from_schema_type(&graphql_field.value_type, &type_index),
             query_module,
+            one_of: false,
+        }
+    }
+
+    /// Creates a FieldSerializer for a variant of a oneOf InputObject enum.
+    ///
+    /// Only one field of a oneOf input object is provided at a time and it
+    /// can't be null, so these must contain the required version of the
+    /// fields type.
+    pub fn new_for_variant(
+        rust_field: &'a InputObjectDeriveField,
+        graphql_field: &'a InputValue,
+        type_index: &TypeIndex,
+        query_module: &'a Ident,
+    ) -> FieldSerializer<'a> {
+        FieldSerializer {
+            one_of: true,
+            ..FieldSerializer::new(rust_field, graphql_field, type_index, query_module)
+        }
+    }
+
+    /// The type that the rust field should have.
+    fn expected_type(&self) -> FieldType {
+        if self.one_of {
+            self.graphql_field_type.as_required()
+        } else {
+            self.graphql_field_type.clone()
         }
     }
 
     /// Validates the FieldSerializer definition, returning errors if there are any.
     pub fn validate(&self) -> Option<syn::Error> {
+        if self.one_of && !self.graphql_field_type.is_nullable() {
+            return Some(syn::Error::new(
+                self.rust_field.ident.span(),
+                format!(
+                    "The fields of a oneOf input object must be nullable, but {} is required",
+                    self.graphql_field.name
+                ),
+            ));
+        }
+
         // First, check for type errors
         if let Err(e) = check_types_are_compatible(
-            &self.graphql_field_type,
+            &self.expected_type(),
             &self.rust_field.ty,
             CheckMode::Normal,
         ) {
@@ -60,16 +98,15 @@ impl<'a> FieldSerializer<'a> {
         // So, we have to construct some functions with constraints
         // in order to make sure the fields are of the right type.
 
-        let type_lock = self
-            .graphql_field_type
-            .as_type_lock(self.query_module.clone().into());
-        let wrapper_type = self.graphql_field_type.wrapper_path().unwrap();
+        let expected_type = self.expected_type();
+        let type_lock = expected_type.as_type_lock(self.query_module.clone().into());
+        let wrapper_type = expected_type.wrapper_path().unwrap();
 
         let rust_field_name = &self.rust_field.ident;
         let graphql_field_name = proc_macro2::Literal::string(&self.graphql_field.name);
 
         quote! {
-            #[allow(clippy::ptr_arg)]
+            #[allow(clippy::ptr_arg, non_snake_case)]
             fn #rust_field_name<SM: ::cynic::serde::ser::SerializeMap>(
                 data: impl ::cynic::InputType<
                     #type_lock,
@@ -77,6 +114,10 @@ impl<'a> FieldSerializer<'a> {
                 >,
                 map_serializer: &mut SM
             ) -> Result<(), SM::Error> {
+                if ::cynic::InputType::is_undefined(&data) {
+                    return Ok(());
+                }
+
                 map_serializer.serialize_entry(
                     #graphql_field_name,
                     &data
@@ -106,4 +147,19 @@ impl<'a> FieldSerializer<'a> {
             insert_call
         }
     }
+
+    /// Outputs a match arm that serializes a variant of a oneOf InputObject
+    /// enum.
+    pub fn variant_insert_arm(
+        &self,
+        enum_ident: &proc_macro2::Ident,
+        serializer_ident: &proc_macro2::Ident,
+    ) -> TokenStream {
+        let variant_span = self.rust_field.ident.span();
+        let variant_name = &self.rust_field.ident;
+
+        quote_spanned! { variant_span =>
+            #enum_ident::#variant_name(value) => #variant_name(value, &mut #serializer_ident)?,
+        }
+    }
 }
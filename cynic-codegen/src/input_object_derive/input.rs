@@ -4,10 +4,10 @@ use crate::ident::RenameAll;
 use proc_macro2::Span;
 
 #[derive(darling::FromDeriveInput)]
-#[darling(attributes(cynic), supports(struct_named))]
+#[darling(attributes(cynic), supports(struct_named, enum_newtype))]
 pub struct InputObjectDeriveInput {
     pub(super) ident: proc_macro2::Ident,
-    pub(super) data: darling::ast::Data<(), InputObjectDeriveField>,
+    pub(super) data: darling::ast::Data<InputObjectDeriveVariant, InputObjectDeriveField>,
 
     pub schema_path: SpannedValue<String>,
 
@@ -40,6 +40,31 @@ pub struct InputObjectDeriveField {
     pub(super) rename: Option<SpannedValue<String>>,
 }
 
+/// A variant of an enum InputObject, which represents a oneOf input object.
+/// Each variant maps to one of the fields of the input object.
+#[derive(Debug, darling::FromVariant)]
+#[darling(attributes(cynic))]
+pub struct InputObjectDeriveVariant {
+    pub(super) ident: proc_macro2::Ident,
+    pub(super) fields: darling::ast::Fields<syn::Type>,
+
+    #[darling(default)]
+    pub(super) rename: Option<SpannedValue<String>>,
+}
+
+impl InputObjectDeriveVariant {
+    /// Converts this variant into the equivalent field of a struct, so we can
+    /// validate it in the same way.
+    pub(super) fn as_field(&self) -> InputObjectDeriveField {
+        InputObjectDeriveField {
+            ident: Some(self.ident.clone()),
+            ty: self.fields.fields[0].clone(),
+            skip_serializing_if: None,
+            rename: self.rename.clone(),
+        }
+    }
+}
+
 impl InputObjectDeriveInput {
     pub fn schema_module(&self) -> SpannedValue<String> {
         if let Some(schema_module) = &self.schema_module_ {
@@ -15,8 +15,8 @@ use field_serializer::FieldSerializer;
 pub(crate) mod input;
 
 use crate::suggestions::{format_guess, guess_field};
-use input::InputObjectDeriveField;
 pub use input::InputObjectDeriveInput;
+use input::{InputObjectDeriveField, InputObjectDeriveVariant};
 
 pub fn input_object_derive(ast: &syn::DeriveInput) -> Result<TokenStream, syn::Error> {
     use darling::FromDeriveInput;
@@ -163,14 +163,110 @@ pub fn input_object_derive_impl(
 
             ::cynic::impl_input_type!(#ident, #query_module::#input_marker_ident);
         })
+    } else if let darling::ast::Data::Enum(variants) = &input.data {
+        one_of_derive_impl(
+            &input,
+            variants,
+            input_object_def,
+            &type_index,
+            rename_all,
+            struct_span,
+        )
     } else {
         Err(syn::Error::new(
             struct_span,
-            "InputObject can only be derived on a struct".to_string(),
+            "InputObject can only be derived on a struct or enum".to_string(),
         ))
     }
 }
 
+/// Derives InputObject for an enum, which represents a oneOf input object.
+///
+/// Each variant of the enum maps to a field of the input object, and is
+/// serialized as an object containing only that field.
+fn one_of_derive_impl(
+    input: &InputObjectDeriveInput,
+    variants: &[InputObjectDeriveVariant],
+    input_object_def: &InputObjectType,
+    type_index: &TypeIndex,
+    rename_all: RenameAll,
+    enum_span: Span,
+) -> Result<TokenStream, syn::Error> {
+    use quote::quote;
+
+    let ident = &input.ident;
+    let input_marker_ident = Ident::for_type(input.graphql_type_name());
+    let query_module = Ident::for_module(&input.schema_module());
+
+    let fields = variants
+        .iter()
+        .map(InputObjectDeriveVariant::as_field)
+        .collect::<Vec<_>>();
+
+    let pairs = match join_fields(
+        &fields,
+        input_object_def,
+        &ident.to_string(),
+        rename_all,
+        input.require_all_fields,
+        &enum_span,
+    ) {
+        Ok(pairs) => pairs,
+        Err(error_tokens) => return Ok(error_tokens),
+    };
+
+    let field_serializers = pairs
+        .into_iter()
+        .map(|(rust_field, graphql_field)| {
+            FieldSerializer::new_for_variant(rust_field, graphql_field, type_index, &query_module)
+        })
+        .collect::<Vec<_>>();
+
+    let errors = field_serializers
+        .iter()
+        .filter_map(|fs| fs.validate())
+        .map(|e| e.to_compile_error())
+        .collect::<TokenStream>();
+
+    if !errors.is_empty() {
+        return Ok(errors);
+    }
+
+    let typecheck_funcs = field_serializers.iter().map(|fs| fs.type_check_fn());
+    let map_serializer_ident = proc_macro2::Ident::new("map_serializer", Span::call_site());
+    let variant_arms = field_serializers
+        .iter()
+        .map(|fs| fs.variant_insert_arm(ident, &map_serializer_ident));
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl ::cynic::InputObject<#query_module::#input_marker_ident> for #ident {}
+
+        #[automatically_derived]
+        impl ::cynic::serde::Serialize for #ident {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::cynic::serde::Serializer,
+            {
+                use ::cynic::serde::ser::SerializeMap;
+                #(
+                    #typecheck_funcs
+                )*
+
+                let mut map_serializer = serializer.serialize_map(Some(1))?;
+
+                match self {
+                    #(#variant_arms)*
+                }
+
+                map_serializer.end()
+            }
+        }
+
+        ::cynic::impl_input_type!(#ident, #query_module::#input_marker_ident);
+    })
+}
+
 fn join_fields<'a>(
     fields: &'a [InputObjectDeriveField],
     input_object_def: &'a InputObjectType,
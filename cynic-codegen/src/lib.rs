@@ -20,12 +20,12 @@ mod type_path;
 mod type_validation;
 
 pub use ident::RenameAll;
+pub use schema::{load_schema, parse_introspection_json, Document, SchemaLoadError};
 
 use error::Errors;
 use field_argument::FieldArgument;
 use field_type::FieldType;
 use ident::Ident;
-use schema::{load_schema, SchemaLoadError};
 use type_index::TypeIndex;
 use type_path::TypePath;
 
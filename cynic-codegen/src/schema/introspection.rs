@@ -0,0 +1,473 @@
+//! Support for converting the results of an introspection query into a
+//! schema `Document`.
+
+use graphql_parser::{query, schema, Pos};
+use serde::Deserialize;
+
+use super::{Document, SchemaLoadError, Type};
+
+/// Parses the JSON response to an introspection query into a `Document`.
+///
+/// This accepts either a full GraphQL response (`{"data": {"__schema": ...}}`)
+/// or just the contents of its `data` field (`{"__schema": ...}`).
+pub fn parse_introspection_json(json: &str) -> Result<Document, SchemaLoadError> {
+    let response = serde_json::from_str::<IntrospectionResponse>(json)
+        .map_err(|e| SchemaLoadError::ParseError(e.to_string()))?;
+
+    let schema = match response {
+        IntrospectionResponse::Response { data, errors } => match data {
+            Some(data) => data.schema,
+            None => {
+                return Err(SchemaLoadError::ParseError(format!(
+                    "Introspection response contained no data: {}",
+                    errors.unwrap_or_default()
+                )))
+            }
+        },
+        IntrospectionResponse::Data(data) => data.schema,
+    };
+
+    schema.into_document()
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IntrospectionResponse {
+    Data(IntrospectionData),
+    Response {
+        data: Option<IntrospectionData>,
+        errors: Option<serde_json::Value>,
+    },
+}
+
+#[derive(Deserialize)]
+struct IntrospectionData {
+    #[serde(rename = "__schema")]
+    schema: IntrospectedSchema,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectedSchema {
+    query_type: NamedTypeRef,
+    mutation_type: Option<NamedTypeRef>,
+    subscription_type: Option<NamedTypeRef>,
+    types: Vec<FullType>,
+    #[serde(default)]
+    directives: Vec<Directive>,
+}
+
+#[derive(Deserialize)]
+struct NamedTypeRef {
+    name: String,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+    List,
+    NonNull,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FullType {
+    kind: TypeKind,
+    name: String,
+    description: Option<String>,
+    fields: Option<Vec<FieldDefinition>>,
+    input_fields: Option<Vec<InputValue>>,
+    interfaces: Option<Vec<TypeRef>>,
+    enum_values: Option<Vec<EnumValue>>,
+    possible_types: Option<Vec<TypeRef>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FieldDefinition {
+    name: String,
+    description: Option<String>,
+    args: Vec<InputValue>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InputValue {
+    name: String,
+    description: Option<String>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    default_value: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EnumValue {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypeRef {
+    kind: TypeKind,
+    name: Option<String>,
+    of_type: Option<Box<TypeRef>>,
+}
+
+#[derive(Deserialize)]
+struct Directive {
+    name: String,
+    description: Option<String>,
+    locations: Vec<String>,
+    args: Vec<InputValue>,
+}
+
+impl IntrospectedSchema {
+    fn into_document(self) -> Result<Document, SchemaLoadError> {
+        let mut definitions = vec![schema::Definition::SchemaDefinition(
+            schema::SchemaDefinition {
+                position: Pos::default(),
+                directives: vec![],
+                query: Some(self.query_type.name),
+                mutation: self.mutation_type.map(|t| t.name),
+                subscription: self.subscription_type.map(|t| t.name),
+            },
+        )];
+
+        for ty in self.types {
+            if ty.name.starts_with("__") || is_builtin_scalar(&ty) {
+                continue;
+            }
+            definitions.push(schema::Definition::TypeDefinition(
+                ty.into_type_definition()?,
+            ));
+        }
+
+        for directive in self.directives {
+            if is_builtin_directive(&directive.name) {
+                continue;
+            }
+            definitions.push(schema::Definition::DirectiveDefinition(
+                directive.into_directive_definition()?,
+            ));
+        }
+
+        Ok(Document { definitions })
+    }
+}
+
+impl FullType {
+    fn into_type_definition(self) -> Result<super::TypeDefinition, SchemaLoadError> {
+        use schema::TypeDefinition;
+
+        let position = Pos::default();
+        let name = self.name;
+        let description = self.description;
+
+        Ok(match self.kind {
+            TypeKind::Scalar => TypeDefinition::Scalar(schema::ScalarType {
+                position,
+                description,
+                name,
+                directives: vec![],
+            }),
+            TypeKind::Object => TypeDefinition::Object(schema::ObjectType {
+                position,
+                description,
+                name,
+                implements_interfaces: named_types(self.interfaces)?,
+                directives: vec![],
+                fields: convert_fields(self.fields)?,
+            }),
+            TypeKind::Interface => TypeDefinition::Interface(schema::InterfaceType {
+                position,
+                description,
+                name,
+                directives: vec![],
+                fields: convert_fields(self.fields)?,
+            }),
+            TypeKind::Union => TypeDefinition::Union(schema::UnionType {
+                position,
+                description,
+                name,
+                directives: vec![],
+                types: named_types(self.possible_types)?,
+            }),
+            TypeKind::Enum => TypeDefinition::Enum(schema::EnumType {
+                position,
+                description,
+                name,
+                directives: vec![],
+                values: self
+                    .enum_values
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|value| schema::EnumValue {
+                        position,
+                        description: value.description,
+                        name: value.name,
+                        directives: deprecated_directive(
+                            value.is_deprecated,
+                            value.deprecation_reason,
+                        ),
+                    })
+                    .collect(),
+            }),
+            TypeKind::InputObject => TypeDefinition::InputObject(schema::InputObjectType {
+                position,
+                description,
+                name,
+                directives: vec![],
+                fields: convert_input_values(self.input_fields)?,
+            }),
+            TypeKind::List | TypeKind::NonNull => {
+                return Err(SchemaLoadError::ParseError(format!(
+                    "Found a wrapper type in the list of named types: {}",
+                    name
+                )))
+            }
+        })
+    }
+}
+
+impl Directive {
+    fn into_directive_definition(
+        self,
+    ) -> Result<schema::DirectiveDefinition<'static, String>, SchemaLoadError> {
+        Ok(schema::DirectiveDefinition {
+            position: Pos::default(),
+            description: self.description,
+            name: self.name,
+            arguments: convert_input_values(Some(self.args))?,
+            locations: self
+                .locations
+                .iter()
+                .map(|location| {
+                    location.parse().map_err(|_| {
+                        SchemaLoadError::ParseError(format!(
+                            "Unknown directive location: {}",
+                            location
+                        ))
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TypeRef {
+    fn into_type(self) -> Result<Type, SchemaLoadError> {
+        match (self.kind, self.name, self.of_type) {
+            (TypeKind::List, _, Some(inner)) => Ok(Type::ListType(Box::new(inner.into_type()?))),
+            (TypeKind::NonNull, _, Some(inner)) => {
+                Ok(Type::NonNullType(Box::new(inner.into_type()?)))
+            }
+            (TypeKind::List, _, None) | (TypeKind::NonNull, _, None) => Err(
+                SchemaLoadError::ParseError("Found a wrapper type without an ofType".into()),
+            ),
+            (_, Some(name), _) => Ok(Type::NamedType(name)),
+            (_, None, _) => Err(SchemaLoadError::ParseError(
+                "Found a named type without a name".into(),
+            )),
+        }
+    }
+
+    fn into_name(self) -> Result<String, SchemaLoadError> {
+        match self.into_type()? {
+            Type::NamedType(name) => Ok(name),
+            _ => Err(SchemaLoadError::ParseError(
+                "Expected a named type but found a wrapper type".into(),
+            )),
+        }
+    }
+}
+
+fn named_types(types: Option<Vec<TypeRef>>) -> Result<Vec<String>, SchemaLoadError> {
+    types
+        .unwrap_or_default()
+        .into_iter()
+        .map(TypeRef::into_name)
+        .collect()
+}
+
+fn convert_fields(
+    fields: Option<Vec<FieldDefinition>>,
+) -> Result<Vec<super::Field>, SchemaLoadError> {
+    fields
+        .unwrap_or_default()
+        .into_iter()
+        .map(|field| {
+            Ok(schema::Field {
+                position: Pos::default(),
+                description: field.description,
+                name: field.name,
+                arguments: convert_input_values(Some(field.args))?,
+                field_type: field.ty.into_type()?,
+                directives: deprecated_directive(field.is_deprecated, field.deprecation_reason),
+            })
+        })
+        .collect()
+}
+
+fn convert_input_values(
+    values: Option<Vec<InputValue>>,
+) -> Result<Vec<super::InputValue>, SchemaLoadError> {
+    values
+        .unwrap_or_default()
+        .into_iter()
+        .map(|value| {
+            Ok(schema::InputValue {
+                position: Pos::default(),
+                description: value.description,
+                name: value.name,
+                value_type: value.ty.into_type()?,
+                default_value: value
+                    .default_value
+                    .as_deref()
+                    .map(parse_value)
+                    .transpose()?,
+                directives: vec![],
+            })
+        })
+        .collect()
+}
+
+fn deprecated_directive(
+    is_deprecated: bool,
+    reason: Option<String>,
+) -> Vec<schema::Directive<'static, String>> {
+    if !is_deprecated {
+        return vec![];
+    }
+
+    vec![schema::Directive {
+        position: Pos::default(),
+        name: "deprecated".into(),
+        arguments: reason
+            .map(|reason| ("reason".to_string(), query::Value::String(reason)))
+            .into_iter()
+            .collect(),
+    }]
+}
+
+/// Parses a default value, which introspection provides as a GraphQL literal
+/// in a string.
+fn parse_value(value: &str) -> Result<query::Value<'static, String>, SchemaLoadError> {
+    // graphql_parser doesn't expose a way to parse just a value, so we wrap
+    // the value in a query and extract it from there.
+    let document = format!("{{ field(arg: {}) }}", value);
+    let parse_error =
+        || SchemaLoadError::ParseError(format!("Could not parse default value: {}", value));
+
+    let document = query::parse_query::<String>(&document).map_err(|_| parse_error())?;
+    let value = match document.definitions.into_iter().next() {
+        Some(query::Definition::Operation(query::OperationDefinition::SelectionSet(
+            selection_set,
+        ))) => match selection_set.items.into_iter().next() {
+            Some(query::Selection::Field(field)) => {
+                field.arguments.into_iter().next().map(|(_, value)| value)
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+
+    value.map(value_into_static).ok_or_else(parse_error)
+}
+
+fn value_into_static(value: query::Value<'_, String>) -> query::Value<'static, String> {
+    // A value that uses Strings for it's data should be safe to cast to 'static lifetime
+    // as there's nothing inside it to reference 'a anyway.  See schema_into_static
+    unsafe { std::mem::transmute::<_, query::Value<'static, String>>(value) }
+}
+
+fn is_builtin_scalar(ty: &FullType) -> bool {
+    ty.kind == TypeKind::Scalar
+        && matches!(
+            ty.name.as_ref(),
+            "String" | "Int" | "Float" | "Boolean" | "ID"
+        )
+}
+
+fn is_builtin_directive(name: &str) -> bool {
+    matches!(name, "skip" | "include" | "deprecated" | "specifiedBy")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_converting_books_introspection() {
+        let json = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../schemas/books.introspection.json"
+        ))
+        .unwrap();
+
+        let document = parse_introspection_json(&json).unwrap();
+
+        insta::assert_snapshot!(document.to_string());
+    }
+
+    #[test]
+    fn test_converting_data_without_response_wrapper() {
+        let json = r#"{"__schema": {
+            "queryType": {"name": "Query"},
+            "mutationType": null,
+            "subscriptionType": null,
+            "types": [{
+                "kind": "OBJECT",
+                "name": "Query",
+                "description": null,
+                "fields": [{
+                    "name": "hello",
+                    "description": null,
+                    "args": [{
+                        "name": "name",
+                        "description": null,
+                        "type": {"kind": "SCALAR", "name": "String", "ofType": null},
+                        "defaultValue": "\"world\""
+                    }],
+                    "type": {
+                        "kind": "NON_NULL",
+                        "name": null,
+                        "ofType": {"kind": "SCALAR", "name": "String", "ofType": null}
+                    },
+                    "isDeprecated": true,
+                    "deprecationReason": "Use goodbye"
+                }],
+                "inputFields": null,
+                "interfaces": [],
+                "enumValues": null,
+                "possibleTypes": null
+            }],
+            "directives": []
+        }}"#;
+
+        insta::assert_snapshot!(parse_introspection_json(json).unwrap().to_string(), @r###"
+        schema {
+          query: Query
+        }
+
+        type Query {
+          hello(name: String = "world"): String! @deprecated(reason: "Use goodbye")
+        }
+        "###);
+    }
+}
@@ -1,3 +1,7 @@
+mod introspection;
+
+pub use introspection::parse_introspection_json;
+
 use crate::{FieldArgument, TypeIndex};
 
 // Alias all the graphql_parser schema types so we don't have to specify generic parameters
@@ -17,6 +21,9 @@ pub type InputValue = graphql_parser::schema::InputValue<'static, String>;
 pub type EnumValue = graphql_parser::schema::EnumValue<'static, String>;
 
 /// Loads a schema from a filename, relative to CARGO_MANIFEST_DIR if it's set.
+///
+/// Files with a `.json` extension are expected to contain the response to an
+/// introspection query, anything else is parsed as GraphQL SDL.
 pub fn load_schema(filename: impl AsRef<std::path::Path>) -> Result<Document, SchemaLoadError> {
     use std::path::PathBuf;
     let mut pathbuf = PathBuf::new();
@@ -31,6 +38,14 @@ pub fn load_schema(filename: impl AsRef<std::path::Path>) -> Result<Document, Sc
     let schema = std::fs::read_to_string(&pathbuf)
         .map_err(|_| SchemaLoadError::FileNotFound(pathbuf.to_str().unwrap().to_string()))?;
 
+    if pathbuf
+        .extension()
+        .map(|ext| ext == "json")
+        .unwrap_or(false)
+    {
+        return parse_introspection_json(&schema);
+    }
+
     parse_schema(&schema)
 }
 
@@ -59,16 +74,22 @@ pub enum SchemaLoadError {
 
 impl SchemaLoadError {
     pub fn into_syn_error(self, schema_span: proc_macro2::Span) -> syn::Error {
-        let message = match self {
-            SchemaLoadError::IoError(e) => format!("Could not load schema file: {}", e),
-            SchemaLoadError::ParseError(e) => format!("Could not parse schema file: {}", e),
-            SchemaLoadError::FileNotFound(e) => format!("Could not find file: {}", e),
-        };
+        syn::Error::new(schema_span, self.to_string())
+    }
+}
 
-        syn::Error::new(schema_span, message)
+impl std::fmt::Display for SchemaLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaLoadError::IoError(e) => write!(f, "Could not load schema file: {}", e),
+            SchemaLoadError::ParseError(e) => write!(f, "Could not parse schema file: {}", e),
+            SchemaLoadError::FileNotFound(e) => write!(f, "Could not find file: {}", e),
+        }
     }
 }
 
+impl std::error::Error for SchemaLoadError {}
+
 impl From<graphql_parser::schema::ParseError> for SchemaLoadError {
     fn from(e: graphql_parser::schema::ParseError) -> SchemaLoadError {
         SchemaLoadError::ParseError(e.to_string())
@@ -33,7 +33,7 @@ pub fn check_spread_type(rust_type: &syn::Type) -> Result<(), syn::Error> {
         ParsedType::Unknown => {
             Err(syn::Error::new(
                 rust_type.span(),
-                "Cynic does not understand this type. Only un-parameterised types, Vecs, Options & Box are accepted currently.",
+                "Cynic does not understand this type. Only un-parameterised types, Vecs, Options, Box & relay::Connection are accepted currently.",
             ))
         },
         ParsedType::Box(inner) => {
@@ -61,6 +61,24 @@ pub fn check_spread_type(rust_type: &syn::Type) -> Result<(), syn::Error> {
     }
 }
 
+/// Checks the type of a field that has a `@skip` or `@include` directive.
+///
+/// The server can omit these fields from a response, so they need to be
+/// wrapped in an Option even if the GraphQL type is required.
+pub fn check_omittable_type(gql_type: &FieldType, rust_type: &syn::Type) -> Result<(), syn::Error> {
+    if let ParsedType::Optional(_) = parse_type(rust_type) {
+        normal_check(&gql_type.as_nullable(), rust_type, false)
+    } else {
+        Err(syn::Error::new(
+            rust_type.span(),
+            format!(
+                "This field has a skip or include directive so the server might omit it, but you're not wrapping the type in Option.  Did you mean Option<{}>",
+                quote! { #rust_type }
+            ),
+        ))
+    }
+}
+
 fn normal_check(
     gql_type: &FieldType,
     rust_type: &syn::Type,
@@ -72,7 +90,7 @@ fn normal_check(
         ParsedType::Unknown => {
             return Err(syn::Error::new(
                 rust_type.span(),
-                "Cynic does not understand this type. Only un-parameterised types, Vecs, Options & Box are accepted currently.",
+                "Cynic does not understand this type. Only un-parameterised types, Vecs, Options, Box & relay::Connection are accepted currently.",
             ))
         },
         ParsedType::Box(inner) => {
@@ -136,7 +154,7 @@ fn recursing_check(gql_type: &FieldType, rust_type: &syn::Type) -> Result<(), sy
     if let ParsedType::Unknown = parsed_type {
         return Err(syn::Error::new(
                 rust_type.span(),
-                "Cynic does not understand this type. Only un-parameterised types, Vecs, Options & Box are accepted currently.",
+                "Cynic does not understand this type. Only un-parameterised types, Vecs, Options, Box & relay::Connection are accepted currently.",
             ));
     };
 
@@ -176,7 +194,11 @@ fn parse_type(ty: &'_ syn::Type) -> ParsedType<'_> {
 
                 return ParsedType::Unknown;
             }
-            if last_segment.ident.to_string() == "Option" {
+            // MaybeUndefined is only used for nullable inputs, so we can
+            // treat it the same as an Option.
+            if last_segment.ident.to_string() == "Option"
+                || last_segment.ident.to_string() == "MaybeUndefined"
+            {
                 if let Some(inner_type) = extract_generic_argument(last_segment) {
                     return ParsedType::Optional(inner_type);
                 }
@@ -193,6 +215,12 @@ fn parse_type(ty: &'_ syn::Type) -> ParsedType<'_> {
             if last_segment.arguments == syn::PathArguments::None {
                 return ParsedType::SimpleType;
             }
+
+            // `relay::Connection<T, TypeLock>` is a QueryFragment like any
+            // other named type, it just happens to have generic parameters.
+            if last_segment.ident == "Connection" {
+                return ParsedType::SimpleType;
+            }
         }
     }
 
@@ -261,6 +289,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_maybe_undefined_validation() {
+        let required_field = FieldType::Scalar(Ident::new("test").into(), false);
+        let optional_field = FieldType::Scalar(Ident::new("test").into(), true);
+
+        assert_matches!(
+            check_types_are_compatible(
+                &optional_field,
+                &syn::parse2(quote! { cynic::MaybeUndefined<i32> }).unwrap(),
+                CheckMode::Normal
+            ),
+            Ok(())
+        );
+        assert_matches!(
+            check_types_are_compatible(
+                &required_field,
+                &syn::parse2(quote! { MaybeUndefined<i32> }).unwrap(),
+                CheckMode::Normal
+            ),
+            Err(_)
+        );
+    }
+
+    #[test]
+    fn test_omittable_validation() {
+        let required_field = FieldType::Scalar(Ident::new("test").into(), false);
+        let optional_field = FieldType::Scalar(Ident::new("test").into(), true);
+
+        assert_matches!(
+            check_omittable_type(
+                &required_field,
+                &syn::parse2(quote! { Option<i32> }).unwrap(),
+            ),
+            Ok(())
+        );
+        assert_matches!(
+            check_omittable_type(
+                &optional_field,
+                &syn::parse2(quote! { Option<i32> }).unwrap(),
+            ),
+            Ok(())
+        );
+        assert_matches!(
+            check_omittable_type(&required_field, &syn::parse2(quote! { i32 }).unwrap()),
+            Err(_)
+        );
+        assert_matches!(
+            check_omittable_type(
+                &optional_field,
+                &syn::parse2(quote! { Option<Option<i32>> }).unwrap(),
+            ),
+            Err(_)
+        );
+    }
+
     #[test]
     fn test_list_validation() {
         let list = FieldType::List(
@@ -384,6 +467,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generic_type_validation() {
+        let required_field = FieldType::Scalar(Ident::new("test").into(), false);
+        let optional_field = FieldType::Scalar(Ident::new("test").into(), true);
+
+        assert_matches!(
+            check_types_are_compatible(
+                &required_field,
+                &syn::parse2(quote! { Connection<Film, schema::FilmsConnection> }).unwrap(),
+                CheckMode::Normal
+            ),
+            Ok(())
+        );
+        assert_matches!(
+            check_types_are_compatible(
+                &optional_field,
+                &syn::parse2(quote! { Connection<Film, schema::FilmsConnection> }).unwrap(),
+                CheckMode::Normal
+            ),
+            Err(_)
+        );
+        assert_matches!(
+            check_types_are_compatible(
+                &required_field,
+                &syn::parse2(quote! { HashMap<String, Film> }).unwrap(),
+                CheckMode::Normal
+            ),
+            Err(_)
+        );
+    }
+
     #[rstest(graphql_field, rust_field,
         case::required_t(
             FieldType::Scalar(Ident::new("T").into(), false),
@@ -0,0 +1,59 @@
+use proc_macro2::TokenStream;
+
+use crate::{
+    schema::{self, TypeDefinition, TypeExt},
+    Ident, TypeIndex,
+};
+
+/// We generate a ConnectionMarker for each object in the schema that looks
+/// like a Relay connection, i.e. it has a `pageInfo` field & an `edges` field
+/// whose type has a `node` field.
+///
+/// These are output as `relay::ConnectionType` implementations, which make
+/// sure the `Node` of a `relay::Connection` is for the right type.
+#[derive(Debug)]
+pub struct ConnectionMarker {
+    pub connection: Ident,
+    pub node: Ident,
+}
+
+impl ConnectionMarker {
+    pub fn from_object(obj: &schema::ObjectType, type_index: &TypeIndex) -> Option<Self> {
+        if !obj.fields.iter().any(|field| field.name == "pageInfo") {
+            return None;
+        }
+
+        let edges = obj.fields.iter().find(|field| field.name == "edges")?;
+        let edge = match type_index.lookup_type(edges.field_type.inner_name())? {
+            TypeDefinition::Object(edge) => edge,
+            _ => return None,
+        };
+
+        let node = edge.fields.iter().find(|field| field.name == "node")?;
+        match type_index.lookup_type(node.field_type.inner_name())? {
+            TypeDefinition::Object(_) | TypeDefinition::Interface(_) | TypeDefinition::Union(_) => {
+            }
+            _ => return None,
+        }
+
+        Some(ConnectionMarker {
+            connection: Ident::for_type(&obj.name),
+            node: Ident::for_type(node.field_type.inner_name()),
+        })
+    }
+}
+
+impl quote::ToTokens for ConnectionMarker {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        use quote::{quote, TokenStreamExt};
+
+        let connection = &self.connection;
+        let node = &self.node;
+
+        tokens.append_all(quote! {
+            impl ::cynic::relay::ConnectionType for #connection {
+                type Node = #node;
+            }
+        });
+    }
+}
@@ -65,7 +65,7 @@ impl quote::ToTokens for FieldSelector {
             .iter()
             .map(|a| {
                 let name = &a.name;
-                quote! { ::cynic::serde_json::to_value(&#name) }
+                quote! { &#name }
             })
             .collect();
         let argument_strings: Vec<_> = self
@@ -87,7 +87,7 @@ impl quote::ToTokens for FieldSelector {
             ) -> #selection_builder {
                 #selection_builder::new(vec![
                     #(
-                        ::cynic::Argument::new(
+                        ::cynic::Argument::from_serializable(
                             #argument_strings,
                             #argument_gql_types,
                             #argument_vals
@@ -1,6 +1,7 @@
 use proc_macro2::TokenStream;
 
 mod argument_parameter;
+mod connection_marker;
 mod field_selector;
 mod input_object_marker;
 mod interface_struct;
@@ -17,6 +18,7 @@ pub use selector_struct::SelectorStruct;
 use super::module::Module;
 use crate::{load_schema, schema, SchemaLoadError, TypeIndex};
 use argument_parameter::{ArgumentParameter, ArgumentParameterType};
+use connection_marker::ConnectionMarker;
 use input_object_marker::InputObjectMarker;
 use interface_struct::InterfaceStruct;
 use interfaces_implementations::InterfacesImplementations;
@@ -64,6 +66,7 @@ pub struct SchemaData {
     pub input_objects: Vec<InputObjectMarker>,
     pub schema_roots: Vec<SchemaRoot>,
     pub interfaces_implementations: Vec<InterfacesImplementations>,
+    pub connection_markers: Vec<ConnectionMarker>,
 }
 
 impl From<schema::Document> for SchemaData {
@@ -80,6 +83,7 @@ impl From<schema::Document> for SchemaData {
         let mut type_lock_markers = vec![];
         let mut schema_roots = vec![];
         let mut interfaces_implementations = vec![];
+        let mut connection_markers = vec![];
 
         let root_types = RootTypes::from_definitions(&document.definitions);
 
@@ -90,6 +94,8 @@ impl From<schema::Document> for SchemaData {
                         interfaces_implementations.push(impls);
                     }
 
+                    connection_markers.extend(ConnectionMarker::from_object(object, &type_index));
+
                     // Would be nice to restructure this so that the argument structs
                     // are visible at the point we're generating the field_selectors...
                     let selector = SelectorStruct::from_object(&object, &type_index);
@@ -146,6 +152,7 @@ impl From<schema::Document> for SchemaData {
             type_lock_markers,
             schema_roots,
             interfaces_implementations,
+            connection_markers,
         }
     }
 }
@@ -162,6 +169,7 @@ impl quote::ToTokens for SchemaData {
         let type_lock_markers = &self.type_lock_markers;
         let schema_roots = &self.schema_roots;
         let interfaces_implementations = &self.interfaces_implementations;
+        let connection_markers = &self.connection_markers;
 
         tokens.append_all(quote! {
             #(
@@ -188,6 +196,9 @@ impl quote::ToTokens for SchemaData {
             #(
                 #interfaces_implementations
             )*
+            #(
+                #connection_markers
+            )*
         })
     }
 }
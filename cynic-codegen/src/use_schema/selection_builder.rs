@@ -118,13 +118,15 @@ impl quote::ToTokens for FieldSelectionBuilder {
                     pub fn #argument_names(
                         mut self, #argument_names: #argument_types
                     ) -> Self {
-                        self.args.push(
-                            ::cynic::Argument::new(
-                                #argument_strings,
-                                #argument_gql_types,
-                                ::cynic::serde_json::to_value(&#argument_names)
-                            )
-                        );
+                        if !::cynic::InputType::is_undefined(&#argument_names) {
+                            self.args.push(
+                                ::cynic::Argument::from_serializable(
+                                    #argument_strings,
+                                    #argument_gql_types,
+                                    &#argument_names
+                                )
+                            );
+                        }
 
                         self
                     }
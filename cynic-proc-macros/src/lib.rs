@@ -31,7 +31,7 @@ pub fn use_schema(input: TokenStream) -> TokenStream {
     rv
 }
 
-#[proc_macro_derive(QueryFragment, attributes(cynic, arguments))]
+#[proc_macro_derive(QueryFragment, attributes(cynic, arguments, directives))]
 pub fn query_fragment_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse_macro_input!(input as syn::DeriveInput);
 
@@ -86,6 +86,18 @@ impl Default for QueryGenOptions {
     }
 }
 
+/// Checks that a query document is valid against a schema, without generating
+/// any code.
+pub fn validate_document(query: impl AsRef<str>, schema: impl AsRef<str>) -> Result<(), Error> {
+    let schema = graphql_parser::parse_schema::<&str>(schema.as_ref())?;
+    let query = graphql_parser::parse_query::<&str>(query.as_ref())?;
+
+    let type_index = Rc::new(TypeIndex::from_schema(&schema));
+    query_parsing::parse_query_document(&query, &type_index)?;
+
+    Ok(())
+}
+
 pub fn document_to_fragment_structs(
     query: impl AsRef<str>,
     schema: impl AsRef<str>,
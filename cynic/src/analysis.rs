@@ -0,0 +1,172 @@
+//! Tools for estimating the cost of an `Operation` before it's sent.
+//!
+//! Many servers limit the size of the queries they'll accept - usually by
+//! their depth or by the number of nodes they could return.  `analyse` walks
+//! the query of an operation & works these out, so they can be checked
+//! without waiting for the server to reject the query:
+//!
+//! ```rust,ignore
+//! let analysis = cynic::analysis::analyse(&operation);
+//! if analysis.node_count > 500_000 {
+//!     // Ask for smaller pages...
+//! }
+//! ```
+//!
+//! The depth of a query can also be checked at compile time with the
+//! `max_depth` attribute of the `QueryFragment` derive.
+
+use std::collections::HashMap;
+
+use crate::{
+    selection_set::printer::{Definition, FragmentDefinition, Selection, Value},
+    Argument, Operation,
+};
+
+/// The size of an operations query, as worked out by `analyse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Analysis {
+    /// The number of levels of nested fields in the query.
+    ///
+    /// A query that only selects scalar fields of the root type has a depth
+    /// of 1.
+    pub depth: usize,
+
+    /// The number of fields in the query.
+    ///
+    /// Fields inside named fragments are counted each time the fragment is
+    /// used.
+    pub field_count: usize,
+
+    /// An estimate of the number of nodes the query could return.
+    ///
+    /// This follows the method used by the GitHub API: each field with a
+    /// `first` or `last` argument is treated as a connection that can return
+    /// that many nodes for each node of its parent connection.
+    pub node_count: u64,
+}
+
+/// Analyses the query of `operation`.
+///
+/// Arguments provided as variables are read from the variables of the
+/// operation.  A connection whose `first` or `last` argument isn't a number
+/// isn't counted towards `node_count`.
+pub fn analyse<'a, ResponseData: 'a, ErrorExtensions>(
+    operation: &Operation<'a, ResponseData, ErrorExtensions>,
+) -> Analysis {
+    let definitions = &operation.document().definitions;
+
+    let analyser = Analyser {
+        fragments: definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+                _ => None,
+            })
+            .collect(),
+        variables: &operation.variables,
+    };
+
+    let mut analysis = Analysis {
+        depth: 0,
+        field_count: 0,
+        node_count: 0,
+    };
+
+    for definition in definitions {
+        let selections = match definition {
+            Definition::Operation(operation) => &operation.selections,
+            Definition::Selections(selections) => selections,
+            Definition::Fragment(_) => continue,
+        };
+
+        let depth = analyser.walk(selections, 1, &mut analysis);
+        analysis.depth = analysis.depth.max(depth);
+    }
+
+    analysis
+}
+
+/// Returns the largest of `depths`, or 0 if it's empty.
+///
+/// This is used by the `QueryFragment` derive to work out the depth of a
+/// fragment at compile time.
+pub const fn deepest(depths: &[usize]) -> usize {
+    let mut deepest = 0;
+    let mut i = 0;
+    while i < depths.len() {
+        if depths[i] > deepest {
+            deepest = depths[i];
+        }
+        i += 1;
+    }
+    deepest
+}
+
+struct Analyser<'a> {
+    fragments: HashMap<&'a str, &'a FragmentDefinition>,
+    variables: &'a HashMap<String, Argument>,
+}
+
+impl Analyser<'_> {
+    /// Adds the fields & nodes of `selections` to `analysis`, returning the
+    /// depth of `selections`.
+    ///
+    /// `multiplier` is the number of nodes that the parent connection of
+    /// these selections could return.
+    fn walk(&self, selections: &[Selection], multiplier: u64, analysis: &mut Analysis) -> usize {
+        let mut depth = 0;
+
+        for selection in selections {
+            let selection_depth = match selection {
+                Selection::Field {
+                    arguments,
+                    selections,
+                    ..
+                } => {
+                    analysis.field_count += 1;
+
+                    let mut inner_multiplier = multiplier;
+                    if let Some(page_size) = self.page_size(arguments) {
+                        inner_multiplier = multiplier.saturating_mul(page_size);
+                        analysis.node_count = analysis.node_count.saturating_add(inner_multiplier);
+                    }
+
+                    let inner_depth = selections
+                        .as_ref()
+                        .map(|selections| self.walk(selections, inner_multiplier, analysis))
+                        .unwrap_or(0);
+
+                    inner_depth + 1
+                }
+                Selection::InlineFragment { selections, .. } => {
+                    self.walk(selections, multiplier, analysis)
+                }
+                Selection::FragmentSpread { name, .. } => self
+                    .fragments
+                    .get(name.as_str())
+                    .map(|fragment| self.walk(&fragment.selections, multiplier, analysis))
+                    .unwrap_or(0),
+            };
+
+            depth = depth.max(selection_depth);
+        }
+
+        depth
+    }
+
+    /// The value of the `first` or `last` argument in `arguments`, if there
+    /// is one.
+    fn page_size(&self, arguments: &[(String, Value)]) -> Option<u64> {
+        arguments
+            .iter()
+            .filter(|(name, _)| name == "first" || name == "last")
+            .find_map(|(_, value)| match value {
+                Value::Literal(value) => value.as_u64(),
+                Value::Variable(name) => self
+                    .variables
+                    .get(name)
+                    .and_then(|argument| argument.serialize_result.as_ref().ok())
+                    .and_then(serde_json::Value::as_u64),
+            })
+    }
+}
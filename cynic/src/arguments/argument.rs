@@ -1,7 +1,25 @@
+use crate::upload::{to_value_with_uploads, Upload, UploadPath};
+
 pub struct Argument {
     pub(crate) name: String,
     pub(crate) serialize_result: Result<serde_json::Value, serde_json::Error>,
     pub(crate) type_: String,
+    pub(crate) source: ArgumentSource,
+    pub(crate) uploads: Vec<(UploadPath, Upload)>,
+}
+
+/// Determines how the value of an `Argument` is provided in a query.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArgumentSource {
+    /// The value is sent as a variable that's named after the argument.
+    ArgumentName,
+    /// The value is sent as a variable with the given name.
+    Variable(String),
+    /// The value is written directly into the query document.
+    ///
+    /// Enum values are serialized as strings, so this should only be used
+    /// for arguments that don't contain any enums.
+    Literal,
 }
 
 impl Argument {
@@ -14,6 +32,44 @@ impl Argument {
             name: name.to_string(),
             serialize_result: result,
             type_: gql_type.to_string(),
+            source: ArgumentSource::ArgumentName,
+            uploads: vec![],
+        }
+    }
+
+    /// Creates an Argument by serializing `value`.
+    ///
+    /// Any `Upload`s inside `value` are replaced with `null` & kept so they
+    /// can be sent as files in a multipart request.
+    pub fn from_serializable<T>(name: &str, gql_type: &str, value: &T) -> Argument
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        let (result, uploads) = to_value_with_uploads(value);
+
+        Argument {
+            uploads,
+            ..Argument::new(name, gql_type, result)
+        }
+    }
+
+    /// Sets how the value of this argument is provided in a query.
+    pub fn with_source(mut self, source: ArgumentSource) -> Argument {
+        self.source = source;
+        self
+    }
+
+    /// Checks whether two arguments could be provided by the same variable.
+    ///
+    /// Arguments containing `Upload`s always get their own variable.
+    pub(crate) fn same_value_as(&self, other: &Argument) -> bool {
+        if !self.uploads.is_empty() || !other.uploads.is_empty() {
+            return false;
+        }
+
+        match (&self.serialize_result, &other.serialize_result) {
+            (Ok(lhs), Ok(rhs)) => self.type_ == other.type_ && lhs == rhs,
+            _ => false,
         }
     }
 }
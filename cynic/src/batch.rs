@@ -0,0 +1,170 @@
+use json_decode::DecodeError;
+use serde::de::DeserializeOwned;
+
+use crate::{GraphQlResponse, Operation, ResponseDecodeError};
+
+type BatchDecoder<'a, ResponseData> = Box<
+    dyn Fn(Vec<GraphQlResponse<serde_json::Value>>) -> Result<ResponseData, ResponseDecodeError>
+        + Send
+        + Sync
+        + 'a,
+>;
+
+/// A batch of Operations that can be sent to a remote GraphQL server in a
+/// single request.
+///
+/// Servers that support batching accept a JSON array of operations and reply
+/// with an array of responses in the same order.  A `BatchOperation` can be
+/// serialized into that array with `serde::Serialize`, and has a
+/// `decode_response` function that decodes each of the responses with the
+/// decoder of the corresponding `Operation`.
+///
+/// BatchOperations can be built from a tuple of `Operation`s, which may each
+/// have a different `ResponseData`:
+///
+/// ```rust
+/// # mod schema {
+/// #   cynic::use_schema!("../schemas/starwars.schema.graphql");
+/// # }
+/// #
+/// # #[derive(cynic::QueryFragment)]
+/// # #[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+/// # struct Film {
+/// #    title: Option<String>,
+/// # }
+/// #
+/// # #[derive(cynic::QueryFragment)]
+/// # #[cynic(schema_path = "../schemas/starwars.schema.graphql", graphql_type = "Root")]
+/// # struct FilmQuery {
+/// #     #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+/// #     film: Option<Film>,
+/// # }
+/// #
+/// # #[derive(cynic::QueryFragment)]
+/// # #[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+/// # struct Person {
+/// #    name: Option<String>,
+/// # }
+/// #
+/// # #[derive(cynic::QueryFragment)]
+/// # #[cynic(schema_path = "../schemas/starwars.schema.graphql", graphql_type = "Root")]
+/// # struct PersonQuery {
+/// #     #[arguments(id = cynic::Id::new("cGVvcGxlOjE="))]
+/// #     person: Option<Person>,
+/// # }
+/// use cynic::{BatchOperation, QueryBuilder};
+///
+/// let batch = BatchOperation::from((FilmQuery::build(()), PersonQuery::build(())));
+/// ```
+///
+/// or from a `Vec` of `Operation`s with the same `ResponseData`.
+pub struct BatchOperation<'a, ResponseData> {
+    operations: Vec<Result<serde_json::Value, serde_json::Error>>,
+    decoder: BatchDecoder<'a, ResponseData>,
+}
+
+impl<'a, ResponseData: 'a> BatchOperation<'a, ResponseData> {
+    /// The number of operations in this batch.
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Whether this batch contains any operations.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Decodes the responses to a batch.  Note that you need to decode the
+    /// list of GraphQlResponses from JSON before passing to this function.
+    pub fn decode_response(
+        &self,
+        responses: Vec<GraphQlResponse<serde_json::Value>>,
+    ) -> Result<ResponseData, ResponseDecodeError> {
+        if responses.len() != self.operations.len() {
+            return Err(DecodeError::Other(format!(
+                "Expected {} responses to a batch but got {}",
+                self.operations.len(),
+                responses.len()
+            ))
+            .into());
+        }
+
+        (self.decoder)(responses)
+    }
+}
+
+impl<ResponseData> serde::Serialize for BatchOperation<'_, ResponseData> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::{Error, SerializeSeq};
+
+        let mut seq = serializer.serialize_seq(Some(self.operations.len()))?;
+        for operation in &self.operations {
+            match operation {
+                Ok(operation) => seq.serialize_element(operation)?,
+                Err(e) => return Err(S::Error::custom(e.to_string())),
+            }
+        }
+        seq.end()
+    }
+}
+
+impl<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>
+    From<Vec<Operation<'a, ResponseData, ErrorExtensions>>>
+    for BatchOperation<'a, Vec<GraphQlResponse<ResponseData, ErrorExtensions>>>
+{
+    fn from(operations: Vec<Operation<'a, ResponseData, ErrorExtensions>>) -> Self {
+        BatchOperation {
+            operations: operations
+                .iter()
+                .map(|operation| serde_json::to_value(operation.body(true)))
+                .collect(),
+            decoder: Box::new(move |responses| {
+                operations
+                    .iter()
+                    .zip(responses)
+                    .map(|(operation, response)| {
+                        operation.decode_graphql_response(response.deserialize_error_extensions()?)
+                    })
+                    .collect()
+            }),
+        }
+    }
+}
+
+macro_rules! impl_batch_from_tuple {
+    ($($response_data:ident, $extensions:ident => $index:tt),+) => {
+        impl<'a, $($response_data: 'a, $extensions: DeserializeOwned + 'a),+>
+            From<($(Operation<'a, $response_data, $extensions>,)+)>
+            for BatchOperation<'a, ($(GraphQlResponse<$response_data, $extensions>,)+)>
+        {
+            fn from(operations: ($(Operation<'a, $response_data, $extensions>,)+)) -> Self {
+                BatchOperation {
+                    operations: vec![$(serde_json::to_value(operations.$index.body(true))),+],
+                    decoder: Box::new(move |responses| {
+                        let mut responses = responses.into_iter();
+                        Ok(($(
+                            operations.$index.decode_graphql_response(
+                                responses
+                                    .next()
+                                    .expect("decode_response to check the length")
+                                    .deserialize_error_extensions()?
+                            )?,
+                        )+))
+                    }),
+                }
+            }
+        }
+    };
+}
+
+impl_batch_from_tuple!(T1, E1 => 0);
+impl_batch_from_tuple!(T1, E1 => 0, T2, E2 => 1);
+impl_batch_from_tuple!(T1, E1 => 0, T2, E2 => 1, T3, E3 => 2);
+impl_batch_from_tuple!(T1, E1 => 0, T2, E2 => 1, T3, E3 => 2, T4, E4 => 3);
+impl_batch_from_tuple!(T1, E1 => 0, T2, E2 => 1, T3, E3 => 2, T4, E4 => 3, T5, E5 => 4);
+impl_batch_from_tuple!(T1, E1 => 0, T2, E2 => 1, T3, E3 => 2, T4, E4 => 3, T5, E5 => 4, T6, E6 => 5);
+impl_batch_from_tuple!(T1, E1 => 0, T2, E2 => 1, T3, E3 => 2, T4, E4 => 3, T5, E5 => 4, T6, E6 => 5, T7, E7 => 6);
+impl_batch_from_tuple!(T1, E1 => 0, T2, E2 => 1, T3, E3 => 2, T4, E4 => 3, T5, E5 => 4, T6, E6 => 5, T7, E7 => 6, T8, E8 => 7);
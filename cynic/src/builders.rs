@@ -1,4 +1,5 @@
 use super::{
+    selection_set::{mutation_root, query_root, subscription_root},
     FragmentContext, MutationRoot, Operation, QueryFragment, QueryRoot, SelectionSet,
     StreamingOperation, SubscriptionRoot,
 };
@@ -12,6 +13,13 @@ pub trait QueryBuilder<'a> {
 
     /// Constructs a query operation for this QueryFragment.
     fn build(args: impl Borrow<Self::Arguments>) -> Operation<'a, Self::ResponseData>;
+
+    /// Constructs a query operation for this QueryFragment, where any
+    /// QueryFragments that are used more than once are output as named
+    /// fragments rather than being repeated.
+    fn build_with_named_fragments(
+        args: impl Borrow<Self::Arguments>,
+    ) -> Operation<'a, Self::ResponseData>;
 }
 
 impl<'a, T, R, Q> QueryBuilder<'a> for T
@@ -25,7 +33,25 @@ where
     type ResponseData = R;
 
     fn build(args: impl Borrow<Self::Arguments>) -> Operation<'a, Self::ResponseData> {
-        Operation::query(Self::fragment(FragmentContext::new(args.borrow())))
+        let selection_set = Self::fragment(FragmentContext::new(args.borrow()));
+
+        match T::operation_name() {
+            Some(name) => Operation::named_query(name, selection_set),
+            None => Operation::query(selection_set),
+        }
+    }
+
+    fn build_with_named_fragments(
+        args: impl Borrow<Self::Arguments>,
+    ) -> Operation<'a, Self::ResponseData> {
+        let selection_set = Self::fragment(FragmentContext::new(args.borrow()));
+        let operation_name = T::operation_name().map(str::to_string);
+
+        Operation::new(
+            query_root(selection_set, operation_name.clone()),
+            operation_name,
+            true,
+        )
     }
 }
 
@@ -36,6 +62,13 @@ pub trait MutationBuilder<'a> {
 
     /// Constructs a mutation operation for this QueryFragment.
     fn build(args: impl Borrow<Self::Arguments>) -> Operation<'a, Self::ResponseData>;
+
+    /// Constructs a mutation operation for this QueryFragment, where any
+    /// QueryFragments that are used more than once are output as named
+    /// fragments rather than being repeated.
+    fn build_with_named_fragments(
+        args: impl Borrow<Self::Arguments>,
+    ) -> Operation<'a, Self::ResponseData>;
 }
 
 impl<'a, T, R, Q> MutationBuilder<'a> for T
@@ -49,7 +82,25 @@ where
     type ResponseData = R;
 
     fn build(args: impl Borrow<Self::Arguments>) -> Operation<'a, Self::ResponseData> {
-        Operation::mutation(Self::fragment(FragmentContext::new(args.borrow())))
+        let selection_set = Self::fragment(FragmentContext::new(args.borrow()));
+
+        match T::operation_name() {
+            Some(name) => Operation::named_mutation(name, selection_set),
+            None => Operation::mutation(selection_set),
+        }
+    }
+
+    fn build_with_named_fragments(
+        args: impl Borrow<Self::Arguments>,
+    ) -> Operation<'a, Self::ResponseData> {
+        let selection_set = Self::fragment(FragmentContext::new(args.borrow()));
+        let operation_name = T::operation_name().map(str::to_string);
+
+        Operation::new(
+            mutation_root(selection_set, operation_name.clone()),
+            operation_name,
+            true,
+        )
     }
 }
 
@@ -60,6 +111,13 @@ pub trait SubscriptionBuilder<'a> {
 
     /// Constructs a subscription operation for this QueryFragment.
     fn build(args: impl Borrow<Self::Arguments>) -> StreamingOperation<'a, Self::ResponseData>;
+
+    /// Constructs a subscription operation for this QueryFragment, where any
+    /// QueryFragments that are used more than once are output as named
+    /// fragments rather than being repeated.
+    fn build_with_named_fragments(
+        args: impl Borrow<Self::Arguments>,
+    ) -> StreamingOperation<'a, Self::ResponseData>;
 }
 
 impl<'a, T, R, Q> SubscriptionBuilder<'a> for T
@@ -73,6 +131,26 @@ where
     type ResponseData = R;
 
     fn build(args: impl Borrow<Self::Arguments>) -> StreamingOperation<'a, Self::ResponseData> {
-        StreamingOperation::subscription(Self::fragment(FragmentContext::new(args.borrow())))
+        let selection_set = Self::fragment(FragmentContext::new(args.borrow()));
+
+        match T::operation_name() {
+            Some(name) => StreamingOperation::named_subscription(name, selection_set),
+            None => StreamingOperation::subscription(selection_set),
+        }
+    }
+
+    fn build_with_named_fragments(
+        args: impl Borrow<Self::Arguments>,
+    ) -> StreamingOperation<'a, Self::ResponseData> {
+        let selection_set = Self::fragment(FragmentContext::new(args.borrow()));
+        let operation_name = T::operation_name().map(str::to_string);
+
+        StreamingOperation {
+            inner: Operation::new(
+                subscription_root(selection_set, operation_name.clone()),
+                operation_name,
+                true,
+            ),
+        }
     }
 }
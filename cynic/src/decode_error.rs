@@ -0,0 +1,153 @@
+use json_decode::DecodeError;
+
+/// An error that occurred while decoding the response to an `Operation`.
+///
+/// This wraps the underlying `DecodeError` with the path to the value in the
+/// response that failed to decode, e.g. `repository.issues.nodes[3].author.login`,
+/// along with the Rust & GraphQL types that were being decoded there.
+#[derive(Debug, PartialEq)]
+pub struct ResponseDecodeError {
+    path: Vec<PathSegment>,
+    rust_type: Option<&'static str>,
+    graphql_type: Option<String>,
+    source: DecodeError,
+}
+
+/// A single segment of the path to a value in a GraphQL response.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    /// A field of an object, named after its alias if it has one.
+    Field(String),
+    /// An index into a list.
+    Index(usize),
+}
+
+impl ResponseDecodeError {
+    /// The path to the value in the response that failed to decode.
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    /// The path to the value in the response that failed to decode, formatted
+    /// as a string like `repository.issues.nodes[3].author.login`.
+    pub fn path_string(&self) -> String {
+        let mut output = String::new();
+        for segment in &self.path {
+            match segment {
+                PathSegment::Field(name) => {
+                    if !output.is_empty() {
+                        output.push('.');
+                    }
+                    output.push_str(name);
+                }
+                PathSegment::Index(index) => output.push_str(&format!("[{}]", index)),
+            }
+        }
+        output
+    }
+
+    /// The name of the Rust type that was being decoded, if known.
+    pub fn rust_type(&self) -> Option<&'static str> {
+        self.rust_type
+    }
+
+    /// The name of the GraphQL type that was being decoded, if known.
+    pub fn graphql_type(&self) -> Option<&str> {
+        self.graphql_type.as_deref()
+    }
+
+    /// The underlying `DecodeError`.
+    pub fn decode_error(&self) -> &DecodeError {
+        &self.source
+    }
+
+    /// Adds a field to the start of the path of this error, recording the
+    /// types being decoded if this is the innermost field we've seen.
+    pub(crate) fn within_field(
+        mut self,
+        name: &str,
+        rust_type: &'static str,
+        graphql_type: Option<&str>,
+    ) -> Self {
+        if self.rust_type.is_none() {
+            self.rust_type = Some(rust_type);
+            self.graphql_type = graphql_type.map(str::to_string);
+        }
+        self.path.insert(0, PathSegment::Field(name.to_string()));
+        self
+    }
+
+    /// Adds a list index to the start of the path of this error.
+    pub(crate) fn within_index(mut self, index: usize) -> Self {
+        self.path.insert(0, PathSegment::Index(index));
+        self
+    }
+}
+
+impl From<DecodeError> for ResponseDecodeError {
+    fn from(source: DecodeError) -> Self {
+        ResponseDecodeError {
+            path: vec![],
+            rust_type: None,
+            graphql_type: None,
+            source,
+        }
+    }
+}
+
+impl std::fmt::Display for ResponseDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "Error decoding {}", self.path_string())?;
+            match (&self.graphql_type, self.rust_type) {
+                (Some(graphql_type), Some(rust_type)) => {
+                    write!(f, " (GraphQL type {} into {})", graphql_type, rust_type)?
+                }
+                (Some(graphql_type), None) => write!(f, " (GraphQL type {})", graphql_type)?,
+                (None, Some(rust_type)) => write!(f, " (into {})", rust_type)?,
+                (None, None) => {}
+            }
+            write!(f, ": ")?;
+        }
+        write!(f, "{}", self.source)
+    }
+}
+
+impl std::error::Error for ResponseDecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Gets the name of the GraphQL type that `TypeLock` represents, if it
+/// represents one.
+///
+/// The marker types in a `schema` module share the names of their GraphQL
+/// types, and the built in scalars are locked to their Rust equivalents.
+pub(crate) fn graphql_type_name<TypeLock>() -> Option<&'static str> {
+    let name = std::any::type_name::<TypeLock>();
+    if name.starts_with('(') || name.contains('<') {
+        return None;
+    }
+
+    Some(match name {
+        "i32" => "Int",
+        "f64" => "Float",
+        "bool" => "Boolean",
+        "cynic::id::Id" => "ID",
+        other => other.rsplit("::").next().unwrap_or(other),
+    })
+}
+
+/// A short description of the kind of a JSON value, for use in errors
+/// where including the whole value would be too noisy.
+pub(crate) fn json_kind(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "Boolean",
+        serde_json::Value::Number(_) => "Number",
+        serde_json::Value::String(_) => "String",
+        serde_json::Value::Array(_) => "Array",
+        serde_json::Value::Object(_) => "Object",
+    }
+}
@@ -4,14 +4,44 @@ pub trait QueryFragment {
     type SelectionSet;
     type Arguments: FragmentArguments;
 
+    /// The depth of the query this fragment selects, used by the `max_depth`
+    /// attribute of the derive to check depth at compile time.
+    ///
+    /// For fragments with recursive fields this is only a lower bound: each
+    /// recursive field is counted as recursing as many times as its limit,
+    /// but only one level deeper each time.  `cynic::analysis` can be used
+    /// to find the actual depth of an operation.
+    const DEPTH: usize = 1;
+
+    /// The depth of the query this fragment selects with every recursive
+    /// field counted as a single level.  The derives use this to work out
+    /// `DEPTH` for recursive fields without a cycle.
+    #[doc(hidden)]
+    const NON_RECURSIVE_DEPTH: usize = Self::DEPTH;
+
     fn fragment(context: FragmentContext<Self::Arguments>) -> Self::SelectionSet;
     fn graphql_type() -> String;
+
+    /// The name to give operations built from this fragment.
+    ///
+    /// If this is None then operations will be named after their type,
+    /// e.g. `Query`.
+    fn operation_name() -> Option<&'static str> {
+        None
+    }
 }
 
 pub trait InlineFragments: Sized {
     type TypeLock;
     type Arguments: FragmentArguments;
 
+    /// The depth of the deepest of these fragments.  See
+    /// `QueryFragment::DEPTH`.
+    const DEPTH: usize = 1;
+
+    #[doc(hidden)]
+    const NON_RECURSIVE_DEPTH: usize = Self::DEPTH;
+
     fn graphql_type() -> String;
     fn fragments(
         context: FragmentContext<Self::Arguments>,
@@ -29,6 +59,9 @@ where
     type SelectionSet = SelectionSet<'static, T, T::TypeLock>;
     type Arguments = <T as InlineFragments>::Arguments;
 
+    const DEPTH: usize = <T as InlineFragments>::DEPTH;
+    const NON_RECURSIVE_DEPTH: usize = <T as InlineFragments>::NON_RECURSIVE_DEPTH;
+
     fn fragment(context: FragmentContext<Self::Arguments>) -> Self::SelectionSet {
         crate::selection_set::inline_fragments(
             Self::fragments(context.clone()),
@@ -17,10 +17,13 @@ pub use reqwest_blocking_ext::ReqwestBlockingExt;
 
 #[cfg(feature = "surf")]
 mod surf_ext {
-    use serde_json::json;
+    use serde::de::DeserializeOwned;
     use std::{future::Future, pin::Pin};
 
-    use crate::{GraphQlResponse, Operation};
+    use crate::{
+        transport::{into_surf_error, SurfRequestBuilderTransport},
+        BatchOperation, Client, GraphQlResponse, Operation,
+    };
 
     type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
@@ -75,24 +78,87 @@ mod surf_ext {
         /// Runs a GraphQL query with the parameters in RequestBuilder, decodes
         /// the and returns the result.
         ///
-        /// If a `json_decode::Error` occurs it can be obtained via downcast_ref on
+        /// If the operation uses automatic persisted queries and the server
+        /// doesn't recognise the query hash, this retries with the full query.
+        ///
+        /// If a `cynic::ResponseDecodeError` occurs it can be obtained via downcast_ref on
+        /// the `surf::Error`.
+        fn run_graphql<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
+            self,
+            operation: Operation<'a, ResponseData, ErrorExtensions>,
+        ) -> BoxFuture<'a, Result<GraphQlResponse<ResponseData, ErrorExtensions>, surf::Error>>;
+
+        /// Runs a batch of GraphQL queries in a single request with the
+        /// parameters in RequestBuilder, decodes each of the responses and
+        /// returns the results.
+        ///
+        /// If a `cynic::ResponseDecodeError` occurs it can be obtained via downcast_ref on
         /// the `surf::Error`.
-        fn run_graphql<'a, ResponseData: 'a>(
+        fn run_graphql_batch<'a, ResponseData: 'a>(
             self,
-            operation: Operation<'a, ResponseData>,
-        ) -> BoxFuture<'a, Result<GraphQlResponse<ResponseData>, surf::Error>>;
+            batch: BatchOperation<'a, ResponseData>,
+        ) -> BoxFuture<'a, Result<ResponseData, surf::Error>>;
+
+        /// Runs a GraphQL query as a multipart request, following the
+        /// [GraphQL multipart request spec][1].  Any `cynic::Upload`s in the
+        /// arguments of the operation are sent as files.
+        ///
+        /// The full query is always sent, even if the operation uses automatic
+        /// persisted queries.
+        ///
+        /// If a `cynic::ResponseDecodeError` occurs it can be obtained via downcast_ref on
+        /// the `surf::Error`.
+        ///
+        /// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+        fn run_graphql_multipart<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
+            self,
+            operation: Operation<'a, ResponseData, ErrorExtensions>,
+        ) -> BoxFuture<'a, Result<GraphQlResponse<ResponseData, ErrorExtensions>, surf::Error>>;
     }
 
     impl SurfExt for surf::RequestBuilder {
-        fn run_graphql<'a, ResponseData: 'a>(
+        fn run_graphql<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
+            self,
+            operation: Operation<'a, ResponseData, ErrorExtensions>,
+        ) -> BoxFuture<'a, Result<GraphQlResponse<ResponseData, ErrorExtensions>, surf::Error>>
+        {
+            Box::pin(async move {
+                Client::new(SurfRequestBuilderTransport::new(self))
+                    .run(operation)
+                    .await
+                    .map_err(into_surf_error)
+            })
+        }
+
+        fn run_graphql_batch<'a, ResponseData: 'a>(
             self,
-            operation: Operation<'a, ResponseData>,
-        ) -> BoxFuture<'a, Result<GraphQlResponse<ResponseData>, surf::Error>> {
+            batch: BatchOperation<'a, ResponseData>,
+        ) -> BoxFuture<'a, Result<ResponseData, surf::Error>> {
             Box::pin(async move {
-                self.body(json!(&operation))
-                    .recv_json::<GraphQlResponse<serde_json::Value>>()
+                Client::new(SurfRequestBuilderTransport::new(self))
+                    .run_batch(batch)
                     .await
-                    .and_then(|response| operation.decode_response(response).map_err(|e| e.into()))
+                    .map_err(into_surf_error)
+            })
+        }
+
+        fn run_graphql_multipart<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
+            self,
+            operation: Operation<'a, ResponseData, ErrorExtensions>,
+        ) -> BoxFuture<'a, Result<GraphQlResponse<ResponseData, ErrorExtensions>, surf::Error>>
+        {
+            Box::pin(async move {
+                let multipart = operation.multipart_body()?;
+
+                self.body(multipart.body)
+                    .header("Content-Type", multipart.content_type.as_str())
+                    .recv_json::<GraphQlResponse<serde_json::Value, ErrorExtensions>>()
+                    .await
+                    .and_then(|response| {
+                        operation
+                            .decode_graphql_response(response)
+                            .map_err(|e| e.into())
+                    })
             })
         }
     }
@@ -104,15 +170,43 @@ pub enum CynicReqwestError {
     #[error("Error making HTTP request: {0}")]
     ReqwestError(#[from] reqwest::Error),
     #[error("Error decoding GraphQL response: {0}")]
-    DecodeError(#[from] json_decode::DecodeError),
+    DecodeError(#[from] crate::ResponseDecodeError),
+    #[error("Error deserializing GraphQL response: {0}")]
+    SerdeError(#[from] serde_json::Error),
+    #[error(transparent)]
+    TransportError(crate::transport::TransportError),
+}
+
+#[cfg(any(feature = "reqwest", feature = "reqwest-blocking"))]
+impl From<crate::transport::ClientError> for CynicReqwestError {
+    fn from(error: crate::transport::ClientError) -> Self {
+        use crate::transport::{ClientError, TransportError};
+
+        match error {
+            ClientError::Transport(TransportError::Transport(error)) => {
+                match error.downcast::<reqwest::Error>() {
+                    Ok(error) => CynicReqwestError::ReqwestError(*error),
+                    Err(error) => {
+                        CynicReqwestError::TransportError(TransportError::Transport(error))
+                    }
+                }
+            }
+            ClientError::Transport(error) => CynicReqwestError::TransportError(error),
+            ClientError::Serde(error) => CynicReqwestError::SerdeError(error),
+            ClientError::Decode(error) => CynicReqwestError::DecodeError(error),
+        }
+    }
 }
 
 #[cfg(feature = "reqwest")]
 mod reqwest_ext {
     use super::CynicReqwestError;
+    use serde::de::DeserializeOwned;
     use std::{future::Future, pin::Pin};
 
-    use crate::{GraphQlResponse, Operation};
+    use crate::{
+        transport::RequestBuilderTransport, BatchOperation, Client, GraphQlResponse, Operation,
+    };
 
     type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
@@ -168,37 +262,79 @@ mod reqwest_ext {
         /// Runs a GraphQL query with the parameters in RequestBuilder, decodes
         /// the and returns the result.
         ///
-        /// If a `json_decode::Error` occurs it can be obtained via downcast_ref on
+        /// If the operation uses automatic persisted queries and the server
+        /// doesn't recognise the query hash, this retries with the full query.
+        ///
+        /// If a `cynic::ResponseDecodeError` occurs it can be obtained via downcast_ref on
         /// the `surf::Error`.
-        fn run_graphql<'a, ResponseData: 'a>(
+        fn run_graphql<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
+            self,
+            operation: Operation<'a, ResponseData, ErrorExtensions>,
+        ) -> BoxFuture<'a, Result<GraphQlResponse<ResponseData, ErrorExtensions>, CynicReqwestError>>;
+
+        /// Runs a batch of GraphQL queries in a single request with the
+        /// parameters in RequestBuilder, decodes each of the responses and
+        /// returns the results.
+        fn run_graphql_batch<'a, ResponseData: 'a>(
             self,
-            operation: Operation<'a, ResponseData>,
-        ) -> BoxFuture<'a, Result<GraphQlResponse<ResponseData>, CynicReqwestError>>;
+            batch: BatchOperation<'a, ResponseData>,
+        ) -> BoxFuture<'a, Result<ResponseData, CynicReqwestError>>;
+
+        /// Runs a GraphQL query as a multipart request, following the
+        /// [GraphQL multipart request spec][1].  Any `cynic::Upload`s in the
+        /// arguments of the operation are sent as files.
+        ///
+        /// The full query is always sent, even if the operation uses automatic
+        /// persisted queries.
+        ///
+        /// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+        fn run_graphql_multipart<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
+            self,
+            operation: Operation<'a, ResponseData, ErrorExtensions>,
+        ) -> BoxFuture<'a, Result<GraphQlResponse<ResponseData, ErrorExtensions>, CynicReqwestError>>;
     }
 
     impl ReqwestExt for reqwest::RequestBuilder {
-        fn run_graphql<'a, ResponseData: 'a>(
+        fn run_graphql<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
+            self,
+            operation: Operation<'a, ResponseData, ErrorExtensions>,
+        ) -> BoxFuture<'a, Result<GraphQlResponse<ResponseData, ErrorExtensions>, CynicReqwestError>>
+        {
+            Box::pin(async move {
+                Ok(Client::new(RequestBuilderTransport::new(self))
+                    .run(operation)
+                    .await?)
+            })
+        }
+
+        fn run_graphql_batch<'a, ResponseData: 'a>(
             self,
-            operation: Operation<'a, ResponseData>,
-        ) -> BoxFuture<'a, Result<GraphQlResponse<ResponseData>, CynicReqwestError>> {
+            batch: BatchOperation<'a, ResponseData>,
+        ) -> BoxFuture<'a, Result<ResponseData, CynicReqwestError>> {
             Box::pin(async move {
-                match self
-                    .json(&operation)
+                Ok(Client::new(RequestBuilderTransport::new(self))
+                    .run_batch(batch)
+                    .await?)
+            })
+        }
+
+        fn run_graphql_multipart<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
+            self,
+            operation: Operation<'a, ResponseData, ErrorExtensions>,
+        ) -> BoxFuture<'a, Result<GraphQlResponse<ResponseData, ErrorExtensions>, CynicReqwestError>>
+        {
+            Box::pin(async move {
+                let multipart = operation.multipart_body()?;
+
+                let response = self
+                    .header(reqwest::header::CONTENT_TYPE, multipart.content_type)
+                    .body(multipart.body)
                     .send()
-                    //.recv_json::<GraphQlResponse<serde_json::Value>>()
-                    .await
-                {
-                    Ok(response) => response
-                        .json::<GraphQlResponse<serde_json::Value>>()
-                        .await
-                        .map_err(CynicReqwestError::ReqwestError)
-                        .and_then(|gql_response| {
-                            operation
-                                .decode_response(gql_response)
-                                .map_err(CynicReqwestError::DecodeError)
-                        }),
-                    Err(e) => Err(CynicReqwestError::ReqwestError(e)),
-                }
+                    .await?
+                    .json::<GraphQlResponse<serde_json::Value, ErrorExtensions>>()
+                    .await?;
+
+                Ok(operation.decode_graphql_response(response)?)
             })
         }
     }
@@ -207,8 +343,12 @@ mod reqwest_ext {
 #[cfg(feature = "reqwest-blocking")]
 mod reqwest_blocking_ext {
     use super::CynicReqwestError;
+    use serde::de::DeserializeOwned;
 
-    use crate::{GraphQlResponse, Operation};
+    use crate::{
+        transport::RequestBuilderTransport, BatchOperation, BlockingClient, GraphQlResponse,
+        Operation,
+    };
 
     /// An extension trait for reqwest::blocking::RequestBuilder.
     ///
@@ -259,28 +399,66 @@ mod reqwest_blocking_ext {
         /// Runs a GraphQL query with the parameters in RequestBuilder, decodes
         /// the and returns the result.
         ///
-        /// If a `json_decode::Error` occurs it can be obtained via downcast_ref on
+        /// If the operation uses automatic persisted queries and the server
+        /// doesn't recognise the query hash, this retries with the full query.
+        ///
+        /// If a `cynic::ResponseDecodeError` occurs it can be obtained via downcast_ref on
         /// the `surf::Error`.
-        fn run_graphql<'a, ResponseData: 'a>(
+        fn run_graphql<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
+            self,
+            operation: Operation<'a, ResponseData, ErrorExtensions>,
+        ) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, CynicReqwestError>;
+
+        /// Runs a batch of GraphQL queries in a single request with the
+        /// parameters in RequestBuilder, decodes each of the responses and
+        /// returns the results.
+        fn run_graphql_batch<'a, ResponseData: 'a>(
+            self,
+            batch: BatchOperation<'a, ResponseData>,
+        ) -> Result<ResponseData, CynicReqwestError>;
+
+        /// Runs a GraphQL query as a multipart request, following the
+        /// [GraphQL multipart request spec][1].  Any `cynic::Upload`s in the
+        /// arguments of the operation are sent as files.
+        ///
+        /// The full query is always sent, even if the operation uses automatic
+        /// persisted queries.
+        ///
+        /// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+        fn run_graphql_multipart<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
             self,
-            operation: Operation<'a, ResponseData>,
-        ) -> Result<GraphQlResponse<ResponseData>, CynicReqwestError>;
+            operation: Operation<'a, ResponseData, ErrorExtensions>,
+        ) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, CynicReqwestError>;
     }
 
     impl ReqwestBlockingExt for reqwest::blocking::RequestBuilder {
-        fn run_graphql<'a, ResponseData: 'a>(
+        fn run_graphql<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
             self,
-            operation: Operation<'a, ResponseData>,
-        ) -> Result<GraphQlResponse<ResponseData>, CynicReqwestError> {
-            self.json(&operation)
-                .send()
-                .and_then(|response| response.json::<GraphQlResponse<serde_json::Value>>())
-                .map_err(CynicReqwestError::ReqwestError)
-                .and_then(|gql_response| {
-                    operation
-                        .decode_response(gql_response)
-                        .map_err(CynicReqwestError::DecodeError)
-                })
+            operation: Operation<'a, ResponseData, ErrorExtensions>,
+        ) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, CynicReqwestError> {
+            Ok(BlockingClient::new(RequestBuilderTransport::new(self)).run(operation)?)
+        }
+
+        fn run_graphql_batch<'a, ResponseData: 'a>(
+            self,
+            batch: BatchOperation<'a, ResponseData>,
+        ) -> Result<ResponseData, CynicReqwestError> {
+            Ok(BlockingClient::new(RequestBuilderTransport::new(self)).run_batch(batch)?)
+        }
+
+        fn run_graphql_multipart<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
+            self,
+            operation: Operation<'a, ResponseData, ErrorExtensions>,
+        ) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, CynicReqwestError> {
+            let multipart = operation.multipart_body()?;
+
+            let response = self
+                .header(reqwest::header::CONTENT_TYPE, multipart.content_type)
+                .body(multipart.body)
+                .send()?
+                .json::<GraphQlResponse<serde_json::Value, ErrorExtensions>>()?;
+
+            Ok(operation.decode_graphql_response(response)?)
         }
     }
 }
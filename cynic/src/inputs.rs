@@ -1,3 +1,5 @@
+use crate::MaybeUndefined;
+
 pub enum NamedType {}
 
 pub struct Nullable<T>(std::marker::PhantomData<T>);
@@ -16,16 +18,30 @@ pub struct List<T>(std::marker::PhantomData<T>);
 pub trait InputType<NamedType, Wrappers>: serde::Serialize {
     //fn as_serializable(&self) -> Self::Output;
     //fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    /// Whether this value should be left out of an `InputObject` or field
+    /// arguments entirely.  This is only true for `MaybeUndefined::Undefined`.
+    fn is_undefined(&self) -> bool {
+        false
+    }
 }
 
-impl<T: ?Sized, TypeLock, Wrappers> InputType<TypeLock, Wrappers> for &T where
-    T: InputType<TypeLock, Wrappers>
+impl<T: ?Sized, TypeLock, Wrappers> InputType<TypeLock, Wrappers> for &T
+where
+    T: InputType<TypeLock, Wrappers>,
 {
+    fn is_undefined(&self) -> bool {
+        T::is_undefined(self)
+    }
 }
 
-impl<T: ?Sized, TypeLock, Wrappers> InputType<TypeLock, Wrappers> for Box<T> where
-    T: InputType<TypeLock, Wrappers>
+impl<T: ?Sized, TypeLock, Wrappers> InputType<TypeLock, Wrappers> for Box<T>
+where
+    T: InputType<TypeLock, Wrappers>,
 {
+    fn is_undefined(&self) -> bool {
+        T::is_undefined(self)
+    }
 }
 
 impl<T: ?Sized, TypeLock, Wrappers> InputType<TypeLock, Wrappers> for std::rc::Rc<T>
@@ -33,6 +49,9 @@ where
     T: InputType<TypeLock, Wrappers>,
     std::rc::Rc<T>: serde::Serialize,
 {
+    fn is_undefined(&self) -> bool {
+        T::is_undefined(self)
+    }
 }
 
 impl<T: ?Sized, TypeLock, Wrappers> InputType<TypeLock, Wrappers> for std::sync::Arc<T>
@@ -40,6 +59,21 @@ where
     T: InputType<TypeLock, Wrappers>,
     std::sync::Arc<T>: serde::Serialize,
 {
+    fn is_undefined(&self) -> bool {
+        T::is_undefined(self)
+    }
+}
+
+// MaybeUndefined can be used anywhere a nullable input is expected.  This
+// covers any type with an InputType impl, including those from
+// `impl_input_type!`.
+impl<T, TypeLock, Wrappers> InputType<TypeLock, Nullable<Wrappers>> for MaybeUndefined<T>
+where
+    T: InputType<TypeLock, Wrappers>,
+{
+    fn is_undefined(&self) -> bool {
+        MaybeUndefined::is_undefined(self)
+    }
 }
 
 impl<'a> InputType<String, NamedType> for &'a str {}
@@ -0,0 +1,196 @@
+//! `Scalar` impls for third party types, behind feature flags.
+//!
+//! These are implemented for every type lock that implements `IsScalar`, so
+//! users can use them with any custom scalar in their schema that they've
+//! opted in.  Scalars don't implement `IsScalar` unless users ask them to, so
+//! enabling one of these features can't conflict with any `impl_scalar!`
+//! calls a crate already has.
+
+// Not every feature uses this macro, so it can be unused when only some are
+// enabled.
+#[allow(unused_macros)]
+macro_rules! impl_scalar_for_custom_scalars {
+    ($type:ty) => {
+        impl<TypeLock: $crate::IsScalar> $crate::Scalar<TypeLock> for $type {
+            type Deserialize = $type;
+
+            fn from_deserialize(x: $type) -> Result<$type, $crate::DecodeError> {
+                Ok(x)
+            }
+        }
+
+        impl<TypeLock: $crate::IsScalar> $crate::InputType<TypeLock, $crate::inputs::NamedType>
+            for $type
+        {
+        }
+
+        impl<TypeLock: $crate::IsScalar>
+            $crate::InputType<TypeLock, $crate::inputs::Nullable<$crate::inputs::NamedType>>
+            for $type
+        {
+        }
+
+        impl<TypeLock: $crate::IsScalar>
+            $crate::InputType<TypeLock, $crate::inputs::Nullable<$crate::inputs::NamedType>>
+            for Option<$type>
+        {
+        }
+
+        impl<'a, TypeLock: $crate::IsScalar>
+            $crate::InputType<TypeLock, $crate::inputs::Nullable<$crate::inputs::NamedType>>
+            for Option<&'a $type>
+        {
+        }
+
+        impl<TypeLock: $crate::IsScalar>
+            $crate::InputType<
+                TypeLock,
+                $crate::inputs::Nullable<$crate::inputs::List<$crate::inputs::NamedType>>,
+            > for Option<Vec<$type>>
+        {
+        }
+
+        impl<TypeLock: $crate::IsScalar>
+            $crate::InputType<TypeLock, $crate::inputs::List<$crate::inputs::NamedType>>
+            for Vec<$type>
+        {
+        }
+
+        impl<TypeLock: $crate::IsScalar>
+            $crate::InputType<
+                TypeLock,
+                $crate::inputs::Nullable<
+                    $crate::inputs::List<$crate::inputs::Nullable<$crate::inputs::NamedType>>,
+                >,
+            > for Option<Vec<Option<$type>>>
+        {
+        }
+    };
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    impl_scalar_for_custom_scalars!(chrono::DateTime<chrono::Utc>);
+    impl_scalar_for_custom_scalars!(chrono::DateTime<chrono::FixedOffset>);
+    impl_scalar_for_custom_scalars!(chrono::NaiveDateTime);
+    impl_scalar_for_custom_scalars!(chrono::NaiveDate);
+    impl_scalar_for_custom_scalars!(chrono::NaiveTime);
+}
+
+#[cfg(feature = "uuid")]
+impl_scalar_for_custom_scalars!(uuid::Uuid);
+
+#[cfg(feature = "url")]
+impl_scalar_for_custom_scalars!(url::Url);
+
+#[cfg(feature = "rust_decimal")]
+impl_scalar_for_custom_scalars!(rust_decimal::Decimal);
+
+#[cfg(feature = "bigdecimal")]
+impl_scalar_for_custom_scalars!(bigdecimal::BigDecimal);
+
+#[cfg(feature = "time")]
+pub use self::time_impls::Rfc3339DateTime;
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::OffsetDateTime;
+
+    /// A `time::OffsetDateTime` that's sent as an RFC3339 string, for use with
+    /// scalars like `DateTime`.
+    ///
+    /// The serde impls of `OffsetDateTime` itself don't use RFC3339, so this
+    /// newtype is needed to match what most GraphQL servers expect.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct Rfc3339DateTime(pub OffsetDateTime);
+
+    impl Rfc3339DateTime {
+        pub fn into_inner(self) -> OffsetDateTime {
+            self.0
+        }
+    }
+
+    impl From<OffsetDateTime> for Rfc3339DateTime {
+        fn from(datetime: OffsetDateTime) -> Self {
+            Rfc3339DateTime(datetime)
+        }
+    }
+
+    impl serde::Serialize for Rfc3339DateTime {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            time::serde::rfc3339::serialize(&self.0, serializer)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for Rfc3339DateTime {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            time::serde::rfc3339::deserialize(deserializer).map(Rfc3339DateTime)
+        }
+    }
+
+    impl_scalar_for_custom_scalars!(Rfc3339DateTime);
+}
+
+#[cfg(feature = "bytes")]
+pub use self::bytes::Base64Bytes;
+
+#[cfg(feature = "bytes")]
+mod bytes {
+    use serde::de::Error;
+
+    /// Binary data that's sent as a base64 encoded string, for use with
+    /// scalars like `Base64` or `Bytes`.
+    ///
+    /// Like the other third party scalar impls, this can be used with any
+    /// custom scalar in a schema that implements `IsScalar`.
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct Base64Bytes(pub Vec<u8>);
+
+    impl Base64Bytes {
+        pub fn into_inner(self) -> Vec<u8> {
+            self.0
+        }
+    }
+
+    impl From<Vec<u8>> for Base64Bytes {
+        fn from(bytes: Vec<u8>) -> Self {
+            Base64Bytes(bytes)
+        }
+    }
+
+    impl AsRef<[u8]> for Base64Bytes {
+        fn as_ref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    impl serde::Serialize for Base64Bytes {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(&base64::encode(&self.0))
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for Base64Bytes {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let encoded = String::deserialize(deserializer)?;
+
+            base64::decode(&encoded)
+                .map(Base64Bytes)
+                .map_err(|e| D::Error::custom(format!("Invalid base64 data: {}", e)))
+        }
+    }
+
+    impl_scalar_for_custom_scalars!(Base64Bytes);
+}
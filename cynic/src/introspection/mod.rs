@@ -0,0 +1,227 @@
+//! A built in introspection query.
+//!
+//! `IntrospectionQuery::build(())` builds an `Operation` that fetches the full
+//! schema of a GraphQL server.  The results can be serialized back into JSON
+//! with serde, and written to an `introspection.json` file that `use_schema!`
+//! & the derives can use in place of a `.graphql` file.
+//!
+//! ```rust,no_run
+//! # fn run_against_server<T: serde::Serialize>(_: &T) -> cynic::GraphQlResponse<serde_json::Value> {
+//! #     unimplemented!()
+//! # }
+//! use cynic::{introspection::IntrospectionQuery, QueryBuilder};
+//!
+//! let operation = IntrospectionQuery::build(());
+//! let data = operation
+//!     .decode_response(run_against_server(&operation))
+//!     .unwrap()
+//!     .unwrap();
+//!
+//! std::fs::write("introspection.json", serde_json::to_string(&data).unwrap())
+//! .unwrap();
+//! ```
+
+use serde::Serialize;
+
+mod schema {
+    cynic::use_schema!("src/introspection/introspection.graphql");
+}
+
+/// The standard introspection query, which fetches a servers entire schema.
+#[derive(cynic::QueryFragment, Serialize, Debug, Clone, PartialEq)]
+#[cynic(
+    schema_path = "src/introspection/introspection.graphql",
+    query_module = "schema",
+    graphql_type = "Query"
+)]
+pub struct IntrospectionQuery {
+    #[cynic(rename = "__schema")]
+    #[serde(rename = "__schema")]
+    pub schema: Schema,
+}
+
+/// The schema of a GraphQL server.
+#[derive(cynic::QueryFragment, Serialize, Debug, Clone, PartialEq)]
+#[cynic(
+    schema_path = "src/introspection/introspection.graphql",
+    query_module = "schema",
+    graphql_type = "__Schema"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema {
+    pub query_type: NamedType,
+    pub mutation_type: Option<NamedType>,
+    pub subscription_type: Option<NamedType>,
+    pub types: Vec<FullType>,
+    pub directives: Vec<Directive>,
+}
+
+/// A reference to a named type.
+#[derive(cynic::QueryFragment, Serialize, Debug, Clone, PartialEq)]
+#[cynic(
+    schema_path = "src/introspection/introspection.graphql",
+    query_module = "schema",
+    graphql_type = "__Type"
+)]
+pub struct NamedType {
+    pub name: Option<String>,
+}
+
+/// The full details of a type in the schema.
+#[derive(cynic::QueryFragment, Serialize, Debug, Clone, PartialEq)]
+#[cynic(
+    schema_path = "src/introspection/introspection.graphql",
+    query_module = "schema",
+    graphql_type = "__Type"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct FullType {
+    pub kind: TypeKind,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[arguments(include_deprecated = true)]
+    pub fields: Option<Vec<Field>>,
+    pub input_fields: Option<Vec<InputValue>>,
+    pub interfaces: Option<Vec<TypeRef>>,
+    #[arguments(include_deprecated = true)]
+    pub enum_values: Option<Vec<EnumValue>>,
+    pub possible_types: Option<Vec<TypeRef>>,
+}
+
+/// A field of an object or interface.
+#[derive(cynic::QueryFragment, Serialize, Debug, Clone, PartialEq)]
+#[cynic(
+    schema_path = "src/introspection/introspection.graphql",
+    query_module = "schema",
+    graphql_type = "__Field"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Field {
+    pub name: String,
+    pub description: Option<String>,
+    pub args: Vec<InputValue>,
+    #[cynic(rename = "type")]
+    #[serde(rename = "type")]
+    pub ty: TypeRef,
+    pub is_deprecated: bool,
+    pub deprecation_reason: Option<String>,
+}
+
+/// An argument to a field or directive, or a field of an input object.
+#[derive(cynic::QueryFragment, Serialize, Debug, Clone, PartialEq)]
+#[cynic(
+    schema_path = "src/introspection/introspection.graphql",
+    query_module = "schema",
+    graphql_type = "__InputValue"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct InputValue {
+    pub name: String,
+    pub description: Option<String>,
+    #[cynic(rename = "type")]
+    #[serde(rename = "type")]
+    pub ty: TypeRef,
+    pub default_value: Option<String>,
+}
+
+/// A value of an enum type.
+#[derive(cynic::QueryFragment, Serialize, Debug, Clone, PartialEq)]
+#[cynic(
+    schema_path = "src/introspection/introspection.graphql",
+    query_module = "schema",
+    graphql_type = "__EnumValue"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct EnumValue {
+    pub name: String,
+    pub description: Option<String>,
+    pub is_deprecated: bool,
+    pub deprecation_reason: Option<String>,
+}
+
+/// A reference to a type, which may be wrapped in lists or non-null types.
+#[derive(cynic::QueryFragment, Serialize, Debug, Clone, PartialEq)]
+#[cynic(
+    schema_path = "src/introspection/introspection.graphql",
+    query_module = "schema",
+    graphql_type = "__Type"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeRef {
+    pub kind: TypeKind,
+    pub name: Option<String>,
+    #[cynic(recurse = "7")]
+    pub of_type: Box<Option<TypeRef>>,
+}
+
+/// A directive supported by the server.
+#[derive(cynic::QueryFragment, Serialize, Debug, Clone, PartialEq)]
+#[cynic(
+    schema_path = "src/introspection/introspection.graphql",
+    query_module = "schema",
+    graphql_type = "__Directive"
+)]
+pub struct Directive {
+    pub name: String,
+    pub description: Option<String>,
+    pub locations: Vec<DirectiveLocation>,
+    pub args: Vec<InputValue>,
+}
+
+/// The different kinds of type in a GraphQL schema.
+#[derive(cynic::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[cynic(
+    schema_path = "src/introspection/introspection.graphql",
+    query_module = "schema",
+    graphql_type = "__TypeKind"
+)]
+pub enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+    List,
+    NonNull,
+}
+
+/// The locations a directive can be used in.
+#[derive(cynic::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[cynic(
+    schema_path = "src/introspection/introspection.graphql",
+    query_module = "schema",
+    graphql_type = "__DirectiveLocation"
+)]
+pub enum DirectiveLocation {
+    Query,
+    Mutation,
+    Subscription,
+    Field,
+    FragmentDefinition,
+    FragmentSpread,
+    InlineFragment,
+    VariableDefinition,
+    Schema,
+    Scalar,
+    Object,
+    FieldDefinition,
+    ArgumentDefinition,
+    Interface,
+    Union,
+    Enum,
+    EnumValue,
+    InputObject,
+    InputFieldDefinition,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::QueryBuilder;
+
+    #[test]
+    fn test_introspection_query() {
+        insta::assert_snapshot!(IntrospectionQuery::build(()).query);
+    }
+}
@@ -94,8 +94,8 @@
 //! let result = query.decode_response(response.json()?)?;
 //! ```
 //!
-//! After this code has run, result will be an instance of `FilmDirectorQuery`
-//! with the film populated appropriately.
+//! After this code has run, result will be a `GraphQlResult` containing an
+//! instance of `FilmDirectorQuery` with the film populated appropriately.
 //!
 //! ### Dynamic Query Arguments
 //!
@@ -156,6 +156,26 @@
 //!   [`reqwest`](https://github.com/seanmonstar/reqwest) http client.
 //! - `rewest-blocking` adds blocking integration with the
 //!   [`reqwest`](https://github.com/seanmonstar/reqwest) http client.
+//! - `ws` adds support for running subscriptions over websockets, using
+//!   the message types from [`tungstenite`](https://github.com/snapview/tungstenite-rs).
+//!
+//! There are also features that implement `Scalar` for some common third party
+//! types.  These can be used with any custom scalar in a schema that
+//! implements `IsScalar`, without calling `impl_scalar!`:
+//!
+//! - `chrono` supports `DateTime<Utc>`, `DateTime<FixedOffset>`,
+//!   `NaiveDateTime`, `NaiveDate` & `NaiveTime` from
+//!   [`chrono`](https://github.com/chronotope/chrono).
+//! - `time` adds `cynic::Rfc3339DateTime`, which wraps an `OffsetDateTime`
+//!   from [`time`](https://github.com/time-rs/time).
+//! - `uuid` supports `Uuid` from [`uuid`](https://github.com/uuid-rs/uuid).
+//! - `url` supports `Url` from [`url`](https://github.com/servo/rust-url).
+//! - `rust_decimal` supports `Decimal` from
+//!   [`rust_decimal`](https://github.com/paupino/rust-decimal).
+//! - `bigdecimal` supports `BigDecimal` from
+//!   [`bigdecimal`](https://github.com/akubera/bigdecimal-rs).
+//! - `bytes` adds `cynic::Base64Bytes`, for scalars that contain base64
+//!   encoded binary data.
 //!
 //! It's worth noting that each of these features pulls in extra
 //! dependencies, which may impact your build size.  Particularly
@@ -165,32 +185,68 @@
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+// Lets the derives that cynic uses internally refer to `::cynic`
+extern crate self as cynic;
+
 mod arguments;
+mod batch;
 mod builders;
+mod decode_error;
 mod enums;
 mod fragments;
 mod id;
+mod integrations;
+mod maybe_undefined;
 mod operation;
 mod result;
 mod scalar;
+mod upload;
 
+pub mod analysis;
 pub mod http;
 pub mod inputs;
+pub mod introspection;
+pub mod relay;
 pub mod selection_set;
+pub mod transport;
 pub mod utils;
 
+#[doc(hidden)]
+pub mod private;
+
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
+#[cfg(feature = "ws")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ws")))]
+pub mod ws;
+
 pub use json_decode::DecodeError;
 
-pub use arguments::{Argument, FromArguments};
+pub use arguments::{Argument, ArgumentSource, FromArguments};
+pub use batch::BatchOperation;
 pub use builders::{MutationBuilder, QueryBuilder, SubscriptionBuilder};
+pub use decode_error::{PathSegment, ResponseDecodeError};
 pub use enums::Enum;
 pub use fragments::{FragmentArguments, FragmentContext, InlineFragments, QueryFragment};
 pub use id::Id;
 pub use inputs::InputType;
-pub use operation::{Operation, StreamingOperation};
+pub use maybe_undefined::MaybeUndefined;
+pub use operation::{Operation, PersistedQueryManifest, StreamingOperation};
 pub use result::{GraphQlError, GraphQlResponse, GraphQlResult, PossiblyParsedData};
 pub use scalar::Scalar;
 pub use selection_set::SelectionSet;
+pub use transport::{BlockingClient, BlockingTransport, Client, Transport};
+pub use upload::Upload;
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+pub use integrations::Base64Bytes;
+
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+pub use integrations::Rfc3339DateTime;
 
 #[allow(deprecated)]
 pub use result::{GraphQLError, GraphQLResponse, GraphQLResult};
@@ -242,3 +298,37 @@ pub trait MutationRoot {}
 /// A marker trait that indicates a particular type is at the root of a GraphQL schemas
 /// subscription hierarchy.
 pub trait SubscriptionRoot {}
+
+/// A marker trait that opts a custom scalar in a GraphQL schema in to the
+/// scalar impls for third party types (behind the `chrono`, `uuid` etc.
+/// feature flags).
+///
+/// `use_schema!` doesn't implement this, so that enabling one of those
+/// features can't conflict with an `impl_scalar!` for the same type.  It can
+/// be implemented for any scalar in a `schema` module:
+///
+/// ```rust
+/// mod schema {
+///     cynic::use_schema!("../schemas/github.graphql");
+///
+///     impl cynic::IsScalar for DateTime {}
+/// }
+/// ```
+pub trait IsScalar {}
+
+/// A marker trait that indicates a particular type represents a custom scalar
+/// that holds integers, such as `Long` or `BigInt`.
+///
+/// Any of Rusts integer types can be used for scalars that implement this.
+/// Like `IsScalar`, `use_schema!` doesn't implement this so it can't conflict
+/// with an `impl_scalar!` for the same scalar.  It can be implemented for any
+/// scalar in a `schema` module:
+///
+/// ```rust
+/// mod schema {
+///     cynic::use_schema!("tests/integers-schema.graphql");
+///
+///     impl cynic::IsIntegerScalar for BigInt {}
+/// }
+/// ```
+pub trait IsIntegerScalar {}
@@ -0,0 +1,80 @@
+/// An input value that can be provided, explicitly set to `null`, or left out
+/// of a request entirely.
+///
+/// Many servers treat a missing input field or argument differently from one
+/// that's `null` - e.g. an update mutation might leave a field unchanged if
+/// it's missing, but clear it if it's `null`.  An `Option` can only express
+/// one of these, so `MaybeUndefined` can be used in its place for any
+/// nullable input field or argument:
+///
+/// ```rust,ignore
+/// #[derive(cynic::InputObject)]
+/// struct UpdateFilmInput {
+///     title: MaybeUndefined<String>,
+///     director: MaybeUndefined<String>,
+/// }
+/// ```
+///
+/// `Undefined` values are left out of `InputObject`s & arguments.  Anywhere
+/// else they're serialized as `null`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum MaybeUndefined<T> {
+    /// The value is left out of the request.
+    #[default]
+    Undefined,
+    /// The value is sent as `null`.
+    Null,
+    /// The value is sent as `T`.
+    Value(T),
+}
+
+impl<T> MaybeUndefined<T> {
+    /// Returns true if this is `MaybeUndefined::Undefined`.
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, MaybeUndefined::Undefined)
+    }
+
+    /// Returns true if this is `MaybeUndefined::Null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, MaybeUndefined::Null)
+    }
+
+    /// Returns the value, if there is one.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            MaybeUndefined::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Converts this into an `Option<Option<T>>`, where the outer `Option` is
+    /// `None` if the value is undefined.
+    pub fn into_option(self) -> Option<Option<T>> {
+        match self {
+            MaybeUndefined::Undefined => None,
+            MaybeUndefined::Null => Some(None),
+            MaybeUndefined::Value(value) => Some(Some(value)),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for MaybeUndefined<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => MaybeUndefined::Value(value),
+            None => MaybeUndefined::Null,
+        }
+    }
+}
+
+impl<T: serde::Serialize> serde::Serialize for MaybeUndefined<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            MaybeUndefined::Value(value) => value.serialize(serializer),
+            MaybeUndefined::Null | MaybeUndefined::Undefined => serializer.serialize_none(),
+        }
+    }
+}
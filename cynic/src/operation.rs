@@ -1,9 +1,13 @@
-use json_decode::BoxDecoder;
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    marker::PhantomData,
+};
 
 use crate::{
-    selection_set::{mutation_root, query_root, subscription_root},
-    Argument, GraphQlResponse, MutationRoot, QueryRoot, SelectionSet, SubscriptionRoot,
+    result::null_error_paths,
+    selection_set::{mutation_root, query_root, subscription_root, BoxDecoder, Document, Style},
+    Argument, GraphQlResponse, GraphQlResult, MutationRoot, PossiblyParsedData, QueryRoot,
+    ResponseDecodeError, SelectionSet, SubscriptionRoot,
 };
 
 /// An Operation that can be sent to a remote GraphQL server.
@@ -11,58 +15,314 @@ use crate::{
 /// This contains a GraphQL query string and variable HashMap.  It can be
 /// serialized into JSON with `serde::Serialize` and sent to a remote server,
 /// and has a `decode_response` function that knows how to decode a response.
-#[derive(serde::Serialize)]
-pub struct Operation<'a, ResponseData> {
+///
+/// The query is pretty printed by default.  `with_compact_query` switches it
+/// to a minified form, which makes for smaller requests.
+///
+/// The `extensions` of any errors in the response are decoded into
+/// `ErrorExtensions`, which can be changed with `with_error_extensions`.
+pub struct Operation<'a, ResponseData, ErrorExtensions = serde_json::Value> {
     pub query: String,
     pub variables: HashMap<String, Argument>,
-    #[serde(skip)]
+    operation_name: Option<String>,
+    document: Document,
     decoder: BoxDecoder<'a, ResponseData>,
+    persisted_query_hash: Option<String>,
+    phantom: PhantomData<fn() -> ErrorExtensions>,
 }
 
 impl<'a, ResponseData: 'a> Operation<'a, ResponseData> {
     /// Constructs a new Operation from a query `SelectionSet`
     pub fn query<Root: QueryRoot>(selection_set: SelectionSet<'a, ResponseData, Root>) -> Self {
-        let (query, arguments, decoder) = query_root(selection_set).query_arguments_and_decoder();
+        Operation::new(query_root(selection_set, None), None, false)
+    }
 
-        let variables = arguments
-            .into_iter()
-            .enumerate()
-            .map(|(i, a)| (format!("_{}", i), a))
-            .collect();
+    /// Constructs a new Operation named `operation_name` from a query
+    /// `SelectionSet`
+    pub fn named_query<Root: QueryRoot>(
+        operation_name: impl Into<String>,
+        selection_set: SelectionSet<'a, ResponseData, Root>,
+    ) -> Self {
+        let operation_name = operation_name.into();
 
-        Operation {
-            query,
-            variables,
-            decoder,
-        }
+        Operation::new(
+            query_root(selection_set, Some(operation_name.clone())),
+            Some(operation_name),
+            false,
+        )
     }
 
     /// Constructs a new Operation from a mutation `SelectionSet`
     pub fn mutation<Root: MutationRoot>(
         selection_set: SelectionSet<'a, ResponseData, Root>,
     ) -> Self {
-        let (query, arguments, decoder) =
-            mutation_root(selection_set).query_arguments_and_decoder();
+        Operation::new(mutation_root(selection_set, None), None, false)
+    }
+
+    /// Constructs a new Operation named `operation_name` from a mutation
+    /// `SelectionSet`
+    pub fn named_mutation<Root: MutationRoot>(
+        operation_name: impl Into<String>,
+        selection_set: SelectionSet<'a, ResponseData, Root>,
+    ) -> Self {
+        let operation_name = operation_name.into();
+
+        Operation::new(
+            mutation_root(selection_set, Some(operation_name.clone())),
+            Some(operation_name),
+            false,
+        )
+    }
+
+    /// Constructs a new Operation from a root `SelectionSet`.
+    ///
+    /// If `named_fragments` is true then any fragments used more than once
+    /// are output as named fragment definitions.
+    pub(crate) fn new(
+        selection_set: SelectionSet<'a, ResponseData, ()>,
+        operation_name: Option<String>,
+        named_fragments: bool,
+    ) -> Self {
+        let (document, variables, decoder) =
+            selection_set.document_variables_and_decoder(named_fragments);
 
-        let variables = arguments
+        let variables = variables
             .into_iter()
-            .enumerate()
-            .map(|(i, a)| (format!("_{}", i), a))
+            .map(|variable| (variable.name, variable.argument))
             .collect();
 
         Operation {
-            query,
+            query: document.print(Style::Pretty),
             variables,
+            operation_name,
+            document,
             decoder,
+            persisted_query_hash: None,
+            phantom: PhantomData,
         }
     }
+}
 
-    /// Decodes a response.  Note that you need to decode a GraphQlResponse
-    /// from JSON before passing to this function
-    pub fn decode_response(
+impl<'a, ResponseData: 'a, ErrorExtensions> Operation<'a, ResponseData, ErrorExtensions> {
+    /// Changes the type that the `extensions` of any errors in the response
+    /// are decoded into.
+    ///
+    /// ```rust
+    /// # mod schema {
+    /// #   cynic::use_schema!("../schemas/starwars.schema.graphql");
+    /// # }
+    /// #
+    /// # #[derive(cynic::QueryFragment)]
+    /// # #[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+    /// # struct Film {
+    /// #    title: Option<String>,
+    /// # }
+    /// #
+    /// # #[derive(cynic::QueryFragment)]
+    /// # #[cynic(schema_path = "../schemas/starwars.schema.graphql", graphql_type = "Root")]
+    /// # struct FilmQuery {
+    /// #     #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+    /// #     film: Option<Film>,
+    /// # }
+    /// use cynic::QueryBuilder;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// #[serde(rename_all = "camelCase")]
+    /// struct MyExtensions {
+    ///     code: String,
+    ///     retry_after: Option<u32>,
+    /// }
+    ///
+    /// let operation = FilmQuery::build(()).with_error_extensions::<MyExtensions>();
+    /// ```
+    pub fn with_error_extensions<NewExtensions>(
+        self,
+    ) -> Operation<'a, ResponseData, NewExtensions> {
+        Operation {
+            query: self.query,
+            variables: self.variables,
+            operation_name: self.operation_name,
+            document: self.document,
+            decoder: self.decoder,
+            persisted_query_hash: self.persisted_query_hash,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Enables [automatic persisted queries][1] for this Operation.
+    ///
+    /// When serialized this Operation will send the sha256 hash of its query
+    /// in `extensions.persistedQuery` rather than the full query string.  If
+    /// the server doesn't know that hash yet it responds with a
+    /// `PersistedQueryNotFound` error, at which point the HTTP extension
+    /// traits in `cynic::http` retry with the full query.
+    ///
+    /// [1]: https://www.apollographql.com/docs/apollo-server/performance/apq/
+    pub fn with_automatic_persisted_queries(mut self) -> Self {
+        self.persisted_query_hash = Some(sha256_hash(&self.query));
+        self
+    }
+
+    /// Switches the query of this Operation to its compact form.
+    ///
+    /// The compact form has all insignificant whitespace removed, which
+    /// makes for smaller requests.  If automatic persisted queries are
+    /// enabled the hash is updated to match.
+    pub fn with_compact_query(mut self) -> Self {
+        self.query = self.compact_query();
+        if self.persisted_query_hash.is_some() {
+            self.persisted_query_hash = Some(sha256_hash(&self.query));
+        }
+        self
+    }
+
+    /// Adds `__typename` to every selection set in the query of this
+    /// Operation.
+    ///
+    /// `InMemoryCache` needs the `__typename` of each object in a response to
+    /// normalise it, so `Client`s with a cache call this on every operation
+    /// they run.  The query keeps its current style & any persisted query
+    /// hash is updated to match.
+    pub fn with_typenames(mut self) -> Self {
+        let compact = self.query == self.compact_query();
+        self.document.add_typenames();
+        self.query = if compact {
+            self.compact_query()
+        } else {
+            self.pretty_query()
+        };
+        if self.persisted_query_hash.is_some() {
+            self.persisted_query_hash = Some(sha256_hash(&self.query));
+        }
+        self
+    }
+
+    /// The query of this Operation, pretty printed.
+    pub fn pretty_query(&self) -> String {
+        self.document.print(Style::Pretty)
+    }
+
+    /// The query of this Operation, with all insignificant whitespace
+    /// removed.
+    pub fn compact_query(&self) -> String {
+        self.document.print(Style::Compact)
+    }
+
+    /// The document that this Operation's query is printed from.
+    pub(crate) fn document(&self) -> &Document {
+        &self.document
+    }
+
+    /// The name of this operation, if it has one.
+    pub fn operation_name(&self) -> Option<&str> {
+        self.operation_name.as_deref()
+    }
+
+    /// The sha256 hash of this Operations query, if automatic persisted
+    /// queries are enabled.
+    pub fn persisted_query_hash(&self) -> Option<&str> {
+        self.persisted_query_hash.as_deref()
+    }
+
+    /// Returns a serializable body for this operation.
+    ///
+    /// If `include_query` is false & automatic persisted queries are enabled
+    /// then the query string is left out.  The body fails to serialize if any
+    /// of the variables contain `Upload`s, as those can only be sent in a
+    /// multipart request.
+    pub(crate) fn body(&self, include_query: bool) -> OperationBody<'_> {
+        let persisted_query =
+            self.persisted_query_hash
+                .as_deref()
+                .map(|sha256_hash| PersistedQueryExtensions {
+                    persisted_query: PersistedQuery {
+                        version: 1,
+                        sha256_hash,
+                    },
+                });
+
+        OperationBody {
+            query: if include_query || persisted_query.is_none() {
+                Some(&self.query)
+            } else {
+                None
+            },
+            variables: Variables {
+                variables: &self.variables,
+                allow_uploads: false,
+            },
+            operation_name: self.operation_name.as_deref(),
+            extensions: persisted_query,
+        }
+    }
+
+    /// Returns a `multipart/form-data` body for this operation, following the
+    /// [GraphQL multipart request spec][1].
+    ///
+    /// Each `Upload` in the variables is sent as a separate file, with its
+    /// path recorded in the `map` field.  The full query is always included.
+    ///
+    /// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+    #[cfg(any(feature = "surf", feature = "reqwest", feature = "reqwest-blocking"))]
+    pub(crate) fn multipart_body(&self) -> Result<crate::upload::MultipartBody, serde_json::Error> {
+        let mut variable_names = self.variables.keys().collect::<Vec<_>>();
+        variable_names.sort();
+
+        let files = variable_names
+            .into_iter()
+            .flat_map(|name| {
+                self.variables[name]
+                    .uploads
+                    .iter()
+                    .map(move |(path, upload)| {
+                        let mut full_path = format!("variables.{}", name);
+                        for segment in path {
+                            full_path.push('.');
+                            full_path.push_str(segment);
+                        }
+                        (full_path, upload)
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        let mut body = self.body(true);
+        body.variables.allow_uploads = true;
+
+        crate::upload::MultipartBody::new(&body, &files)
+    }
+
+    /// Checks whether a response indicates that we should retry with the
+    /// full query, because the server didn't recognise our persisted query.
+    ///
+    /// This takes the raw JSON of the response, as the `ErrorExtensions` of
+    /// this operation might not contain the error code.
+    pub(crate) fn should_retry_with_query(&self, response: &serde_json::Value) -> bool {
+        if self.persisted_query_hash.is_none() {
+            return false;
+        }
+
+        let errors = match response.get("errors").and_then(|errors| errors.as_array()) {
+            Some(errors) => errors,
+            None => return false,
+        };
+
+        errors.iter().any(|error| {
+            error.get("message").and_then(|message| message.as_str())
+                == Some("PersistedQueryNotFound")
+                || error
+                    .pointer("/extensions/code")
+                    .and_then(|code| code.as_str())
+                    == Some("PERSISTED_QUERY_NOT_FOUND")
+        })
+    }
+
+    /// Decodes the data of a response, leaving any errors as they are.
+    ///
+    /// This is used by the clients in cynic, which return a `GraphQlResponse`.
+    pub(crate) fn decode_graphql_response(
         &self,
-        response: GraphQlResponse<serde_json::Value>,
-    ) -> Result<GraphQlResponse<ResponseData>, json_decode::DecodeError> {
+        response: GraphQlResponse<serde_json::Value, ErrorExtensions>,
+    ) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, ResponseDecodeError> {
         if let Some(data) = response.data {
             Ok(GraphQlResponse {
                 data: Some(self.decoder.decode(&data)?),
@@ -75,47 +335,316 @@ impl<'a, ResponseData: 'a> Operation<'a, ResponseData> {
             })
         }
     }
+
+    /// Decodes a response straight from the bytes of a response body.
+    ///
+    /// Rather than parsing the body into a `serde_json::Value` and running
+    /// it through the decoder of this Operation, this uses the
+    /// `serde::Deserialize` impl that the `QueryFragment` derive generates
+    /// for `ResponseData`, which decodes the response in a single pass.
+    /// Strings are borrowed from `body` where possible.
+    pub fn decode_response_slice<'de>(
+        &self,
+        body: &'de [u8],
+    ) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, serde_json::Error>
+    where
+        ResponseData: serde::Deserialize<'de>,
+        ErrorExtensions: serde::Deserialize<'de>,
+    {
+        serde_json::from_slice(body)
+    }
+
+    /// Decodes a response into a `GraphQlResult`.  Note that you need to
+    /// decode a GraphQlResponse from JSON before passing to this function.
+    ///
+    /// If the response has errors then each error is attached to the field
+    /// its `path` points at: that field is treated as null and decoding is
+    /// retried, moving up to the parent field if it still fails.  This lets
+    /// us decode partial data even when a field that isn't an `Option` was
+    /// nulled because of an error.  If the data can't be decoded at all it's
+    /// returned as `PossiblyParsedData::UnparsedData`.
+    ///
+    /// This only returns a `ResponseDecodeError` if the response had no errors but
+    /// the data still couldn't be decoded.
+    pub fn decode_response(
+        &self,
+        response: GraphQlResponse<serde_json::Value, ErrorExtensions>,
+    ) -> Result<GraphQlResult<ResponseData, ErrorExtensions>, ResponseDecodeError> {
+        let errors = response.errors.unwrap_or_default();
+        let data = match response.data {
+            Some(data) => data,
+            None if errors.is_empty() => {
+                return Err(json_decode::DecodeError::Other(
+                    "GraphQL response had no data or errors".into(),
+                )
+                .into())
+            }
+            None => {
+                return Ok(Err((
+                    PossiblyParsedData::UnparsedData(serde_json::Value::Null),
+                    errors,
+                )))
+            }
+        };
+
+        if errors.is_empty() {
+            return self.decoder.decode(&data).map(Ok);
+        }
+
+        let max_depth = errors
+            .iter()
+            .filter_map(|error| error.path.as_ref().map(Vec::len))
+            .max()
+            .unwrap_or(0);
+
+        // First we null out every errored field, moving each of them up a
+        // level at a time until we can decode.  Then we try moving each error
+        // back down individually, so one error that needs its parent nulled
+        // doesn't null the parents of all the others.
+        let decode = |levels: &[usize]| {
+            self.decoder
+                .decode(&null_error_paths(&data, &errors, levels))
+                .ok()
+        };
+
+        let found = (0..max_depth).find_map(|level| {
+            let levels = vec![level; errors.len()];
+            decode(&levels).map(|decoded| (levels, decoded))
+        });
+
+        if let Some((mut levels, mut decoded)) = found {
+            for i in 0..levels.len() {
+                for level in 0..levels[i] {
+                    let mut candidate = levels.clone();
+                    candidate[i] = level;
+                    if let Some(candidate_decoded) = decode(&candidate) {
+                        levels = candidate;
+                        decoded = candidate_decoded;
+                        break;
+                    }
+                }
+            }
+
+            return Ok(Err((PossiblyParsedData::ParsedData(decoded), errors)));
+        }
+
+        match self.decoder.decode(&data) {
+            Ok(decoded) => Ok(Err((PossiblyParsedData::ParsedData(decoded), errors))),
+            Err(_) => Ok(Err((PossiblyParsedData::UnparsedData(data), errors))),
+        }
+    }
+}
+
+impl<'a, ResponseData: 'a, ErrorExtensions> serde::Serialize
+    for Operation<'a, ResponseData, ErrorExtensions>
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.body(false).serialize(serializer)
+    }
+}
+
+/// The JSON body of an Operation.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OperationBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<&'a str>,
+    variables: Variables<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    operation_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extensions: Option<PersistedQueryExtensions<'a>>,
+}
+
+/// The variables of an Operation.
+///
+/// `Upload`s are serialized as `null`, so unless we're building a multipart
+/// request these return an error rather than silently dropping the files.
+struct Variables<'a> {
+    variables: &'a HashMap<String, Argument>,
+    allow_uploads: bool,
+}
+
+impl serde::Serialize for Variables<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::Error;
+
+        if !self.allow_uploads
+            && self
+                .variables
+                .values()
+                .any(|argument| !argument.uploads.is_empty())
+        {
+            return Err(S::Error::custom(
+                "Operations containing Uploads must be sent as multipart requests, \
+                 with one of the run_graphql_multipart functions in cynic::http",
+            ));
+        }
+
+        self.variables.serialize(serializer)
+    }
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedQueryExtensions<'a> {
+    persisted_query: PersistedQuery<'a>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedQuery<'a> {
+    version: u8,
+    sha256_hash: &'a str,
+}
+
+/// A manifest of persisted queries.
+///
+/// Servers that only accept persisted queries can use this to build an
+/// allow-list of the queries a client is going to send.  It serializes as a
+/// JSON object mapping each sha256 hash to its query document.
+///
+/// ```rust,no_run
+/// # mod schema {
+/// #   cynic::use_schema!("../schemas/starwars.schema.graphql");
+/// # }
+/// #
+/// # #[derive(cynic::QueryFragment)]
+/// # #[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+/// # struct Film {
+/// #    title: Option<String>,
+/// # }
+/// #
+/// # #[derive(cynic::QueryFragment)]
+/// # #[cynic(schema_path = "../schemas/starwars.schema.graphql", graphql_type = "Root")]
+/// # struct FilmQuery {
+/// #     #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+/// #     film: Option<Film>,
+/// # }
+/// use cynic::{PersistedQueryManifest, QueryBuilder};
+///
+/// let mut manifest = PersistedQueryManifest::new();
+/// manifest.add(&FilmQuery::build(()));
+///
+/// std::fs::write(
+///     "persisted-queries.json",
+///     serde_json::to_string_pretty(&manifest).unwrap(),
+/// )
+/// .unwrap();
+/// ```
+#[derive(serde::Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(transparent)]
+pub struct PersistedQueryManifest {
+    queries: BTreeMap<String, String>,
+}
+
+impl PersistedQueryManifest {
+    pub fn new() -> Self {
+        PersistedQueryManifest::default()
+    }
+
+    /// Adds the query of an Operation to the manifest.
+    pub fn add<ResponseData, ErrorExtensions>(
+        &mut self,
+        operation: &Operation<'_, ResponseData, ErrorExtensions>,
+    ) -> &mut Self {
+        self.queries
+            .insert(sha256_hash(&operation.query), operation.query.clone());
+        self
+    }
+
+    /// Iterates over the hashes & queries in the manifest.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.queries
+            .iter()
+            .map(|(hash, query)| (hash.as_str(), query.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+}
+
+fn sha256_hash(query: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(query.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
 }
 
 /// A StreamingOperation is an Operation that expects a stream of results.
 ///
 /// Currently this is means subscriptions.
 pub struct StreamingOperation<'a, ResponseData> {
-    inner: Operation<'a, ResponseData>,
+    pub(crate) inner: Operation<'a, ResponseData>,
 }
 
 impl<'a, ResponseData: 'a> StreamingOperation<'a, ResponseData> {
-    /// Constructs a new Operation from a query `SelectionSet`
+    /// Constructs a new Operation from a subscription `SelectionSet`
     pub fn subscription<Root: SubscriptionRoot>(
         selection_set: SelectionSet<'a, ResponseData, Root>,
     ) -> Self {
-        let (query, arguments, decoder) =
-            subscription_root(selection_set).query_arguments_and_decoder();
+        StreamingOperation {
+            inner: Operation::new(subscription_root(selection_set, None), None, false),
+        }
+    }
 
-        let variables = arguments
-            .into_iter()
-            .enumerate()
-            .map(|(i, a)| (format!("_{}", i), a))
-            .collect();
+    /// Constructs a new Operation named `operation_name` from a subscription
+    /// `SelectionSet`
+    pub fn named_subscription<Root: SubscriptionRoot>(
+        operation_name: impl Into<String>,
+        selection_set: SelectionSet<'a, ResponseData, Root>,
+    ) -> Self {
+        let operation_name = operation_name.into();
 
         StreamingOperation {
-            inner: Operation {
-                query,
-                variables,
-                decoder,
-            },
+            inner: Operation::new(
+                subscription_root(selection_set, Some(operation_name.clone())),
+                Some(operation_name),
+                false,
+            ),
         }
     }
 
+    /// Switches the query of this operation to its compact form.  See
+    /// `Operation::with_compact_query` for details.
+    pub fn with_compact_query(self) -> Self {
+        StreamingOperation {
+            inner: self.inner.with_compact_query(),
+        }
+    }
+
+    /// Decodes a response into a `GraphQlResult`.  See
+    /// `Operation::decode_response` for details.
     pub fn decode_response(
         &self,
         response: GraphQlResponse<serde_json::Value>,
-    ) -> Result<GraphQlResponse<ResponseData>, json_decode::DecodeError> {
+    ) -> Result<GraphQlResult<ResponseData>, ResponseDecodeError> {
         self.inner.decode_response(response)
     }
+
+    #[cfg(feature = "ws")]
+    pub(crate) fn decode_graphql_response(
+        &self,
+        response: GraphQlResponse<serde_json::Value>,
+    ) -> Result<GraphQlResponse<ResponseData>, ResponseDecodeError> {
+        self.inner.decode_graphql_response(response)
+    }
 }
 
-impl<ResponseData> serde::Serialize for StreamingOperation<'_, ResponseData> {
+impl<'a, ResponseData: 'a> serde::Serialize for StreamingOperation<'a, ResponseData> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
@@ -123,3 +652,161 @@ impl<ResponseData> serde::Serialize for StreamingOperation<'_, ResponseData> {
         self.inner.serialize(serializer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{QueryBuilder, QueryFragment};
+    use serde_json::json;
+
+    mod schema {
+        cynic::use_schema!("../schemas/starwars.schema.graphql");
+    }
+
+    #[derive(cynic::QueryFragment)]
+    #[cynic(
+        schema_path = "../schemas/starwars.schema.graphql",
+        graphql_type = "Root"
+    )]
+    #[allow(dead_code)]
+    struct AllFilms {
+        all_films: Option<FilmsConnection>,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    #[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+    #[allow(dead_code)]
+    struct FilmsConnection {
+        total_count: Option<i32>,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    #[cynic(
+        schema_path = "../schemas/starwars.schema.graphql",
+        graphql_type = "Root",
+        operation_name = "FilmCount"
+    )]
+    #[allow(dead_code)]
+    struct RenamedOperation {
+        all_films: Option<FilmsConnection>,
+    }
+
+    #[test]
+    fn test_sha256_hash() {
+        assert_eq!(
+            sha256_hash("{ __typename }"),
+            "7f56e67dd21ab3f30d1ff8b7bed08893f0a0db86449836189b361dd1e56ddb4b"
+        );
+    }
+
+    #[test]
+    fn test_persisted_query_serialization() {
+        let operation = AllFilms::build(()).with_automatic_persisted_queries();
+        let hash = sha256_hash(&operation.query);
+
+        assert_eq!(
+            serde_json::to_value(&operation).unwrap(),
+            json!({
+                "variables": {},
+                "operationName": "AllFilms",
+                "extensions": {"persistedQuery": {"version": 1, "sha256Hash": hash}}
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(operation.body(true)).unwrap(),
+            json!({
+                "query": operation.query,
+                "variables": {},
+                "operationName": "AllFilms",
+                "extensions": {"persistedQuery": {"version": 1, "sha256Hash": hash}}
+            })
+        );
+    }
+
+    #[test]
+    fn test_serialization_without_persisted_queries() {
+        let operation = AllFilms::build(());
+
+        assert_eq!(
+            serde_json::to_value(&operation).unwrap(),
+            json!({"query": operation.query, "variables": {}, "operationName": "AllFilms"})
+        );
+        assert!(operation.query.starts_with("query AllFilms {"));
+    }
+
+    #[test]
+    fn test_unnamed_operation() {
+        let operation = Operation::query(AllFilms::fragment(crate::FragmentContext::empty()));
+
+        assert_eq!(operation.operation_name(), None);
+        assert!(operation.query.starts_with("query Query {"));
+        assert_eq!(
+            serde_json::to_value(&operation).unwrap(),
+            json!({"query": operation.query, "variables": {}})
+        );
+    }
+
+    #[test]
+    fn test_operation_name_attribute() {
+        let operation = RenamedOperation::build(());
+
+        assert_eq!(operation.operation_name(), Some("FilmCount"));
+        assert!(operation.query.starts_with("query FilmCount {"));
+    }
+
+    #[test]
+    fn test_compact_query() {
+        let operation = AllFilms::build(()).with_automatic_persisted_queries();
+
+        assert_eq!(
+            operation.compact_query(),
+            "query AllFilms{allFilms{totalCount}}"
+        );
+        assert_eq!(operation.pretty_query(), operation.query);
+
+        let operation = operation.with_compact_query();
+
+        assert_eq!(operation.query, "query AllFilms{allFilms{totalCount}}");
+        assert_eq!(
+            operation.persisted_query_hash(),
+            Some(sha256_hash("query AllFilms{allFilms{totalCount}}").as_str())
+        );
+    }
+
+    #[test]
+    fn test_should_retry_with_query() {
+        let not_found = json!({
+            "errors": [{"message": "PersistedQueryNotFound"}]
+        });
+        let not_found_code = json!({
+            "errors": [{
+                "message": "Not found",
+                "extensions": {"code": "PERSISTED_QUERY_NOT_FOUND"}
+            }]
+        });
+        let other_error = json!({
+            "errors": [{"message": "Something else"}]
+        });
+
+        let operation = AllFilms::build(()).with_automatic_persisted_queries();
+        assert!(operation.should_retry_with_query(&not_found));
+        assert!(operation.should_retry_with_query(&not_found_code));
+        assert!(!operation.should_retry_with_query(&other_error));
+
+        assert!(!AllFilms::build(()).should_retry_with_query(&not_found));
+    }
+
+    #[test]
+    fn test_persisted_query_manifest() {
+        let operation = AllFilms::build(());
+
+        let mut manifest = PersistedQueryManifest::new();
+        manifest.add(&operation).add(&operation);
+
+        assert_eq!(manifest.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&manifest).unwrap(),
+            json!({ sha256_hash(&operation.query): operation.query })
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! A buffer for deserialized values, used when we need to look at an object
+//! more than once while deserializing it.
+//!
+//! This is similar to the `Content` type that serde uses internally for
+//! `#[serde(flatten)]`.  Strings are borrowed from the input where possible,
+//! so buffering doesn't have to allocate for every string in an object.
+
+use std::{fmt, marker::PhantomData};
+
+use serde::de::{
+    self,
+    value::{MapDeserializer, SeqDeserializer},
+    Deserialize, Deserializer, IntoDeserializer, Visitor,
+};
+
+/// A buffered value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Content<'de> {
+    Bool(bool),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    Str(&'de str),
+    String(String),
+    Bytes(&'de [u8]),
+    ByteBuf(Vec<u8>),
+    Unit,
+    Some(Box<Content<'de>>),
+    Seq(Vec<Content<'de>>),
+    Map(Vec<(Content<'de>, Content<'de>)>),
+}
+
+impl<'de> Content<'de> {
+    /// Returns the contents of a string value.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Content::Str(s) => Some(s),
+            Content::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this is a map.
+    pub fn is_map(&self) -> bool {
+        matches!(self, Content::Map(_))
+    }
+
+    /// Looks up the value of `key`, if this is a map.
+    pub fn get(&self, key: &str) -> Option<&Content<'de>> {
+        match self {
+            Content::Map(entries) => entries
+                .iter()
+                .find(|(k, _)| k.as_str() == Some(key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn unexpected(&self) -> de::Unexpected<'_> {
+        match self {
+            Content::Bool(b) => de::Unexpected::Bool(*b),
+            Content::U64(n) => de::Unexpected::Unsigned(*n),
+            Content::I64(n) => de::Unexpected::Signed(*n),
+            Content::F64(n) => de::Unexpected::Float(*n),
+            Content::Str(s) => de::Unexpected::Str(s),
+            Content::String(s) => de::Unexpected::Str(s),
+            Content::Bytes(b) => de::Unexpected::Bytes(b),
+            Content::ByteBuf(b) => de::Unexpected::Bytes(b),
+            Content::Unit => de::Unexpected::Unit,
+            Content::Some(_) => de::Unexpected::Option,
+            Content::Seq(_) => de::Unexpected::Seq,
+            Content::Map(_) => de::Unexpected::Map,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Content<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ContentVisitor)
+    }
+}
+
+struct ContentVisitor;
+
+impl<'de> Visitor<'de> for ContentVisitor {
+    type Value = Content<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(Content::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(Content::I64(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(Content::U64(value))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(Content::F64(value))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(Content::String(value.to_string()))
+    }
+
+    fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E> {
+        Ok(Content::Str(value))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+        Ok(Content::String(value))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E> {
+        Ok(Content::ByteBuf(value.to_vec()))
+    }
+
+    fn visit_borrowed_bytes<E>(self, value: &'de [u8]) -> Result<Self::Value, E> {
+        Ok(Content::Bytes(value))
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Content::ByteBuf(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Content::Unit)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(Content::Unit)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Content::deserialize(deserializer).map(|content| Content::Some(Box::new(content)))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Content::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Content::Seq(items))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(Content::Map(entries))
+    }
+}
+
+/// A `Deserializer` that deserializes from a borrowed `Content`.
+pub struct ContentRefDeserializer<'a, 'de, E> {
+    content: &'a Content<'de>,
+    err: PhantomData<E>,
+}
+
+impl<'a, 'de, E> ContentRefDeserializer<'a, 'de, E> {
+    pub fn new(content: &'a Content<'de>) -> Self {
+        ContentRefDeserializer {
+            content,
+            err: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'de, E> Clone for ContentRefDeserializer<'a, 'de, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, 'de, E> Copy for ContentRefDeserializer<'a, 'de, E> {}
+
+impl<'a, 'de, E: de::Error> IntoDeserializer<'de, E> for ContentRefDeserializer<'a, 'de, E> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'a, 'de, E: de::Error> Deserializer<'de> for ContentRefDeserializer<'a, 'de, E> {
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Bool(b) => visitor.visit_bool(*b),
+            Content::U64(n) => visitor.visit_u64(*n),
+            Content::I64(n) => visitor.visit_i64(*n),
+            Content::F64(n) => visitor.visit_f64(*n),
+            Content::Str(s) => visitor.visit_borrowed_str(s),
+            Content::String(s) => visitor.visit_str(s),
+            Content::Bytes(b) => visitor.visit_borrowed_bytes(b),
+            Content::ByteBuf(b) => visitor.visit_bytes(b),
+            Content::Unit => visitor.visit_unit(),
+            Content::Some(content) => visitor.visit_some(ContentRefDeserializer::new(content)),
+            Content::Seq(items) => {
+                let mut seq = SeqDeserializer::new(items.iter().map(ContentRefDeserializer::new));
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Content::Map(entries) => {
+                let mut map = MapDeserializer::new(entries.iter().map(|(k, v)| {
+                    (
+                        ContentRefDeserializer::new(k),
+                        ContentRefDeserializer::new(v),
+                    )
+                }));
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Unit => visitor.visit_none(),
+            Content::Some(content) => visitor.visit_some(ContentRefDeserializer::new(content)),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content.as_str() {
+            Some(variant) => visitor.visit_enum(variant.into_deserializer()),
+            None => Err(de::Error::invalid_type(
+                self.content.unexpected(),
+                &"a string",
+            )),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_content_round_trip() {
+        let json = r#"{"a": [1, -2, 3.5, null], "b": {"c": "d\n"}, "e": true}"#;
+        let content = serde_json::from_str::<Content>(json).unwrap();
+
+        let value = serde_json::Value::deserialize(
+            ContentRefDeserializer::<serde_json::Error>::new(&content),
+        )
+        .unwrap();
+
+        assert_eq!(
+            value,
+            json!({"a": [1, -2, 3.5, null], "b": {"c": "d\n"}, "e": true})
+        );
+    }
+
+    #[test]
+    fn test_content_borrows_strings() {
+        let content = serde_json::from_str::<Content>(r#"{"a": "b"}"#).unwrap();
+
+        assert_eq!(content.get("a"), Some(&Content::Str("b")));
+    }
+}
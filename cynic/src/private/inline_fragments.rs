@@ -0,0 +1,128 @@
+//! Deserialization for `InlineFragments`, which need the `__typename` of an
+//! object before they know how to deserialize the rest of it.
+
+use std::{fmt, marker::PhantomData};
+
+use serde::de::{self, value::MapAccessDeserializer, DeserializeSeed, IntoDeserializer};
+
+use super::content::{Content, ContentRefDeserializer};
+
+const TYPENAME: &str = "__typename";
+
+/// Implemented by the `InlineFragments` derive to deserialize the variant
+/// for a given `__typename`.
+pub trait DeserializeInlineFragment<'de>: Sized {
+    fn deserialize_variant<D>(typename: &str, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>;
+}
+
+/// Deserializes an `InlineFragments` type.
+///
+/// If `__typename` is the first field of the object (as it will be when the
+/// server returns fields in the order they were queried) the rest of the
+/// object is deserialized in a single pass.  Otherwise the object is
+/// buffered so we can find the `__typename`.
+pub fn deserialize_inline_fragments<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: de::Deserializer<'de>,
+    T: DeserializeInlineFragment<'de>,
+{
+    deserializer.deserialize_map(InlineFragmentsVisitor(PhantomData))
+}
+
+struct InlineFragmentsVisitor<T>(PhantomData<fn() -> T>);
+
+impl<'de, T> de::Visitor<'de> for InlineFragmentsVisitor<T>
+where
+    T: DeserializeInlineFragment<'de>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an object with a __typename")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<T, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let first_key = match map.next_key::<Content<'de>>()? {
+            Some(key) => key,
+            None => return Err(de::Error::missing_field(TYPENAME)),
+        };
+
+        if first_key.as_str() == Some(TYPENAME) {
+            let typename = map.next_value::<Content<'de>>()?;
+            let typename_str = typename
+                .as_str()
+                .ok_or_else(|| de::Error::invalid_type(de::Unexpected::Map, &"a string"))?;
+
+            return T::deserialize_variant(
+                typename_str,
+                MapAccessDeserializer::new(TypenameFirst {
+                    typename: Some(&typename),
+                    pending_typename: None,
+                    map,
+                }),
+            );
+        }
+
+        let mut entries = vec![(first_key, map.next_value()?)];
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        let content = Content::Map(entries);
+
+        let typename = content
+            .get(TYPENAME)
+            .and_then(Content::as_str)
+            .ok_or_else(|| de::Error::missing_field(TYPENAME))?;
+
+        T::deserialize_variant(typename, ContentRefDeserializer::new(&content))
+    }
+}
+
+/// A `MapAccess` that replays a `__typename` that has already been read
+/// before the rest of a map.
+struct TypenameFirst<'a, 'de, A> {
+    typename: Option<&'a Content<'de>>,
+    pending_typename: Option<&'a Content<'de>>,
+    map: A,
+}
+
+impl<'a, 'de, A> de::MapAccess<'de> for TypenameFirst<'a, 'de, A>
+where
+    A: de::MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.typename.take() {
+            Some(typename) => {
+                self.pending_typename = Some(typename);
+                seed.deserialize(TYPENAME.into_deserializer()).map(Some)
+            }
+            None => self.map.next_key_seed(seed),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.pending_typename.take() {
+            Some(typename) => seed.deserialize(ContentRefDeserializer::new(typename)),
+            None => self.map.next_value_seed(seed),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.map
+            .size_hint()
+            .map(|size| size + self.typename.is_some() as usize)
+    }
+}
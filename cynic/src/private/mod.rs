@@ -0,0 +1,73 @@
+//! Helpers for the `serde::Deserialize` impls that the derives generate.
+//!
+//! These aren't part of the public API of cynic, and may change at any time.
+
+mod content;
+mod inline_fragments;
+
+use std::marker::PhantomData;
+
+use serde::{de, Deserialize, Deserializer};
+
+use crate::Scalar;
+
+pub use content::{Content, ContentRefDeserializer};
+pub use inline_fragments::{deserialize_inline_fragments, DeserializeInlineFragment};
+
+/// Deserializes a `Scalar` through `Scalar::Deserialize` &
+/// `Scalar::from_deserialize`, so that the generated `Deserialize` impls
+/// decode scalars exactly the same way as a `SelectionSet` does.
+pub struct DeserializeScalar<S, TypeLock> {
+    value: S,
+    phantom: PhantomData<fn() -> TypeLock>,
+}
+
+impl<'de, S, TypeLock> Deserialize<'de> for DeserializeScalar<S, TypeLock>
+where
+    S: Scalar<TypeLock>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = S::Deserialize::deserialize(deserializer)?;
+
+        S::from_deserialize(value)
+            .map(|value| DeserializeScalar {
+                value,
+                phantom: PhantomData,
+            })
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Converts a type containing `DeserializeScalar`s into the equivalent type
+/// containing the scalars themselves.  e.g. `Option<DeserializeScalar<T, _>>`
+/// into `Option<T>`
+pub trait IntoScalars<T> {
+    fn into_scalars(self) -> T;
+}
+
+impl<S, TypeLock> IntoScalars<S> for DeserializeScalar<S, TypeLock> {
+    fn into_scalars(self) -> S {
+        self.value
+    }
+}
+
+impl<T, U: IntoScalars<T>> IntoScalars<Option<T>> for Option<U> {
+    fn into_scalars(self) -> Option<T> {
+        self.map(IntoScalars::into_scalars)
+    }
+}
+
+impl<T, U: IntoScalars<T>> IntoScalars<Vec<T>> for Vec<U> {
+    fn into_scalars(self) -> Vec<T> {
+        self.into_iter().map(IntoScalars::into_scalars).collect()
+    }
+}
+
+impl<T, U: IntoScalars<T>> IntoScalars<Box<T>> for Box<U> {
+    fn into_scalars(self) -> Box<T> {
+        Box::new((*self).into_scalars())
+    }
+}
@@ -0,0 +1,280 @@
+//! Helpers for Relay style cursor connections.
+//!
+//! Many GraphQL APIs paginate lists with
+//! [connections](https://relay.dev/graphql/connections.htm), which look like
+//! `edges { node } pageInfo { endCursor hasNextPage }`.  `Connection` is a
+//! `QueryFragment` that selects one of these for any `Node` fragment, so there's
+//! no need to write a connection & edge struct for each list in a schema.
+//!
+//! `Client::paginate` & `BlockingClient::paginate` can then fetch every page of
+//! a connection, re-running a query with the `after` argument set to the
+//! `endCursor` of the previous page until `hasNextPage` is false.
+//!
+//! ```rust
+//! # mod schema {
+//! #   cynic::use_schema!("../schemas/starwars.schema.graphql");
+//! # }
+//! use cynic::relay::{Connection, CursorArguments};
+//!
+//! #[derive(cynic::FragmentArguments, Clone)]
+//! struct FilmsArguments {
+//!     after: Option<String>,
+//! }
+//!
+//! impl CursorArguments for FilmsArguments {
+//!     fn set_after(&mut self, cursor: Option<String>) {
+//!         self.after = cursor;
+//!     }
+//! }
+//!
+//! #[derive(cynic::QueryFragment)]
+//! #[cynic(
+//!     schema_path = "../schemas/starwars.schema.graphql",
+//!     graphql_type = "Root",
+//!     argument_struct = "FilmsArguments"
+//! )]
+//! struct AllFilmsQuery {
+//!     #[arguments(first = 10, after = args.after.clone())]
+//!     all_films: Option<Connection<Film, schema::FilmsConnection>>,
+//! }
+//!
+//! #[derive(cynic::QueryFragment)]
+//! #[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+//! struct Film {
+//!     title: Option<String>,
+//! }
+//! ```
+//!
+//! The pages can then be fetched with a `Client`:
+//!
+//! ```rust,no_run
+//! # mod schema {
+//! #   cynic::use_schema!("../schemas/starwars.schema.graphql");
+//! # }
+//! # use cynic::relay::{Connection, CursorArguments};
+//! # #[derive(cynic::FragmentArguments, Clone)]
+//! # struct FilmsArguments {
+//! #     after: Option<String>,
+//! # }
+//! # impl CursorArguments for FilmsArguments {
+//! #     fn set_after(&mut self, cursor: Option<String>) {
+//! #         self.after = cursor;
+//! #     }
+//! # }
+//! # #[derive(cynic::QueryFragment)]
+//! # #[cynic(
+//! #     schema_path = "../schemas/starwars.schema.graphql",
+//! #     graphql_type = "Root",
+//! #     argument_struct = "FilmsArguments"
+//! # )]
+//! # struct AllFilmsQuery {
+//! #     #[arguments(first = 10, after = args.after.clone())]
+//! #     all_films: Option<Connection<Film, schema::FilmsConnection>>,
+//! # }
+//! # #[derive(cynic::QueryFragment)]
+//! # #[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+//! # struct Film {
+//! #     title: Option<String>,
+//! # }
+//! # fn client() -> cynic::BlockingClient<cynic::transport::MockTransport> { unimplemented!() }
+//! let client = client();
+//!
+//! let pages = client.paginate::<AllFilmsQuery, _>(
+//!     FilmsArguments { after: None },
+//!     |query| query.all_films.as_ref().map(|films| &films.page_info),
+//! );
+//! for page in pages {
+//!     let films = page.unwrap().data.unwrap().all_films.unwrap();
+//!     for film in films.nodes() {
+//!         println!("{:?}", film.title);
+//!     }
+//! }
+//! ```
+
+use serde::Deserialize;
+use std::marker::PhantomData;
+
+use crate::{
+    decode_error::graphql_type_name,
+    selection_set::{boolean, field, map, map2, option, string, vec, SelectionSet},
+    utils::FlattenInto,
+    FragmentArguments, FragmentContext, GraphQlResponse, QueryFragment,
+};
+
+/// A Relay connection to a list of `Node`s.
+///
+/// `TypeLock` should be the type of the connection in a `schema` module, e.g.
+/// `schema::PullRequestConnection`, and `Node` should be a `QueryFragment` for
+/// the type of the nodes of that connection.  Any null edges in the response
+/// are skipped.
+pub struct Connection<Node, TypeLock> {
+    pub edges: Vec<Edge<Node>>,
+    pub page_info: PageInfo,
+    phantom: PhantomData<fn() -> TypeLock>,
+}
+
+/// An edge of a Relay connection.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Edge<Node> {
+    pub node: Option<Node>,
+}
+
+/// The `pageInfo` of a Relay connection.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
+impl<Node, TypeLock> Connection<Node, TypeLock> {
+    pub fn new(edges: Vec<Edge<Node>>, page_info: PageInfo) -> Self {
+        Connection {
+            edges,
+            page_info,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Iterates over the nodes of this connection, skipping any that are null.
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.edges.iter().filter_map(|edge| edge.node.as_ref())
+    }
+
+    /// Converts this connection into its nodes, skipping any that are null.
+    pub fn into_nodes(self) -> Vec<Node> {
+        self.edges
+            .into_iter()
+            .filter_map(|edge| edge.node)
+            .collect()
+    }
+}
+
+impl PageInfo {
+    /// A `SelectionSet` for the `hasNextPage` & `endCursor` fields of a
+    /// `PageInfo` type.
+    pub fn select<TypeLock>() -> SelectionSet<'static, PageInfo, TypeLock> {
+        map2(
+            |has_next_page, end_cursor| PageInfo {
+                has_next_page,
+                end_cursor,
+            },
+            field::<_, TypeLock, _>("hasNextPage", vec![], boolean()),
+            field::<_, TypeLock, _>("endCursor", vec![], option(string())),
+        )
+    }
+}
+
+/// Implemented by `use_schema!` for each type in a schema that looks like a
+/// Relay connection.
+pub trait ConnectionType {
+    /// The type of the `node` field of this connections edges.
+    type Node;
+}
+
+impl<Node, TypeLock> QueryFragment for Connection<Node, TypeLock>
+where
+    Node: QueryFragment<SelectionSet = SelectionSet<'static, Node, TypeLock::Node>>,
+    Node: Send + Sync + 'static,
+    TypeLock: ConnectionType + 'static,
+{
+    type SelectionSet = SelectionSet<'static, Self, TypeLock>;
+    type Arguments = Node::Arguments;
+
+    // `edges { node { ... } }` is always deeper than `pageInfo { ... }`
+    const DEPTH: usize = 2 + Node::DEPTH;
+    const NON_RECURSIVE_DEPTH: usize = 2 + Node::NON_RECURSIVE_DEPTH;
+
+    fn fragment(context: FragmentContext<Self::Arguments>) -> Self::SelectionSet {
+        let edge = map(
+            |node| Edge { node },
+            field::<_, (), TypeLock::Node>("node", vec![], option(Node::fragment(context))),
+        );
+
+        map2(
+            |edges: Option<Vec<Option<Edge<Node>>>>, page_info| {
+                Connection::new(edges.flatten_into(), page_info)
+            },
+            field::<_, TypeLock, _>("edges", vec![], option(vec(option(edge)))),
+            field::<_, TypeLock, _>("pageInfo", vec![], PageInfo::select::<()>()),
+        )
+    }
+
+    fn graphql_type() -> String {
+        graphql_type_name::<TypeLock>()
+            .unwrap_or("Connection")
+            .to_string()
+    }
+}
+
+impl<'de, Node, TypeLock> Deserialize<'de> for Connection<Node, TypeLock>
+where
+    Node: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        #[serde(bound(deserialize = "Node: Deserialize<'de>"))]
+        struct Response<Node> {
+            edges: Option<Vec<Option<Edge<Node>>>>,
+            page_info: PageInfo,
+        }
+
+        let response = Response::<Node>::deserialize(deserializer)?;
+
+        Ok(Connection::new(
+            response.edges.flatten_into(),
+            response.page_info,
+        ))
+    }
+}
+
+impl<Node: std::fmt::Debug, TypeLock> std::fmt::Debug for Connection<Node, TypeLock> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Connection")
+            .field("edges", &self.edges)
+            .field("page_info", &self.page_info)
+            .finish()
+    }
+}
+
+impl<Node: Clone, TypeLock> Clone for Connection<Node, TypeLock> {
+    fn clone(&self) -> Self {
+        Connection::new(self.edges.clone(), self.page_info.clone())
+    }
+}
+
+impl<Node: PartialEq, TypeLock> PartialEq for Connection<Node, TypeLock> {
+    fn eq(&self, other: &Self) -> bool {
+        self.edges == other.edges && self.page_info == other.page_info
+    }
+}
+
+/// `FragmentArguments` with an `after` cursor, which `Client::paginate` &
+/// `BlockingClient::paginate` update to fetch each page of a connection.
+pub trait CursorArguments: FragmentArguments + Clone {
+    /// Sets the cursor that the next page should start after.
+    fn set_after(&mut self, cursor: Option<String>);
+}
+
+/// Works out the arguments for the page after `response`, if there is one.
+pub(crate) fn next_page_arguments<Arguments, ResponseData, ErrorExtensions, F>(
+    mut arguments: Arguments,
+    response: &GraphQlResponse<ResponseData, ErrorExtensions>,
+    page_info: &F,
+) -> Option<Arguments>
+where
+    Arguments: CursorArguments,
+    F: Fn(&ResponseData) -> Option<&PageInfo>,
+{
+    let page_info = response.data.as_ref().and_then(page_info)?;
+    if !page_info.has_next_page {
+        return None;
+    }
+
+    arguments.set_after(Some(page_info.end_cursor.clone()?));
+    Some(arguments)
+}
@@ -3,7 +3,8 @@
 /// Either it fully succeeds, or we have some errors.  If we have some errors
 /// then some fields might unexpectedly be null, so deserialization _might_ have
 /// failed.  We represent that with a PossiblyParsedData<T>.
-pub type GraphQlResult<T> = Result<T, (PossiblyParsedData<T>, Vec<GraphQlError>)>;
+pub type GraphQlResult<T, ErrorExtensions = serde_json::Value> =
+    Result<T, (PossiblyParsedData<T>, Vec<GraphQlError<ErrorExtensions>>)>;
 
 #[deprecated(
     since = "0.13.0",
@@ -12,10 +13,14 @@ pub type GraphQlResult<T> = Result<T, (PossiblyParsedData<T>, Vec<GraphQlError>)
 #[allow(clippy::upper_case_acronyms)]
 pub type GraphQLResult<T> = GraphQlResult<T>;
 
+/// A response from a GraphQL server.
+///
+/// `ErrorExtensions` is the type that the `extensions` of any errors will be
+/// deserialized into.
 #[derive(Debug, serde::Deserialize)]
-pub struct GraphQlResponse<T> {
+pub struct GraphQlResponse<T, ErrorExtensions = serde_json::Value> {
     pub data: Option<T>,
-    pub errors: Option<Vec<GraphQlError>>,
+    pub errors: Option<Vec<GraphQlError<ErrorExtensions>>>,
 }
 
 #[deprecated(
@@ -25,37 +30,91 @@ pub struct GraphQlResponse<T> {
 #[allow(clippy::upper_case_acronyms)]
 pub type GraphQLResponse<T> = GraphQlResponse<T>;
 
-/*
-impl<T> GraphQLResponse<T> {
-    fn into_result(self) -> GraphQLResult<T> {
-        // TODO: I need access to the query/selection set in order to decode this.
-        if let Some(errors) = self.errors {
-            if !errors.is_empty() {
-                // TODO: Return an error.
-            }
-        }
+impl<T, ErrorExtensions> GraphQlResponse<T, ErrorExtensions> {
+    /// Converts this response into a `GraphQlResult`.
+    ///
+    /// This is `Ok` if the response had data & no errors, and `Err` otherwise.
+    /// Note that a response without data is always an `Err`, even if the
+    /// server didn't send any errors.
+    pub fn into_result(self) -> GraphQlResult<T, ErrorExtensions> {
+        let errors = self.errors.unwrap_or_default();
 
-        match self {
-            GraphQLResponse::Ok(t) => Ok(t),
-            GraphQLResponse::Err(poss_data, errs) => Err((poss_data, errs)),
+        match self.data {
+            Some(data) if errors.is_empty() => Ok(data),
+            Some(data) => Err((PossiblyParsedData::ParsedData(data), errors)),
+            None => Err((
+                PossiblyParsedData::UnparsedData(serde_json::Value::Null),
+                errors,
+            )),
         }
     }
-}*/
+}
+
+impl<T> GraphQlResponse<T> {
+    /// Deserializes the `extensions` of any errors in this response into
+    /// `ErrorExtensions`.
+    pub(crate) fn deserialize_error_extensions<ErrorExtensions>(
+        self,
+    ) -> Result<GraphQlResponse<T, ErrorExtensions>, json_decode::DecodeError>
+    where
+        ErrorExtensions: serde::de::DeserializeOwned,
+    {
+        let errors =
+            self.errors
+                .map(|errors| {
+                    errors
+                        .into_iter()
+                        .map(|error| {
+                            let extensions = error
+                                .extensions
+                                .map(serde_json::from_value)
+                                .transpose()
+                                .map_err(|e| json_decode::DecodeError::SerdeError(e.to_string()))?;
+
+                            Ok(GraphQlError::new(
+                                error.message,
+                                error.locations,
+                                error.path,
+                                extensions,
+                            ))
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?;
+
+        Ok(GraphQlResponse {
+            data: self.data,
+            errors,
+        })
+    }
+}
 
 /// A model describing an error which has taken place during execution.
-#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, thiserror::Error)]
-#[error("{message}")]
-pub struct GraphQlError {
+///
+/// Servers can provide more details about an error in its `extensions`.  By
+/// default these are left as a `serde_json::Value`, but any type that
+/// implements `serde::Deserialize` can be used instead by providing it as the
+/// `Extensions` parameter.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+pub struct GraphQlError<Extensions = serde_json::Value> {
     /// A description of the error which has taken place.
     pub message: String,
     /// Optional description of the locations where the errors have taken place.
     pub locations: Option<Vec<GraphQlErrorLocation>>,
     /// Optional path to the response field which experienced the associated error.
     pub path: Option<Vec<GraphQlErrorPathSegment>>,
-    /// Optional arbitrary JSON data describing the error in more detail.
-    pub extensions: Option<serde_json::Value>,
+    /// Optional data describing the error in more detail.
+    pub extensions: Option<Extensions>,
 }
 
+impl<Extensions> std::fmt::Display for GraphQlError<Extensions> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl<Extensions: std::fmt::Debug> std::error::Error for GraphQlError<Extensions> {}
+
 #[deprecated(
     since = "0.13.0",
     note = "GraphQlError has been deprecated in favour of GraphQlError"
@@ -63,13 +122,13 @@ pub struct GraphQlError {
 #[allow(clippy::upper_case_acronyms)]
 pub type GraphQLError = GraphQlError;
 
-impl GraphQlError {
+impl<Extensions> GraphQlError<Extensions> {
     /// Construct a new instance.
     pub fn new(
         message: String,
         locations: Option<Vec<GraphQlErrorLocation>>,
         path: Option<Vec<GraphQlErrorPathSegment>>,
-        extensions: Option<serde_json::Value>,
+        extensions: Option<Extensions>,
     ) -> Self {
         GraphQlError {
             message,
@@ -117,9 +176,149 @@ pub type GraphQLErrorPathSegment = GraphQlErrorPathSegment;
 /// GraphQL allows servers to return partial data in this case, but if there's
 /// missing fields that aren't represented by an Option we won't have been
 /// able to decode that data.
+#[derive(Clone, Debug, PartialEq)]
 pub enum PossiblyParsedData<T> {
     ParsedData(T),
-    // TODO: Could pass serde_json::Value from here, can't be bothered right
-    // now though....
-    UnparsedData,
+    /// The raw data from the response, which we couldn't decode.
+    UnparsedData(serde_json::Value),
+}
+
+impl<T> PossiblyParsedData<T> {
+    /// Returns the parsed data, if we were able to parse it.
+    pub fn parsed(self) -> Option<T> {
+        match self {
+            PossiblyParsedData::ParsedData(data) => Some(data),
+            PossiblyParsedData::UnparsedData(_) => None,
+        }
+    }
+}
+
+/// Sets the values that `errors` point at to null, to allow the rest of the
+/// data to be decoded.
+///
+/// Each error path is first truncated by the corresponding entry in `levels`,
+/// mirroring the way GraphQL servers null out the parent of a non-nullable
+/// field that errored.  Paths are never truncated past the root field.
+pub(crate) fn null_error_paths<ErrorExtensions>(
+    data: &serde_json::Value,
+    errors: &[GraphQlError<ErrorExtensions>],
+    levels: &[usize],
+) -> serde_json::Value {
+    let mut data = data.clone();
+
+    for (error, levels) in errors.iter().zip(levels) {
+        let path = match error.path.as_deref() {
+            Some(path) if !path.is_empty() => path,
+            _ => continue,
+        };
+        let len = path.len().saturating_sub(*levels).max(1);
+        if let Some(value) = value_at_path(&mut data, &path[0..len]) {
+            *value = serde_json::Value::Null;
+        }
+    }
+
+    data
+}
+
+/// Finds the value at `path` in `data`, inserting a null if the last field
+/// doesn't exist yet.
+fn value_at_path<'a>(
+    data: &'a mut serde_json::Value,
+    path: &[GraphQlErrorPathSegment],
+) -> Option<&'a mut serde_json::Value> {
+    let (last, parents) = path.split_last()?;
+
+    let mut current = data;
+    for segment in parents {
+        current = match segment {
+            GraphQlErrorPathSegment::Field(name) => current.get_mut(name.as_str())?,
+            GraphQlErrorPathSegment::Index(index) => current.get_mut(*index as usize)?,
+        };
+    }
+
+    match last {
+        GraphQlErrorPathSegment::Field(name) => Some(
+            current
+                .as_object_mut()?
+                .entry(name.as_str())
+                .or_insert(serde_json::Value::Null),
+        ),
+        GraphQlErrorPathSegment::Index(index) => current.get_mut(*index as usize),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn error_at(path: serde_json::Value) -> GraphQlError {
+        serde_json::from_value(json!({"message": "Oops", "path": path})).unwrap()
+    }
+
+    #[test]
+    fn test_null_error_paths() {
+        let data = json!({
+            "film": {
+                "director": {"name": null},
+                "characters": [{"name": "Luke"}, {"name": null}]
+            }
+        });
+        let errors = vec![
+            error_at(json!(["film", "director", "name"])),
+            error_at(json!(["film", "characters", 1, "name"])),
+        ];
+
+        assert_eq!(null_error_paths(&data, &errors, &[0, 0]), data);
+        assert_eq!(
+            null_error_paths(&data, &errors, &[1, 1]),
+            json!({
+                "film": {
+                    "director": null,
+                    "characters": [{"name": "Luke"}, null]
+                }
+            })
+        );
+        assert_eq!(
+            null_error_paths(&data, &errors, &[0, 2]),
+            json!({"film": {"director": {"name": null}, "characters": null}})
+        );
+        assert_eq!(
+            null_error_paths(&data, &errors, &[2, 5]),
+            json!({ "film": null })
+        );
+    }
+
+    #[test]
+    fn test_into_result() {
+        let ok: GraphQlResponse<i32> = GraphQlResponse {
+            data: Some(1),
+            errors: Some(vec![]),
+        };
+        assert_eq!(ok.into_result(), Ok(1));
+
+        let partial: GraphQlResponse<i32> = GraphQlResponse {
+            data: Some(1),
+            errors: Some(vec![error_at(json!(["one"]))]),
+        };
+        assert_eq!(
+            partial.into_result(),
+            Err((
+                PossiblyParsedData::ParsedData(1),
+                vec![error_at(json!(["one"]))]
+            ))
+        );
+
+        let no_data = GraphQlResponse::<i32> {
+            data: None,
+            errors: None,
+        };
+        assert_eq!(
+            no_data.into_result(),
+            Err((
+                PossiblyParsedData::UnparsedData(serde_json::Value::Null),
+                vec![]
+            ))
+        );
+    }
 }
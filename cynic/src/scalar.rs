@@ -1,5 +1,7 @@
 use json_decode::{BoxDecoder, DecodeError, Decoder};
-use std::marker::PhantomData;
+use std::{convert::TryFrom, marker::PhantomData};
+
+use crate::inputs::{List, NamedType, Nullable};
 
 pub trait Scalar<TypeLock>: Sized + serde::Serialize {
     type Deserialize: serde::de::DeserializeOwned;
@@ -55,6 +57,108 @@ impl_scalar!(String, String);
 
 impl_scalar!(serde_json::Value, serde_json::Value);
 
+/// Implements `Scalar` & `InputType` for an integer type, for both the `Int`
+/// scalar & any scalar that implements `IsIntegerScalar`.
+///
+/// Integers are decoded via a `serde_json::Number` so they can be range
+/// checked against the target type.
+macro_rules! impl_integer_scalar {
+    ($type:ty) => {
+        impl Scalar<i32> for $type {
+            type Deserialize = serde_json::Number;
+
+            fn from_deserialize(x: serde_json::Number) -> Result<Self, DecodeError> {
+                integer_from_number(x, stringify!($type))
+            }
+        }
+
+        impl<TypeLock: crate::IsIntegerScalar> Scalar<TypeLock> for $type {
+            type Deserialize = serde_json::Number;
+
+            fn from_deserialize(x: serde_json::Number) -> Result<Self, DecodeError> {
+                integer_from_number(x, stringify!($type))
+            }
+        }
+
+        crate::impl_input_type!($type, i32);
+        impl_integer_input_type!($type);
+    };
+}
+
+/// The generic equivalent of `impl_input_type!`, for integer scalars.
+macro_rules! impl_integer_input_type {
+    ($type:ty) => {
+        impl<TypeLock: crate::IsIntegerScalar> crate::InputType<TypeLock, NamedType> for $type {}
+
+        impl<TypeLock: crate::IsIntegerScalar> crate::InputType<TypeLock, Nullable<NamedType>>
+            for $type
+        {
+        }
+
+        impl<TypeLock: crate::IsIntegerScalar> crate::InputType<TypeLock, Nullable<NamedType>>
+            for Option<$type>
+        {
+        }
+
+        impl<'a, TypeLock: crate::IsIntegerScalar> crate::InputType<TypeLock, Nullable<NamedType>>
+            for Option<&'a $type>
+        {
+        }
+
+        impl<TypeLock: crate::IsIntegerScalar> crate::InputType<TypeLock, Nullable<List<NamedType>>>
+            for Option<Vec<$type>>
+        {
+        }
+
+        impl<TypeLock: crate::IsIntegerScalar> crate::InputType<TypeLock, List<NamedType>>
+            for Vec<$type>
+        {
+        }
+
+        impl<TypeLock: crate::IsIntegerScalar>
+            crate::InputType<TypeLock, Nullable<List<Nullable<NamedType>>>>
+            for Option<Vec<Option<$type>>>
+        {
+        }
+    };
+}
+
+impl_integer_scalar!(u8);
+impl_integer_scalar!(i16);
+impl_integer_scalar!(u32);
+impl_integer_scalar!(i64);
+impl_integer_scalar!(u64);
+impl_integer_scalar!(usize);
+
+// i32 is already the `Int` scalar, so only needs the integer scalar impls.
+impl<TypeLock: crate::IsIntegerScalar> Scalar<TypeLock> for i32 {
+    type Deserialize = serde_json::Number;
+
+    fn from_deserialize(x: serde_json::Number) -> Result<Self, DecodeError> {
+        integer_from_number(x, "i32")
+    }
+}
+
+impl_integer_input_type!(i32);
+
+fn integer_from_number<T>(
+    number: serde_json::Number,
+    type_name: &'static str,
+) -> Result<T, DecodeError>
+where
+    T: TryFrom<i64> + TryFrom<u64>,
+{
+    let result = if let Some(x) = number.as_i64() {
+        T::try_from(x).ok()
+    } else if let Some(x) = number.as_u64() {
+        T::try_from(x).ok()
+    } else {
+        return Err(DecodeError::InvalidInteger(number.to_string()));
+    };
+
+    result.ok_or_else(|| DecodeError::IntegerOverflow(number.to_string(), type_name))
+}
+
 struct ScalarDecoder<S, T> {
     phantom: PhantomData<(S, T)>,
 }
@@ -0,0 +1,122 @@
+//! The decoders that `SelectionSet`s use to decode GraphQL responses.
+//!
+//! The leaf decoders come from `json_decode`, but the decoders for fields,
+//! lists & so on live here so that they can record where in the response
+//! an error occurred.
+
+use json_decode::DecodeError;
+use serde_json::Value;
+
+use crate::{decode_error::json_kind, ResponseDecodeError};
+
+/// Decodes some JSON into `DecodesTo`.
+pub(crate) trait Decoder<DecodesTo>: Send + Sync {
+    fn decode(&self, value: &Value) -> Result<DecodesTo, ResponseDecodeError>;
+}
+
+pub(crate) type BoxDecoder<'a, DecodesTo> = Box<dyn Decoder<DecodesTo> + 'a>;
+
+impl<DecodesTo, F> Decoder<DecodesTo> for F
+where
+    F: Fn(&Value) -> Result<DecodesTo, ResponseDecodeError> + Send + Sync,
+{
+    fn decode(&self, value: &Value) -> Result<DecodesTo, ResponseDecodeError> {
+        self(value)
+    }
+}
+
+/// Converts a `json_decode` decoder into one of ours.
+pub(crate) fn from_json_decode<'a, DecodesTo>(
+    decoder: json_decode::BoxDecoder<'a, DecodesTo>,
+) -> BoxDecoder<'a, DecodesTo>
+where
+    DecodesTo: 'a,
+{
+    Box::new(move |value: &Value| Ok(decoder.decode(value)?))
+}
+
+/// Decodes the field `response_key` of an object with `decoder`.
+///
+/// Any errors decoding the field have it added to their path, and if the
+/// error came from this field itself `rust_type` & `graphql_type` are
+/// recorded as the types being decoded.
+pub(crate) fn field<'a, DecodesTo>(
+    response_key: &str,
+    rust_type: &'static str,
+    graphql_type: Option<&'static str>,
+    decoder: BoxDecoder<'a, DecodesTo>,
+) -> BoxDecoder<'a, DecodesTo>
+where
+    DecodesTo: 'a,
+{
+    let response_key = response_key.to_string();
+
+    Box::new(move |value: &Value| match value {
+        Value::Object(map) => match map.get(&response_key) {
+            Some(inner_value) => decoder.decode(inner_value),
+            None => Err(DecodeError::MissingField(response_key.clone(), "Object".into()).into()),
+        }
+        .map_err(|error| error.within_field(&response_key, rust_type, graphql_type)),
+        other => Err(DecodeError::IncorrectType("Object".into(), json_kind(other).into()).into()),
+    })
+}
+
+/// Decodes a list, using `decoder` for each of its items.
+pub(crate) fn list<'a, DecodesTo>(
+    decoder: BoxDecoder<'a, DecodesTo>,
+) -> BoxDecoder<'a, Vec<DecodesTo>>
+where
+    DecodesTo: 'a,
+{
+    Box::new(move |value: &Value| match value {
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                decoder
+                    .decode(item)
+                    .map_err(|error| error.within_index(index))
+            })
+            .collect(),
+        other => Err(DecodeError::IncorrectType("Array".into(), json_kind(other).into()).into()),
+    })
+}
+
+/// Decodes a nullable value, using `decoder` if it's not null.
+pub(crate) fn option<'a, DecodesTo>(
+    decoder: BoxDecoder<'a, DecodesTo>,
+) -> BoxDecoder<'a, Option<DecodesTo>>
+where
+    DecodesTo: 'a,
+{
+    Box::new(move |value: &Value| match value {
+        Value::Null => Ok(None),
+        other => decoder.decode(other).map(Some),
+    })
+}
+
+pub(crate) fn map<'a, F, T, NewDecodesTo>(
+    func: F,
+    decoder: BoxDecoder<'a, T>,
+) -> BoxDecoder<'a, NewDecodesTo>
+where
+    F: Fn(T) -> NewDecodesTo + 'a + Send + Sync,
+    T: 'a,
+    NewDecodesTo: 'a,
+{
+    Box::new(move |value: &Value| decoder.decode(value).map(&func))
+}
+
+/// Decodes with `decoder`, then uses the result to pick another decoder to
+/// decode the same value with.
+pub(crate) fn and_then<'a, F, T, NewDecodesTo>(
+    func: F,
+    decoder: BoxDecoder<'a, T>,
+) -> BoxDecoder<'a, NewDecodesTo>
+where
+    F: Fn(T) -> BoxDecoder<'a, NewDecodesTo> + 'a + Send + Sync,
+    T: 'a,
+    NewDecodesTo: 'a,
+{
+    Box::new(move |value: &Value| func(decoder.decode(value)?).decode(value))
+}
@@ -1,4 +1,9 @@
-use crate::Argument;
+use std::collections::HashMap;
+
+use super::printer::{
+    self, Definition, Document, FragmentDefinition, OperationDefinition, Selection, Value,
+};
+use crate::{Argument, ArgumentSource};
 
 pub enum OperationType {
     Query,
@@ -7,160 +12,526 @@ pub enum OperationType {
 }
 
 pub enum Field {
-    Root(Vec<Field>, OperationType),
-    Leaf(String, Vec<Argument>),
-    Composite(String, Vec<Argument>, Vec<Field>),
+    Root(Vec<Field>, OperationType, Option<String>),
+    Leaf(String, Vec<Argument>, Vec<Directive>),
+    Composite(String, Vec<Argument>, Vec<Directive>, Vec<Field>),
     Alias(String, Box<Field>),
     InlineFragment(String, Vec<Field>),
+    /// The fields of a `QueryFragment`, along with the fragments name & the
+    /// type it's on.
+    Fragment(String, String, Vec<Directive>, Vec<Field>),
+}
+
+/// A variable used by a query, along with it's value.
+pub struct Variable {
+    pub name: String,
+    pub argument: Argument,
+}
+
+/// A directive that has been applied to a field, e.g. `@skip(if: true)`
+pub struct Directive {
+    name: String,
+    arguments: Vec<Argument>,
+}
+
+impl Directive {
+    pub fn new(name: &str, arguments: Vec<Argument>) -> Directive {
+        Directive {
+            name: name.to_string(),
+            arguments,
+        }
+    }
+}
+
+/// The state that's built up while converting fields into a query.
+#[derive(Default)]
+pub struct QueryContext {
+    pub variables: Vec<Variable>,
+    fragments: Option<FragmentDefinitions>,
+}
+
+impl QueryContext {
+    /// Creates a QueryContext that renders any fragments that are used more
+    /// than once in `fields` as named fragment definitions.
+    pub fn with_named_fragments(fields: &[Field]) -> QueryContext {
+        let mut usages = HashMap::new();
+        for field in fields {
+            field.count_fragment_usages(&mut usages);
+        }
+
+        QueryContext {
+            variables: vec![],
+            fragments: Some(FragmentDefinitions {
+                usages,
+                names: HashMap::new(),
+                definitions: vec![],
+            }),
+        }
+    }
+
+    fn is_reused(&self, signature: &str) -> bool {
+        match &self.fragments {
+            Some(fragments) => fragments.usages.get(signature).copied().unwrap_or(0) > 1,
+            None => false,
+        }
+    }
+}
+
+/// The named fragments of a query.
+struct FragmentDefinitions {
+    /// The number of places each fragment is used, keyed by signature.
+    usages: HashMap<String, usize>,
+    /// The name of each fragment that has been defined, keyed by signature.
+    names: HashMap<String, String>,
+    definitions: Vec<FragmentDefinition>,
 }
 
 impl Field {
-    pub fn query(
-        self,
-        indent: usize,
-        indent_size: usize,
-        arguments_out: &mut Vec<Argument>,
-    ) -> String {
+    /// Adds a directive to this field.
+    ///
+    /// Returns the keys this field will be found under in the response, which
+    /// will be empty if this field doesn't support directives.
+    pub fn add_directive(&mut self, directive: Directive) -> Vec<String> {
         match self {
-            Field::Leaf(field_name, args) => {
-                let arguments = handle_field_arguments(args, arguments_out);
-                format!(
-                    "{:indent$}{field_name}{arguments}\n",
-                    "",
-                    field_name = field_name,
-                    arguments = arguments,
-                    indent = indent
-                )
+            Field::Leaf(_, _, directives)
+            | Field::Composite(_, _, directives, _)
+            | Field::Fragment(_, _, directives, _) => {
+                directives.push(directive);
+                self.response_keys()
             }
-            Field::Composite(field_name, args, child_fields) => {
-                let arguments = handle_field_arguments(args, arguments_out);
-                let child_query: String = child_fields
-                    .into_iter()
-                    .map(|f| f.query(indent + indent_size, indent_size, arguments_out))
-                    .collect();
-
-                format!(
-                    "{0:indent$}{field_name}{arguments} {{\n{child_query}{0:indent$}}}\n",
-                    "",
-                    field_name = field_name,
-                    child_query = child_query,
-                    indent = indent,
-                    arguments = arguments
-                )
+            Field::Alias(alias, inner_field) => {
+                if inner_field.add_directive(directive).is_empty() {
+                    return vec![];
+                }
+                vec![alias.clone()]
+            }
+            Field::Root(_, _, _) | Field::InlineFragment(_, _) => vec![],
+        }
+    }
+
+    /// The keys this field will be found under in the response.
+    fn response_keys(&self) -> Vec<String> {
+        match self {
+            Field::Leaf(field_name, _, _) | Field::Composite(field_name, _, _, _) => {
+                vec![field_name.clone()]
+            }
+            Field::Alias(alias, _) => vec![alias.clone()],
+            Field::Fragment(_, _, _, fields) => {
+                fields.iter().flat_map(Field::response_keys).collect()
+            }
+            Field::Root(_, _, _) | Field::InlineFragment(_, _) => vec![],
+        }
+    }
+
+    /// Sets the source of any arguments named `argument_name` on this field.
+    pub fn set_argument_source(&mut self, argument_name: &str, source: &ArgumentSource) {
+        match self {
+            Field::Leaf(_, arguments, _) | Field::Composite(_, arguments, _, _) => {
+                for argument in arguments {
+                    if argument.name == argument_name {
+                        argument.source = source.clone();
+                    }
+                }
+            }
+            Field::Alias(_, inner_field) => inner_field.set_argument_source(argument_name, source),
+            Field::Fragment(_, _, _, fields) => {
+                for field in fields {
+                    field.set_argument_source(argument_name, source);
+                }
+            }
+            Field::Root(_, _, _) | Field::InlineFragment(_, _) => {}
+        }
+    }
+
+    /// Counts the number of places each fragment is used, keyed by signature.
+    ///
+    /// Fragments inside a fragment are only counted the first time we see the
+    /// outer fragment, as any repeats will be replaced with a spread.
+    fn count_fragment_usages(&self, usages: &mut HashMap<String, usize>) {
+        match self {
+            Field::Leaf(_, _, _) => {}
+            Field::Composite(_, _, _, fields)
+            | Field::InlineFragment(_, fields)
+            | Field::Root(fields, _, _) => {
+                for field in fields {
+                    field.count_fragment_usages(usages);
+                }
+            }
+            Field::Alias(_, inner_field) => inner_field.count_fragment_usages(usages),
+            Field::Fragment(name, type_name, _, fields) => {
+                let count = usages
+                    .entry(fragment_signature(name, type_name, fields))
+                    .or_insert(0);
+                *count += 1;
+                if *count == 1 {
+                    for field in fields {
+                        field.count_fragment_usages(usages);
+                    }
+                }
+            }
+        }
+    }
+
+    /// A string that uniquely identifies the contents of this field, including
+    /// the values of any arguments.
+    fn signature(&self) -> String {
+        fn arguments_signature(arguments: &[Argument]) -> String {
+            arguments
+                .iter()
+                .map(|arg| match &arg.serialize_result {
+                    Ok(value) => format!("{}:{}:{:?}:{}", arg.name, arg.type_, arg.source, value),
+                    Err(e) => format!("{}:{}:{:?}:{}", arg.name, arg.type_, arg.source, e),
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+        fn directives_signature(directives: &[Directive]) -> String {
+            directives
+                .iter()
+                .map(|d| format!("@{}({})", d.name, arguments_signature(&d.arguments)))
+                .collect()
+        }
+
+        match self {
+            Field::Leaf(name, arguments, directives) => format!(
+                "{}({}){}",
+                name,
+                arguments_signature(arguments),
+                directives_signature(directives)
+            ),
+            Field::Composite(name, arguments, directives, fields) => format!(
+                "{}({}){}{{{}}}",
+                name,
+                arguments_signature(arguments),
+                directives_signature(directives),
+                fields_signature(fields)
+            ),
+            Field::Alias(alias, inner_field) => format!("{}:{}", alias, inner_field.signature()),
+            Field::InlineFragment(type_name, fields) => {
+                format!("... on {}{{{}}}", type_name, fields_signature(fields))
+            }
+            Field::Fragment(name, type_name, directives, fields) => format!(
+                "{}{}",
+                fragment_signature(name, type_name, fields),
+                directives_signature(directives)
+            ),
+            Field::Root(fields, _, _) => fields_signature(fields),
+        }
+    }
+
+    /// Converts this field into the selections that represent it in a
+    /// query, extracting any variables & named fragments into `context`.
+    pub fn into_selections(self, context: &mut QueryContext) -> Vec<Selection> {
+        match self {
+            Field::Leaf(field_name, args, directives) => vec![Selection::Field {
+                alias: None,
+                name: field_name,
+                arguments: handle_field_arguments(args, None, &mut context.variables),
+                directives: handle_directives(directives, &mut context.variables),
+                selections: None,
+            }],
+            Field::Composite(field_name, args, directives, child_fields) => {
+                let arguments = handle_field_arguments(args, None, &mut context.variables);
+                let directives = handle_directives(directives, &mut context.variables);
+
+                vec![Selection::Field {
+                    alias: None,
+                    name: field_name,
+                    arguments,
+                    directives,
+                    selections: Some(fields_into_selections(child_fields, context)),
+                }]
             }
             Field::Alias(alias_name, inner_field) => {
-                let inner_field = inner_field.query(indent, indent_size, arguments_out);
-                format!(
-                    "{0:indent$}{alias_name}: {inner_field}",
-                    "",
-                    alias_name = alias_name,
-                    inner_field = inner_field,
-                    indent = indent
-                )
+                let mut selections = inner_field.into_selections(context);
+                for selection in &mut selections {
+                    if let Selection::Field { alias, .. } = selection {
+                        *alias = Some(alias_name.clone());
+                    }
+                }
+                selections
             }
-            Field::InlineFragment(type_name, child_fields) => {
-                let child_query: String = child_fields
-                    .into_iter()
-                    .map(|f| f.query(indent + indent_size, indent_size, arguments_out))
-                    .collect();
-
-                format!(
-                    "{0:indent$}... on {type_name} {{\n{child_query}{0:indent$}}}\n",
-                    "",
-                    type_name = type_name,
-                    child_query = child_query,
-                    indent = indent
+            Field::InlineFragment(type_name, child_fields) => vec![Selection::InlineFragment {
+                type_condition: Some(type_name),
+                directives: vec![],
+                selections: fields_into_selections(child_fields, context),
+            }],
+            Field::Fragment(name, type_name, directives, child_fields) => {
+                let signature = fragment_signature(&name, &type_name, &child_fields);
+                handle_fragment(
+                    name,
+                    type_name,
+                    directives,
+                    child_fields,
+                    signature,
+                    context,
                 )
             }
-            Field::Root(fields, operation_type) => {
-                let child_query: String = fields
-                    .into_iter()
-                    .map(|f| f.query(indent + indent_size, indent_size, arguments_out))
-                    .collect();
-
-                let arguments = handle_query_arguments(arguments_out);
-
-                let operation_def = match operation_type {
-                    OperationType::Query => "query Query",
-                    OperationType::Mutation => "mutation Mutation",
-                    OperationType::Subscription => "subscription Subscription",
+            // Root fields only make sense at the top level of a document, so
+            // anywhere else we just output their children.
+            Field::Root(fields, _, _) => fields_into_selections(fields, context),
+        }
+    }
+}
+
+/// Converts some top level fields into a `Document`.
+pub fn into_document(fields: Vec<Field>, context: &mut QueryContext) -> Document {
+    let mut definitions = vec![];
+
+    for field in fields {
+        match field {
+            Field::Root(fields, operation_type, operation_name) => {
+                let selections = fields_into_selections(fields, context);
+
+                let (keyword, default_name) = match operation_type {
+                    OperationType::Query => ("query", "Query"),
+                    OperationType::Mutation => ("mutation", "Mutation"),
+                    OperationType::Subscription => ("subscription", "Subscription"),
                 };
 
-                format!(
-                    "{operation_def}{arguments} {{\n{child_query}}}\n",
-                    operation_def = operation_def,
-                    arguments = arguments,
-                    child_query = child_query
-                )
+                definitions.push(Definition::Operation(OperationDefinition {
+                    keyword,
+                    name: operation_name.unwrap_or_else(|| default_name.to_string()),
+                    variables: context
+                        .variables
+                        .iter()
+                        .map(|variable| (variable.name.clone(), variable.argument.type_.clone()))
+                        .collect(),
+                    selections,
+                }));
+            }
+            other => {
+                let selections = other.into_selections(context);
+                match definitions.last_mut() {
+                    Some(Definition::Selections(existing)) => existing.extend(selections),
+                    _ => definitions.push(Definition::Selections(selections)),
+                }
             }
         }
     }
+
+    if let Some(fragments) = context.fragments.as_mut() {
+        definitions.extend(fragments.definitions.drain(..).map(Definition::Fragment));
+    }
+
+    Document { definitions }
+}
+
+fn fields_into_selections(fields: Vec<Field>, context: &mut QueryContext) -> Vec<Selection> {
+    fields
+        .into_iter()
+        .flat_map(|field| field.into_selections(context))
+        .collect()
+}
+
+fn fields_signature(fields: &[Field]) -> String {
+    fields
+        .iter()
+        .map(Field::signature)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The signature of a fragment, not including any directives applied to it.
+///
+/// Fragments with the same signature can share a definition.
+fn fragment_signature(name: &str, type_name: &str, fields: &[Field]) -> String {
+    format!(
+        "fragment {} on {}{{{}}}",
+        name,
+        type_name,
+        fields_signature(fields)
+    )
+}
+
+/// Returns the selections for a fragment.
+///
+/// If the fragment is used more than once & the context has named fragments
+/// enabled this will be a spread of a named fragment, which is defined the first
+/// time it's seen.  Otherwise the fields of the fragment are output directly.
+fn handle_fragment(
+    name: String,
+    type_name: String,
+    directives: Vec<Directive>,
+    child_fields: Vec<Field>,
+    signature: String,
+    context: &mut QueryContext,
+) -> Vec<Selection> {
+    let directives = handle_directives(directives, &mut context.variables);
+
+    if !context.is_reused(&signature) {
+        let selections = fields_into_selections(child_fields, context);
+        if directives.is_empty() {
+            return selections;
+        }
+
+        // We need somewhere to put the directives so we wrap the fields in
+        // an inline fragment without a type condition.
+        return vec![Selection::InlineFragment {
+            type_condition: None,
+            directives,
+            selections,
+        }];
+    }
+
+    let existing_name = context
+        .fragments
+        .as_ref()
+        .and_then(|fragments| fragments.names.get(&signature).cloned());
+
+    let fragment_name = match existing_name {
+        Some(fragment_name) => fragment_name,
+        None => {
+            let selections = fields_into_selections(child_fields, context);
+
+            let fragments = context
+                .fragments
+                .as_mut()
+                .expect("is_reused should only be true when fragments are named");
+
+            let mut fragment_name = name.clone();
+            let mut suffix = 1;
+            while fragments.names.values().any(|n| *n == fragment_name) {
+                suffix += 1;
+                fragment_name = format!("{}{}", name, suffix);
+            }
+
+            fragments.names.insert(signature, fragment_name.clone());
+            fragments.definitions.push(FragmentDefinition {
+                name: fragment_name.clone(),
+                type_condition: type_name,
+                selections,
+            });
+
+            fragment_name
+        }
+    };
+
+    vec![Selection::FragmentSpread {
+        name: fragment_name,
+        directives,
+    }]
+}
+
+/// Extracts any argument values & returns the arguments to use in a query.
+///
+/// `directive_name` should be provided for the arguments of a directive, and
+/// is used when naming their variables.
+fn handle_field_arguments(
+    arguments: Vec<Argument>,
+    directive_name: Option<&str>,
+    variables_out: &mut Vec<Variable>,
+) -> Vec<(String, Value)> {
+    arguments
+        .into_iter()
+        .map(|arg| {
+            let name = arg.name.clone();
+            let value = handle_argument_value(arg, directive_name, variables_out);
+            (name, value)
+        })
+        .collect()
 }
 
-/// Extracts any argument values & returns a string to be used in a query.
-fn handle_field_arguments(arguments: Vec<Argument>, arguments_out: &mut Vec<Argument>) -> String {
-    if arguments.is_empty() {
-        "".to_string()
-    } else {
-        let mut argument_index = arguments_out.len();
-
-        let comma_seperated = arguments
-            .into_iter()
-            .map(|arg| {
-                let rv = format!("{}: $_{}", arg.name, argument_index);
-                arguments_out.push(arg);
-                argument_index += 1;
-                rv
-            })
-            .collect::<Vec<_>>()
-            .join(", ");
-
-        format!("({})", comma_seperated)
+/// Returns the value to use for an argument in a query.
+///
+/// This will be a literal if the argument was provided as one, otherwise it
+/// will be a variable.  Arguments with the same name & value share a variable,
+/// and arguments with the same name but different values are given distinct
+/// variables.
+fn handle_argument_value(
+    argument: Argument,
+    directive_name: Option<&str>,
+    variables_out: &mut Vec<Variable>,
+) -> Value {
+    let base_name = match (&argument.source, &argument.serialize_result) {
+        (ArgumentSource::Literal, Ok(value)) if argument.uploads.is_empty() => {
+            return Value::Literal(value.clone())
+        }
+        (ArgumentSource::Variable(name), _) => name.clone(),
+        (_, _) => match directive_name {
+            Some(directive_name) => format!("{}{}", directive_name, capitalize(&argument.name)),
+            None => argument.name.clone(),
+        },
+    };
+
+    let mut name = base_name.clone();
+    let mut suffix = 1;
+    while let Some(existing) = variables_out.iter().find(|v| v.name == name) {
+        if existing.argument.same_value_as(&argument) {
+            return Value::Variable(name);
+        }
+        suffix += 1;
+        name = format!("{}{}", base_name, suffix);
     }
+
+    variables_out.push(Variable {
+        name: name.clone(),
+        argument,
+    });
+
+    Value::Variable(name)
 }
 
-/// Extracts any argument values & returns a string to be used in a query.
-fn handle_query_arguments(arguments: &[Argument]) -> String {
-    if arguments.is_empty() {
-        "".to_string()
-    } else {
-        let comma_seperated = arguments
-            .iter()
-            .enumerate()
-            .map(|(i, arg)| format!("$_{}: {}", i, arg.type_))
-            .collect::<Vec<_>>()
-            .join(", ");
-
-        format!("({})", comma_seperated)
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
     }
 }
 
+/// Extracts the argument values of any directives & returns the directives
+/// to use in a query.
+fn handle_directives(
+    directives: Vec<Directive>,
+    variables_out: &mut Vec<Variable>,
+) -> Vec<printer::Directive> {
+    directives
+        .into_iter()
+        .map(|directive| printer::Directive {
+            arguments: handle_field_arguments(
+                directive.arguments,
+                Some(&directive.name),
+                variables_out,
+            ),
+            name: directive.name,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
+    use super::super::printer::Style;
     use super::*;
     use serde_json::json;
 
+    fn print(fields: Vec<Field>, context: &mut QueryContext) -> String {
+        into_document(fields, context).print(Style::Pretty)
+    }
+
     #[test]
     fn test_query_building() {
         let fields = Field::Composite(
             "test_struct".to_string(),
             vec![],
+            vec![],
             vec![
-                Field::Leaf("field_one".to_string(), vec![]),
+                Field::Leaf("field_one".to_string(), vec![], vec![]),
                 Field::Composite(
                     "nested".to_string(),
                     vec![],
-                    vec![Field::Leaf("a_string".to_string(), vec![])],
+                    vec![],
+                    vec![Field::Leaf("a_string".to_string(), vec![], vec![])],
                 ),
             ],
         );
-        let mut arguments = vec![];
+        let mut context = QueryContext::default();
 
         assert_eq!(
-            fields.query(0, 2, &mut arguments),
+            print(vec![fields], &mut context),
             "test_struct {\n  field_one\n  nested {\n    a_string\n  }\n}\n"
         );
-        assert!(arguments.is_empty());
+        assert!(context.variables.is_empty());
     }
 
     #[test]
@@ -168,21 +539,22 @@ mod tests {
         let fields = Field::Composite(
             "test".to_string(),
             vec![],
+            vec![],
             vec![
-                Field::Leaf("__typename".to_string(), vec![]),
+                Field::Leaf("__typename".to_string(), vec![], vec![]),
                 Field::InlineFragment(
                     "TypeOne".to_string(),
-                    vec![Field::Leaf("a_field".to_string(), vec![])],
+                    vec![Field::Leaf("a_field".to_string(), vec![], vec![])],
                 ),
                 Field::InlineFragment(
                     "TypeTwo".to_string(),
-                    vec![Field::Leaf("another_field".to_string(), vec![])],
+                    vec![Field::Leaf("another_field".to_string(), vec![], vec![])],
                 ),
             ],
         );
-        let mut arguments = vec![];
+        let mut context = QueryContext::default();
         assert_eq!(
-            fields.query(0, 2, &mut arguments),
+            print(vec![fields], &mut context),
             "test {\n  __typename\n  ... on TypeOne {\n    a_field\n  }\n  ... on TypeTwo {\n    another_field\n  }\n}\n"
         );
     }
@@ -192,35 +564,40 @@ mod tests {
         let fields = Field::Composite(
             "test_struct".to_string(),
             vec![Argument::new("an_arg", "Bool!", Ok(json! { false }))],
+            vec![],
             vec![
-                Field::Leaf("field_one".to_string(), vec![]),
+                Field::Leaf("field_one".to_string(), vec![], vec![]),
                 Field::Composite(
                     "nested".to_string(),
                     vec![],
+                    vec![],
                     vec![Field::Leaf(
                         "a_string".to_string(),
                         vec![Argument::new("another_arg", "Bool!", Ok(json! { true }))],
+                        vec![],
                     )],
                 ),
             ],
         );
-        let mut arguments = vec![];
+        let mut context = QueryContext::default();
 
         assert_eq!(
-            fields.query(0, 2, &mut arguments),
-            "test_struct(an_arg: $_0) {\n  field_one\n  nested {\n    a_string(another_arg: $_1)\n  }\n}\n"
+            print(vec![fields], &mut context),
+            "test_struct(an_arg: $an_arg) {\n  field_one\n  nested {\n    a_string(another_arg: $another_arg)\n  }\n}\n"
         );
         assert_eq!(
-            arguments
+            context
+                .variables
                 .iter()
-                .map(|a| a.serialize_result.as_ref().unwrap())
+                .map(|a| a.argument.serialize_result.as_ref().unwrap())
                 .collect::<Vec<_>>(),
             vec![&json!(false), &json!(true)]
         );
         assert_eq!(
-            arguments
+            context
+                .variables
                 .iter()
-                .map(|a| a.type_.clone())
+                .map(|a| a.argument.type_.clone())
                 .collect::<Vec<_>>(),
             vec!["Bool!", "Bool!"]
         );
@@ -231,26 +608,199 @@ mod tests {
         let fields = Field::Composite(
             "hello".into(),
             vec![],
+            vec![],
             vec![
                 Field::Alias(
                     "fieldOne".into(),
-                    Box::new(Field::Leaf("anInt".into(), vec![])),
+                    Box::new(Field::Leaf("anInt".into(), vec![], vec![])),
                 ),
                 Field::Alias(
                     "fieldTwo".into(),
                     Box::new(Field::Leaf(
                         "aBool".into(),
                         vec![Argument::new("myArg", "Bool!", Ok(json!(true)))],
+                        vec![],
                     )),
                 ),
             ],
         );
 
-        let mut arguments = vec![];
-        insta::assert_snapshot!(fields.query(0, 2, &mut arguments), @r###"
+        let mut context = QueryContext::default();
+        insta::assert_snapshot!(print(vec![fields], &mut context), @r###"
         hello {
-          fieldOne:   anInt
-          fieldTwo:   aBool(myArg: $_0)
+          fieldOne: anInt
+          fieldTwo: aBool(myArg: $myArg)
+        }
+        "###);
+    }
+
+    #[test]
+    fn test_directives() {
+        let fields = Field::Composite(
+            "hello".into(),
+            vec![],
+            vec![],
+            vec![
+                Field::Leaf(
+                    "anInt".into(),
+                    vec![Argument::new("myArg", "Bool!", Ok(json!(true)))],
+                    vec![Directive::new(
+                        "skip",
+                        vec![Argument::new("if", "Boolean!", Ok(json!(false)))],
+                    )],
+                ),
+                Field::Composite(
+                    "nested".into(),
+                    vec![],
+                    vec![Directive::new(
+                        "include",
+                        vec![Argument::new("if", "Boolean!", Ok(json!(true)))],
+                    )],
+                    vec![Field::Leaf("aString".into(), vec![], vec![])],
+                ),
+            ],
+        );
+
+        let mut context = QueryContext::default();
+        insta::assert_snapshot!(print(vec![fields], &mut context), @r###"
+        hello {
+          anInt(myArg: $myArg) @skip(if: $skipIf)
+          nested @include(if: $includeIf) {
+            aString
+          }
+        }
+        "###);
+        assert_eq!(
+            context
+                .variables
+                .iter()
+                .map(|a| a.argument.type_.clone())
+                .collect::<Vec<_>>(),
+            vec!["Bool!", "Boolean!", "Boolean!"]
+        );
+    }
+
+    #[test]
+    fn test_variable_names() {
+        let film = |alias: &str, arg: Argument| {
+            Field::Alias(
+                alias.into(),
+                Box::new(Field::Leaf("film".into(), vec![arg], vec![])),
+            )
+        };
+        let fields = Field::Root(
+            vec![
+                film("one", Argument::new("id", "ID", Ok(json!("1")))),
+                film("two", Argument::new("id", "ID", Ok(json!("2")))),
+                film("three", Argument::new("id", "ID", Ok(json!("1")))),
+                film(
+                    "four",
+                    Argument::new("id", "ID", Ok(json!("4")))
+                        .with_source(ArgumentSource::Variable("filmId".into())),
+                ),
+                film(
+                    "five",
+                    Argument::new("id", "ID", Ok(json!({"a": [1, "b"]})))
+                        .with_source(ArgumentSource::Literal),
+                ),
+            ],
+            OperationType::Query,
+            None,
+        );
+
+        let mut context = QueryContext::default();
+        insta::assert_snapshot!(print(vec![fields], &mut context), @r###"
+        query Query($id: ID, $id2: ID, $filmId: ID) {
+          one: film(id: $id)
+          two: film(id: $id2)
+          three: film(id: $id)
+          four: film(id: $filmId)
+          five: film(id: {a: [1, "b"]})
+        }
+        "###);
+        assert_eq!(
+            context
+                .variables
+                .iter()
+                .map(|v| (
+                    v.name.as_str(),
+                    v.argument.serialize_result.as_ref().unwrap()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("id", &json!("1")),
+                ("id2", &json!("2")),
+                ("filmId", &json!("4"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_named_fragments() {
+        let fragment = |field_name: &str| {
+            Field::Fragment(
+                "Film".into(),
+                "Film".into(),
+                vec![],
+                vec![Field::Leaf(field_name.into(), vec![], vec![])],
+            )
+        };
+        let film = |alias: &str, fragment: Field| {
+            Field::Alias(
+                alias.into(),
+                Box::new(Field::Composite(
+                    "film".into(),
+                    vec![],
+                    vec![],
+                    vec![fragment],
+                )),
+            )
+        };
+        let mut skipped = fragment("title");
+        skipped.add_directive(Directive::new(
+            "skip",
+            vec![Argument::new("if", "Boolean!", Ok(json!(true)))],
+        ));
+        let fields = vec![Field::Root(
+            vec![
+                film("one", fragment("title")),
+                film("two", skipped),
+                film("three", fragment("director")),
+                film("four", fragment("director")),
+                film("five", fragment("producers")),
+            ],
+            OperationType::Query,
+            None,
+        )];
+
+        let mut context = QueryContext::with_named_fragments(&fields);
+        let query = print(fields, &mut context);
+
+        insta::assert_snapshot!(query, @r###"
+        query Query($skipIf: Boolean!) {
+          one: film {
+            ...Film
+          }
+          two: film {
+            ...Film @skip(if: $skipIf)
+          }
+          three: film {
+            ...Film2
+          }
+          four: film {
+            ...Film2
+          }
+          five: film {
+            producers
+          }
+        }
+
+        fragment Film on Film {
+          title
+        }
+
+        fragment Film2 on Film {
+          director
         }
         "###);
     }
@@ -13,20 +13,33 @@
 //!    uses another selection set to decode the type of that object.
 //! 4. Combinators like the `map`, `map2` etc, functions that combine multiple
 //!    selection sets into a single type.
+//! 5. The `skip` & `include` functions, which apply directives to the fields
+//!    of a selection set.
 //!
 //! Cynic provides Query DSL generation & derive macros that mean for many cases you
 //! shouldn't need to use the functions in this module directly.  However for more
 //! advanced use cases (or if you dislike macros) these can still be useful.
 
+mod decoder;
 mod field;
+pub(crate) mod printer;
 
-use json_decode::{BoxDecoder, DecodeError};
+use json_decode::DecodeError;
 use std::collections::HashMap;
 use std::marker::PhantomData;
 
-use crate::{scalar, Argument, MutationRoot, QueryRoot, SubscriptionRoot};
+use crate::{
+    decode_error::{graphql_type_name, json_kind},
+    scalar, Argument, ArgumentSource, MutationRoot, QueryRoot, ResponseDecodeError,
+    SubscriptionRoot,
+};
 
-use field::{Field, OperationType};
+use decoder::Decoder;
+use field::{Directive, Field, OperationType, QueryContext};
+
+pub(crate) use decoder::BoxDecoder;
+pub(crate) use field::Variable;
+pub(crate) use printer::{Document, Style};
 
 /// A marker trait used to encode GraphQL subtype relationships into the Rust
 /// typesystem.
@@ -78,7 +91,7 @@ impl<'a, DecodesTo, TypeLock> SelectionSet<'a, DecodesTo, TypeLock> {
         DecodesTo: 'a,
         R: 'a,
     {
-        SelectionSet::new(self.fields, json_decode::map(f, self.decoder))
+        SelectionSet::new(self.fields, decoder::map(f, self.decoder))
     }
 
     /// Creates a `SelectionSet` that depends on previous resutls.
@@ -104,7 +117,7 @@ impl<'a, DecodesTo, TypeLock> SelectionSet<'a, DecodesTo, TypeLock> {
         let boxed_func = Box::new(f);
         SelectionSet::new(
             self.fields,
-            json_decode::and_then(move |value| (*boxed_func)(value).decoder, self.decoder),
+            decoder::and_then(move |value| (*boxed_func)(value).decoder, self.decoder),
         )
     }
 
@@ -121,43 +134,78 @@ impl<'a, DecodesTo, TypeLock> SelectionSet<'a, DecodesTo, TypeLock> {
         SelectionSet::new(self.fields, self.decoder)
     }
 
+    /// Sets how the value of the `argument_name` argument to the fields of
+    /// this `SelectionSet` is provided in the query.
+    ///
+    /// ```rust
+    /// # use cynic::{ArgumentSource, selection_set::{field, string}};
+    /// field::<_, (), _>(
+    ///     "film",
+    ///     vec![cynic::Argument::new("id", "ID!", Ok("ZmlsbXM6MQ==".into()))],
+    ///     string(),
+    /// )
+    /// .with_argument_source("id", ArgumentSource::Variable("filmId".into()));
+    /// ```
+    pub fn with_argument_source(mut self, argument_name: &str, source: ArgumentSource) -> Self {
+        for field in &mut self.fields {
+            field.set_argument_source(argument_name, &source);
+        }
+        self
+    }
+
     #[cfg(test)]
-    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, ResponseDecodeError> {
         (*self.decoder).decode(value)
     }
 
-    pub(crate) fn query_arguments_and_decoder(
+    /// Converts this `SelectionSet` into a document, it's variables & a
+    /// decoder.
+    ///
+    /// If `named_fragments` is true, any fragments that are used more than
+    /// once will be output as named fragment definitions.
+    pub(crate) fn document_variables_and_decoder(
         self,
-    ) -> (String, Vec<Argument>, BoxDecoder<'a, DecodesTo>) {
-        let mut arguments: Vec<Argument> = vec![];
-        let query = self
-            .fields
-            .into_iter()
-            .map(|f| f.query(0, 2, &mut arguments))
-            .collect();
+        named_fragments: bool,
+    ) -> (Document, Vec<Variable>, BoxDecoder<'a, DecodesTo>) {
+        let mut context = if named_fragments {
+            QueryContext::with_named_fragments(&self.fields)
+        } else {
+            QueryContext::default()
+        };
+        let document = field::into_document(self.fields, &mut context);
 
-        (query, arguments, self.decoder)
+        (document, context.variables, self.decoder)
+    }
+
+    #[cfg(test)]
+    fn query_variables_and_decoder(
+        self,
+        named_fragments: bool,
+    ) -> (String, Vec<Variable>, BoxDecoder<'a, DecodesTo>) {
+        let (document, variables, decoder) = self.document_variables_and_decoder(named_fragments);
+
+        (document.print(Style::Pretty), variables, decoder)
     }
 }
 
 /// Creates a `SelectionSet` that will decode a `String`
 pub fn string() -> SelectionSet<'static, String, String> {
-    SelectionSet::new(vec![], json_decode::string())
+    SelectionSet::new(vec![], decoder::from_json_decode(json_decode::string()))
 }
 
 /// Creates a `SelectionSet` that will decode an `i32`
 pub fn integer() -> SelectionSet<'static, i32, i32> {
-    SelectionSet::new(vec![], json_decode::integer())
+    SelectionSet::new(vec![], decoder::from_json_decode(json_decode::integer()))
 }
 
 /// Creates a `SelectionSet` that will decode an `f64`
 pub fn float() -> SelectionSet<'static, f64, f64> {
-    SelectionSet::new(vec![], json_decode::float())
+    SelectionSet::new(vec![], decoder::from_json_decode(json_decode::float()))
 }
 
 /// Creates a `SelectionSet` that will decode a `bool`
 pub fn boolean() -> SelectionSet<'static, bool, bool> {
-    SelectionSet::new(vec![], json_decode::boolean())
+    SelectionSet::new(vec![], decoder::from_json_decode(json_decode::boolean()))
 }
 
 /// Creates a `SelectionSet` for decoding a GQL enum with a function.
@@ -169,7 +217,7 @@ where
     E: crate::Enum<TypeLock> + 'static,
     F: (Fn(String) -> SelectionSet<'static, E, TypeLock>) + 'static + Sync + Send,
 {
-    SelectionSet::new(vec![], json_decode::string()).and_then(f)
+    SelectionSet::new(vec![], decoder::from_json_decode(json_decode::string())).and_then(f)
 }
 
 /// Creates a `SelectionSet` that will decode a type that implements `serde::Deserialize`
@@ -178,12 +226,12 @@ where
     for<'de> T: serde::Deserialize<'de>,
     T: 'static + Send + Sync,
 {
-    SelectionSet::new(vec![], json_decode::serde())
+    SelectionSet::new(vec![], decoder::from_json_decode(json_decode::serde()))
 }
 
 /// Creates a `SelectionSet` that will decode into a `serde_json::Value`
 pub fn json() -> SelectionSet<'static, serde_json::Value, ()> {
-    SelectionSet::new(vec![], json_decode::json())
+    SelectionSet::new(vec![], decoder::from_json_decode(json_decode::json()))
 }
 
 /// Creates a `SelectionSet` that will decode a type that implements `Scalar`
@@ -192,7 +240,7 @@ where
     S: scalar::Scalar<TypeLock> + 'static + Send + Sync,
     TypeLock: 'static + Send + Sync,
 {
-    SelectionSet::new(vec![], scalar::decoder())
+    SelectionSet::new(vec![], decoder::from_json_decode(scalar::decoder()))
 }
 
 /// Creates a `SelectionSet` that decodes a Vec of `inner_selection`
@@ -204,7 +252,7 @@ where
 {
     SelectionSet::new(
         inner_selection.fields,
-        json_decode::list(inner_selection.decoder),
+        decoder::list(inner_selection.decoder),
     )
 }
 
@@ -217,7 +265,7 @@ where
 {
     SelectionSet::new(
         inner_selection.fields,
-        json_decode::option(inner_selection.decoder),
+        decoder::option(inner_selection.decoder),
     )
 }
 
@@ -231,14 +279,24 @@ where
     DecodesTo: 'a,
 {
     let field = if selection_set.fields.is_empty() {
-        Field::Leaf(field_name.to_string(), arguments)
+        Field::Leaf(field_name.to_string(), arguments, vec![])
     } else {
-        Field::Composite(field_name.to_string(), arguments, selection_set.fields)
+        Field::Composite(
+            field_name.to_string(),
+            arguments,
+            vec![],
+            selection_set.fields,
+        )
     };
 
     SelectionSet::new(
         vec![field],
-        json_decode::field(field_name, selection_set.decoder),
+        decoder::field(
+            field_name,
+            std::any::type_name::<DecodesTo>(),
+            graphql_type_name::<InnerTypeLock>(),
+            selection_set.decoder,
+        ),
     )
 }
 
@@ -253,14 +311,24 @@ where
     DecodesTo: 'a,
 {
     let inner_field = if selection_set.fields.is_empty() {
-        Field::Leaf(field_name.to_string(), arguments)
+        Field::Leaf(field_name.to_string(), arguments, vec![])
     } else {
-        Field::Composite(field_name.to_string(), arguments, selection_set.fields)
+        Field::Composite(
+            field_name.to_string(),
+            arguments,
+            vec![],
+            selection_set.fields,
+        )
     };
 
     SelectionSet::new(
         vec![Field::Alias(alias.into(), Box::new(inner_field))],
-        json_decode::field(alias, selection_set.decoder),
+        decoder::field(
+            alias,
+            std::any::type_name::<DecodesTo>(),
+            graphql_type_name::<InnerTypeLock>(),
+            selection_set.decoder,
+        ),
     )
 }
 
@@ -283,7 +351,7 @@ where
     let mut fields = vec![];
     let mut decoders = HashMap::new();
 
-    fields.push(Field::Leaf("__typename".to_string(), vec![]));
+    fields.push(Field::Leaf("__typename".to_string(), vec![], vec![]));
 
     for (fragment_type, selection_set) in fragments {
         fields.push(Field::InlineFragment(
@@ -310,56 +378,235 @@ where
     )
 }
 
+/// Marks the fields of a `SelectionSet` as belonging to a named fragment on
+/// `type_name`.
+///
+/// By default these fields are output exactly as they would be without this,
+/// but an `Operation` can be built that outputs any fragments that are used in
+/// several places as named fragment definitions.  The `QueryFragment` derive
+/// calls this with the name of the struct it's deriving for.
+///
+/// ```rust
+/// # use cynic::selection_set::{field, named_fragment, string};
+/// named_fragment::<_, ()>("FilmFields", "Film", field("title", vec![], string()));
+/// ```
+pub fn named_fragment<'a, DecodesTo, TypeLock>(
+    name: &str,
+    type_name: &str,
+    selection_set: SelectionSet<'a, DecodesTo, TypeLock>,
+) -> SelectionSet<'a, DecodesTo, TypeLock>
+where
+    DecodesTo: 'a,
+{
+    if selection_set.fields.is_empty() {
+        return selection_set;
+    }
+
+    SelectionSet::new(
+        vec![Field::Fragment(
+            name.to_string(),
+            type_name.to_string(),
+            vec![],
+            selection_set.fields,
+        )],
+        selection_set.decoder,
+    )
+}
+
 struct FragmentDecoder<'a, DecodesTo> {
     decoders: HashMap<String, BoxDecoder<'a, DecodesTo>>,
     backup_decoder: Option<BoxDecoder<'a, DecodesTo>>,
 }
 
-impl<'a, DecodesTo> json_decode::Decoder<'a, DecodesTo> for FragmentDecoder<'a, DecodesTo> {
-    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
-        let typename = value["__typename"].as_str().ok_or_else(|| {
-            json_decode::DecodeError::MissingField("__typename".into(), value.to_string())
-        })?;
+impl<'a, DecodesTo> Decoder<DecodesTo> for FragmentDecoder<'a, DecodesTo> {
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, ResponseDecodeError> {
+        let typename = value
+            .get("__typename")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| {
+                ResponseDecodeError::from(DecodeError::MissingField(
+                    "__typename".into(),
+                    json_kind(value).into(),
+                ))
+                .within_field(
+                    "__typename",
+                    std::any::type_name::<String>(),
+                    Some("String"),
+                )
+            })?;
 
         if let Some(decoder) = self.decoders.get(typename) {
             decoder.decode(value)
         } else if let Some(backup_decoder) = &self.backup_decoder {
             backup_decoder.decode(value)
         } else {
-            Err(json_decode::DecodeError::Other(format!(
-                "Unknown __typename: {}",
-                typename,
-            )))
+            Err(DecodeError::Other(format!("Unknown __typename: {}", typename)).into())
+        }
+    }
+}
+
+/// Adds a `@skip(if: condition)` directive to the fields of a `SelectionSet`.
+///
+/// The server will omit the fields from it's response if `condition` is true,
+/// so this decodes to `None` when they're missing.
+///
+/// ```rust
+/// # use cynic::selection_set::{field, skip, string};
+/// skip::<_, ()>(true, field::<_, (), _>("name", vec![], string()));
+/// ```
+pub fn skip<'a, DecodesTo, TypeLock>(
+    condition: bool,
+    selection_set: SelectionSet<'a, DecodesTo, TypeLock>,
+) -> SelectionSet<'a, Option<DecodesTo>, TypeLock>
+where
+    DecodesTo: 'a,
+{
+    with_condition_directive(
+        "skip",
+        condition,
+        ArgumentSource::ArgumentName,
+        selection_set,
+    )
+}
+
+/// Like `skip`, but with control over how `condition` is provided in the
+/// query.
+pub fn skip_with_source<'a, DecodesTo, TypeLock>(
+    condition: bool,
+    source: ArgumentSource,
+    selection_set: SelectionSet<'a, DecodesTo, TypeLock>,
+) -> SelectionSet<'a, Option<DecodesTo>, TypeLock>
+where
+    DecodesTo: 'a,
+{
+    with_condition_directive("skip", condition, source, selection_set)
+}
+
+/// Adds an `@include(if: condition)` directive to the fields of a
+/// `SelectionSet`.
+///
+/// The server will omit the fields from it's response if `condition` is false,
+/// so this decodes to `None` when they're missing.
+pub fn include<'a, DecodesTo, TypeLock>(
+    condition: bool,
+    selection_set: SelectionSet<'a, DecodesTo, TypeLock>,
+) -> SelectionSet<'a, Option<DecodesTo>, TypeLock>
+where
+    DecodesTo: 'a,
+{
+    with_condition_directive(
+        "include",
+        condition,
+        ArgumentSource::ArgumentName,
+        selection_set,
+    )
+}
+
+/// Like `include`, but with control over how `condition` is provided in the
+/// query.
+pub fn include_with_source<'a, DecodesTo, TypeLock>(
+    condition: bool,
+    source: ArgumentSource,
+    selection_set: SelectionSet<'a, DecodesTo, TypeLock>,
+) -> SelectionSet<'a, Option<DecodesTo>, TypeLock>
+where
+    DecodesTo: 'a,
+{
+    with_condition_directive("include", condition, source, selection_set)
+}
+
+fn with_condition_directive<'a, DecodesTo, TypeLock>(
+    directive_name: &str,
+    condition: bool,
+    source: ArgumentSource,
+    selection_set: SelectionSet<'a, DecodesTo, TypeLock>,
+) -> SelectionSet<'a, Option<DecodesTo>, TypeLock>
+where
+    DecodesTo: 'a,
+{
+    let mut fields = selection_set.fields;
+    let field_names = fields
+        .iter_mut()
+        .flat_map(|field| {
+            field.add_directive(Directive::new(
+                directive_name,
+                vec![
+                    Argument::new("if", "Boolean!", serde_json::to_value(condition))
+                        .with_source(source.clone()),
+                ],
+            ))
+        })
+        .collect();
+
+    SelectionSet::new(
+        fields,
+        Box::new(OmittableFieldsDecoder {
+            field_names,
+            inner: selection_set.decoder,
+        }),
+    )
+}
+
+/// A decoder for fields that the server might omit from a response.
+///
+/// Decodes to `None` if any of the fields are missing, otherwise runs the
+/// inner decoder.
+struct OmittableFieldsDecoder<'a, DecodesTo> {
+    field_names: Vec<String>,
+    inner: BoxDecoder<'a, DecodesTo>,
+}
+
+impl<'a, DecodesTo> Decoder<Option<DecodesTo>> for OmittableFieldsDecoder<'a, DecodesTo> {
+    fn decode(&self, value: &serde_json::Value) -> Result<Option<DecodesTo>, ResponseDecodeError> {
+        if self
+            .field_names
+            .iter()
+            .any(|field_name| value.get(field_name).is_none())
+        {
+            return Ok(None);
         }
+
+        self.inner.decode(value).map(Some)
     }
 }
 
 pub(crate) fn query_root<'a, DecodesTo, InnerTypeLock: QueryRoot>(
     selection_set: SelectionSet<'a, DecodesTo, InnerTypeLock>,
+    operation_name: Option<String>,
 ) -> SelectionSet<'a, DecodesTo, ()>
 where
     DecodesTo: 'a,
 {
     SelectionSet::new(
-        vec![Field::Root(selection_set.fields, OperationType::Query)],
+        vec![Field::Root(
+            selection_set.fields,
+            OperationType::Query,
+            operation_name,
+        )],
         selection_set.decoder,
     )
 }
 
 pub(crate) fn mutation_root<'a, DecodesTo, InnerTypeLock: MutationRoot>(
     selection_set: SelectionSet<'a, DecodesTo, InnerTypeLock>,
+    operation_name: Option<String>,
 ) -> SelectionSet<'a, DecodesTo, ()>
 where
     DecodesTo: 'a,
 {
     SelectionSet::new(
-        vec![Field::Root(selection_set.fields, OperationType::Mutation)],
+        vec![Field::Root(
+            selection_set.fields,
+            OperationType::Mutation,
+            operation_name,
+        )],
         selection_set.decoder,
     )
 }
 
 pub(crate) fn subscription_root<'a, DecodesTo, InnerTypeLock: SubscriptionRoot>(
     selection_set: SelectionSet<'a, DecodesTo, InnerTypeLock>,
+    operation_name: Option<String>,
 ) -> SelectionSet<'a, DecodesTo, ()>
 where
     DecodesTo: 'a,
@@ -368,6 +615,7 @@ where
         vec![Field::Root(
             selection_set.fields,
             OperationType::Subscription,
+            operation_name,
         )],
         selection_set.decoder,
     )
@@ -386,7 +634,7 @@ where
     T1: 'a,
     NewDecodesTo: 'a,
 {
-    SelectionSet::new(param1.fields, json_decode::map(func, param1.decoder))
+    SelectionSet::new(param1.fields, decoder::map(func, param1.decoder))
 }
 
 macro_rules! define_map {
@@ -432,7 +680,14 @@ macro_rules! define_map {
                 fields.extend($i.fields.into_iter());
             )+
 
-            SelectionSet::new(fields, json_decode::$fn_name(func, $($i.decoder, )+))
+            $(
+                let $i = $i.decoder;
+            )+
+
+            SelectionSet::new(
+                fields,
+                Box::new(move |value: &serde_json::Value| Ok(func($($i.decode(value)?, )+))),
+            )
         }
     };
 }
@@ -602,7 +857,10 @@ pub fn succeed<'a, V, TypeLock>(value: V) -> SelectionSet<'a, V, TypeLock>
 where
     V: Clone + Send + Sync + 'a,
 {
-    SelectionSet::new(vec![], json_decode::succeed(value))
+    SelectionSet::new(
+        vec![],
+        Box::new(move |_: &serde_json::Value| Ok(value.clone())),
+    )
 }
 
 /// Creates a `SelectionSet` that always decodes succesfully to the result of a function
@@ -614,13 +872,7 @@ where
     F: Fn() -> V + Send + Sync + 'a,
     V: Send + Sync + 'a,
 {
-    SelectionSet::new(
-        vec![],
-        json_decode::map(
-            move |_| f(),
-            json_decode::succeed::<Option<core::convert::Infallible>>(None),
-        ),
-    )
+    SelectionSet::new(vec![], Box::new(move |_: &serde_json::Value| Ok(f())))
 }
 
 /// Creates a `SelectionSet` that always fails to decode.
@@ -631,7 +883,11 @@ where
 /// See the [`SelectionSet::and_then`](cynic::selection_set::SelectionSet::and_then)
 /// docs for an example.
 pub fn fail<V, TypeLock>(err: impl Into<String>) -> SelectionSet<'static, V, TypeLock> {
-    SelectionSet::new(vec![], json_decode::fail(err))
+    let err = err.into();
+    SelectionSet::new(
+        vec![],
+        Box::new(move |_: &serde_json::Value| Err(DecodeError::Other(err.clone()).into())),
+    )
 }
 
 #[cfg(test)]
@@ -791,7 +1047,7 @@ mod tests {
             )),
         );
 
-        let (query, args, _) = selection_set.query_arguments_and_decoder();
+        let (query, args, _) = selection_set.query_variables_and_decoder(false);
 
         assert_eq!(
             query,
@@ -813,7 +1069,7 @@ mod tests {
             ),
         );
 
-        let (_query, args, _) = selection_set.query_arguments_and_decoder();
+        let (_query, args, _) = selection_set.query_variables_and_decoder(false);
         assert_eq!(args.len(), 1);
     }
 
@@ -832,7 +1088,7 @@ mod tests {
             ),
         );
 
-        let (_query, args, _) = selection_set.query_arguments_and_decoder();
+        let (_query, args, _) = selection_set.query_variables_and_decoder(false);
         assert_eq!(args.len(), 2);
     }
 
@@ -878,7 +1134,7 @@ mod tests {
     fn inline_fragments_no_backup_builds_correct_query() {
         let selection_set = inline_fragment_query(None);
 
-        let (query, _, _) = selection_set.query_arguments_and_decoder();
+        let (query, _, _) = selection_set.query_variables_and_decoder(false);
 
         insta::assert_snapshot!(query, @r###"
         __typename
@@ -895,7 +1151,7 @@ mod tests {
     fn inline_fragments_with_backup_builds_correct_query() {
         let selection_set = inline_fragment_query(Some(field("other", vec![], string())));
 
-        let (query, _, _) = selection_set.query_arguments_and_decoder();
+        let (query, _, _) = selection_set.query_variables_and_decoder(false);
 
         insta::assert_snapshot!(query, @r###"
         __typename
@@ -913,7 +1169,7 @@ mod tests {
     fn inline_fragments_decoding_without_backup() {
         let selection_set = inline_fragment_query(None);
 
-        let (_, _, decoder) = selection_set.query_arguments_and_decoder();
+        let (_, _, decoder) = selection_set.query_variables_and_decoder(false);
 
         let result =
             decoder.decode(&json!({"__typename": "User", "name": "Graeme", "other": "hello"}));
@@ -924,7 +1180,7 @@ mod tests {
 
         assert_eq!(
             result,
-            Err(DecodeError::Other("Unknown __typename: Other".to_string()))
+            Err(DecodeError::Other("Unknown __typename: Other".to_string()).into())
         )
     }
 
@@ -932,7 +1188,7 @@ mod tests {
     fn inline_fragments_decoding_with_backup() {
         let selection_set = inline_fragment_query(Some(field("other", vec![], string())));
 
-        let (_, _, decoder) = selection_set.query_arguments_and_decoder();
+        let (_, _, decoder) = selection_set.query_variables_and_decoder(false);
 
         let result =
             decoder.decode(&json!({"__typename": "Bot", "login": "Dependabot", "other": "hello"}));
@@ -948,10 +1204,78 @@ mod tests {
     fn test_decode_field_alias() {
         let selection_set = field_alias::<_, (), _>("field", "alias", vec![], boolean());
 
-        let (_, _, decoder) = selection_set.query_arguments_and_decoder();
+        let (_, _, decoder) = selection_set.query_variables_and_decoder(false);
 
         let result = decoder.decode(&json!({"alias": true}));
 
         assert_eq!(result, Ok(true));
     }
+
+    #[test]
+    fn test_skip_builds_correct_query() {
+        let selection_set = map2::<_, _, _, _, ()>(
+            |one, two| (one, two),
+            skip(true, field("one", vec![], string())),
+            include(false, field_alias("two", "alias", vec![], string())),
+        );
+
+        let (query, args, _) = selection_set.query_variables_and_decoder(false);
+
+        insta::assert_snapshot!(query, @r###"
+        one @skip(if: $skipIf)
+        alias: two @include(if: $includeIf)
+        "###);
+        assert_eq!(
+            args.iter()
+                .map(|a| a.argument.serialize_result.as_ref().unwrap().clone())
+                .collect::<Vec<_>>(),
+            vec![json!(true), json!(false)]
+        );
+    }
+
+    #[test]
+    fn test_decode_skipped_fields() {
+        let selection_set = skip::<_, ()>(false, field_alias("field", "alias", vec![], boolean()));
+
+        let (_, _, decoder) = selection_set.query_variables_and_decoder(false);
+
+        assert_eq!(decoder.decode(&json!({"alias": true})), Ok(Some(true)));
+        assert_eq!(decoder.decode(&json!({})), Ok(None));
+        assert_matches!(decoder.decode(&json!({"alias": null})), Err(_));
+    }
+
+    #[test]
+    fn test_decode_error_paths() {
+        let selection_set = field::<_, (), ()>(
+            "users",
+            vec![],
+            vec(field::<_, (), ()>(
+                "pet",
+                vec![],
+                option(field::<_, (), String>("name", vec![], string())),
+            )),
+        );
+
+        let result = selection_set.decode(&json!({
+            "users": [{"pet": null}, {"pet": {"name": 1}}]
+        }));
+
+        let error = result.unwrap_err();
+        assert_eq!(error.path_string(), "users[1].pet.name");
+        assert_eq!(error.rust_type(), Some("alloc::string::String"));
+        assert_eq!(error.graphql_type(), Some("String"));
+    }
+
+    #[test]
+    fn test_decode_error_missing_typename() {
+        let selection_set = field::<_, (), ()>("node", vec![], inline_fragment_query(None));
+
+        let error = selection_set.decode(&json!({"node": {}})).unwrap_err();
+
+        assert_eq!(error.path_string(), "node.__typename");
+        assert_eq!(
+            error.decode_error(),
+            &DecodeError::MissingField("__typename".into(), "Object".into())
+        );
+    }
 }
@@ -0,0 +1,448 @@
+//! Prints GraphQL documents.
+//!
+//! `Field`s are first converted into a `Document`, which has all of its
+//! variables & fragments resolved.  A `Document` can then be printed in
+//! either a pretty or compact style.
+
+use std::fmt::Write;
+
+/// The style a `Document` is printed in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Style {
+    /// Human readable output, with each selection on its own line and nested
+    /// selections indented by two spaces.
+    Pretty,
+    /// Minified output.  All insignificant whitespace & commas are removed,
+    /// with sibling items separated by a single space.
+    Compact,
+}
+
+/// A GraphQL document that's ready to be printed.
+#[derive(Clone, Debug)]
+pub struct Document {
+    pub definitions: Vec<Definition>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Definition {
+    Operation(OperationDefinition),
+    Fragment(FragmentDefinition),
+    /// Selections that aren't part of an operation.  These are printed
+    /// without any surrounding braces.
+    Selections(Vec<Selection>),
+}
+
+#[derive(Clone, Debug)]
+pub struct OperationDefinition {
+    /// The keyword for this operation, e.g. `query`.
+    pub keyword: &'static str,
+    pub name: String,
+    /// The name & type of each variable.
+    pub variables: Vec<(String, String)>,
+    pub selections: Vec<Selection>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FragmentDefinition {
+    pub name: String,
+    pub type_condition: String,
+    pub selections: Vec<Selection>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Selection {
+    Field {
+        alias: Option<String>,
+        name: String,
+        arguments: Vec<(String, Value)>,
+        directives: Vec<Directive>,
+        /// The selections of a composite field.  This is `None` for leaf
+        /// fields.
+        selections: Option<Vec<Selection>>,
+    },
+    InlineFragment {
+        type_condition: Option<String>,
+        directives: Vec<Directive>,
+        selections: Vec<Selection>,
+    },
+    FragmentSpread {
+        name: String,
+        directives: Vec<Directive>,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct Directive {
+    pub name: String,
+    pub arguments: Vec<(String, Value)>,
+}
+
+/// The value of an argument.
+#[derive(Clone, Debug)]
+pub enum Value {
+    Variable(String),
+    Literal(serde_json::Value),
+}
+
+impl Document {
+    pub fn print(&self, style: Style) -> String {
+        let mut printer = Printer::new(style);
+        for (i, definition) in self.definitions.iter().enumerate() {
+            if i > 0 && style == Style::Pretty {
+                printer.output.push('\n');
+            }
+            printer.definition(definition);
+        }
+        printer.output
+    }
+
+    /// Adds a `__typename` field to every selection set of a composite
+    /// field that doesn't already select it.
+    pub fn add_typenames(&mut self) {
+        for definition in &mut self.definitions {
+            match definition {
+                Definition::Operation(OperationDefinition { selections, .. })
+                | Definition::Fragment(FragmentDefinition { selections, .. })
+                | Definition::Selections(selections) => add_typenames(selections),
+            }
+        }
+    }
+}
+
+fn add_typenames(selections: &mut Vec<Selection>) {
+    for selection in selections {
+        match selection {
+            Selection::Field {
+                selections: Some(selections),
+                ..
+            } => {
+                let has_typename = selections.iter().any(|selection| {
+                    matches!(
+                        selection,
+                        Selection::Field { alias: None, name, .. } if name == "__typename"
+                    )
+                });
+                if !has_typename {
+                    selections.insert(
+                        0,
+                        Selection::Field {
+                            alias: None,
+                            name: "__typename".into(),
+                            arguments: vec![],
+                            directives: vec![],
+                            selections: None,
+                        },
+                    );
+                }
+                add_typenames(selections);
+            }
+            Selection::InlineFragment { selections, .. } => add_typenames(selections),
+            Selection::Field { .. } | Selection::FragmentSpread { .. } => {}
+        }
+    }
+}
+
+struct Printer {
+    style: Style,
+    indent: usize,
+    output: String,
+}
+
+impl Printer {
+    fn new(style: Style) -> Self {
+        Printer {
+            style,
+            indent: 0,
+            output: String::new(),
+        }
+    }
+
+    fn definition(&mut self, definition: &Definition) {
+        match definition {
+            Definition::Operation(operation) => {
+                self.output.push_str(operation.keyword);
+                self.output.push(' ');
+                self.output.push_str(&operation.name);
+                if !operation.variables.is_empty() {
+                    let variables = operation
+                        .variables
+                        .iter()
+                        .map(|(name, type_)| format!("${}{}{}", name, self.colon(), type_))
+                        .collect::<Vec<_>>();
+                    write!(self.output, "({})", variables.join(self.comma())).unwrap();
+                }
+                self.selection_set(&operation.selections);
+                self.end_line();
+            }
+            Definition::Fragment(fragment) => {
+                write!(
+                    self.output,
+                    "fragment {} on {}",
+                    fragment.name, fragment.type_condition
+                )
+                .unwrap();
+                self.selection_set(&fragment.selections);
+                self.end_line();
+            }
+            Definition::Selections(selections) => self.selections(selections),
+        }
+    }
+
+    fn selection_set(&mut self, selections: &[Selection]) {
+        match self.style {
+            Style::Pretty => {
+                self.output.push_str(" {\n");
+                self.indent += 2;
+                self.selections(selections);
+                self.indent -= 2;
+                write!(self.output, "{:indent$}}}", "", indent = self.indent).unwrap();
+            }
+            Style::Compact => {
+                self.output.push('{');
+                self.selections(selections);
+                self.output.push('}');
+            }
+        }
+    }
+
+    fn selections(&mut self, selections: &[Selection]) {
+        for (i, selection) in selections.iter().enumerate() {
+            match self.style {
+                Style::Pretty => {
+                    write!(self.output, "{:indent$}", "", indent = self.indent).unwrap();
+                }
+                Style::Compact if i > 0 => self.output.push(' '),
+                Style::Compact => {}
+            }
+            self.selection(selection);
+            self.end_line();
+        }
+    }
+
+    fn selection(&mut self, selection: &Selection) {
+        match selection {
+            Selection::Field {
+                alias,
+                name,
+                arguments,
+                directives,
+                selections,
+            } => {
+                if let Some(alias) = alias {
+                    write!(self.output, "{}{}", alias, self.colon()).unwrap();
+                }
+                self.output.push_str(name);
+                self.arguments(arguments);
+                self.directives(directives);
+                if let Some(selections) = selections {
+                    self.selection_set(selections);
+                }
+            }
+            Selection::InlineFragment {
+                type_condition,
+                directives,
+                selections,
+            } => {
+                self.output.push_str("...");
+                if let Some(type_condition) = type_condition {
+                    match self.style {
+                        Style::Pretty => self.output.push(' '),
+                        Style::Compact => {}
+                    }
+                    write!(self.output, "on {}", type_condition).unwrap();
+                }
+                self.directives(directives);
+                self.selection_set(selections);
+            }
+            Selection::FragmentSpread { name, directives } => {
+                write!(self.output, "...{}", name).unwrap();
+                self.directives(directives);
+            }
+        }
+    }
+
+    fn arguments(&mut self, arguments: &[(String, Value)]) {
+        if arguments.is_empty() {
+            return;
+        }
+
+        let arguments = arguments
+            .iter()
+            .map(|(name, value)| format!("{}{}{}", name, self.colon(), self.value(value)))
+            .collect::<Vec<_>>();
+
+        write!(self.output, "({})", arguments.join(self.comma())).unwrap();
+    }
+
+    fn directives(&mut self, directives: &[Directive]) {
+        for directive in directives {
+            match self.style {
+                Style::Pretty => self.output.push(' '),
+                Style::Compact => {}
+            }
+            write!(self.output, "@{}", directive.name).unwrap();
+            self.arguments(&directive.arguments);
+        }
+    }
+
+    fn value(&self, value: &Value) -> String {
+        match value {
+            Value::Variable(name) => format!("${}", name),
+            Value::Literal(literal) => self.literal(literal),
+        }
+    }
+
+    /// Converts a JSON value into a GraphQL literal.
+    fn literal(&self, value: &serde_json::Value) -> String {
+        use serde_json::Value;
+
+        match value {
+            Value::Array(values) => format!(
+                "[{}]",
+                values
+                    .iter()
+                    .map(|value| self.literal(value))
+                    .collect::<Vec<_>>()
+                    .join(self.comma())
+            ),
+            Value::Object(fields) => format!(
+                "{{{}}}",
+                fields
+                    .iter()
+                    .map(|(name, value)| format!("{}{}{}", name, self.colon(), self.literal(value)))
+                    .collect::<Vec<_>>()
+                    .join(self.comma())
+            ),
+            // JSON strings, numbers, booleans & null are all valid GraphQL literals.
+            other => other.to_string(),
+        }
+    }
+
+    fn end_line(&mut self) {
+        if self.style == Style::Pretty {
+            self.output.push('\n');
+        }
+    }
+
+    fn colon(&self) -> &'static str {
+        match self.style {
+            Style::Pretty => ": ",
+            Style::Compact => ":",
+        }
+    }
+
+    fn comma(&self) -> &'static str {
+        match self.style {
+            Style::Pretty => ", ",
+            Style::Compact => " ",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document() -> Document {
+        let film = |alias: &str, id: Value| Selection::Field {
+            alias: Some(alias.into()),
+            name: "film".into(),
+            arguments: vec![("id".into(), id)],
+            directives: vec![],
+            selections: Some(vec![
+                Selection::Field {
+                    alias: None,
+                    name: "title".into(),
+                    arguments: vec![],
+                    directives: vec![Directive {
+                        name: "include".into(),
+                        arguments: vec![("if".into(), Value::Variable("includeIf".into()))],
+                    }],
+                    selections: None,
+                },
+                Selection::FragmentSpread {
+                    name: "FilmDetails".into(),
+                    directives: vec![],
+                },
+            ]),
+        };
+
+        Document {
+            definitions: vec![
+                Definition::Operation(OperationDefinition {
+                    keyword: "query",
+                    name: "Films".into(),
+                    variables: vec![
+                        ("id".into(), "ID".into()),
+                        ("includeIf".into(), "Boolean!".into()),
+                    ],
+                    selections: vec![
+                        film("one", Value::Variable("id".into())),
+                        film("two", Value::Literal(json!({"a": [1, "b"]}))),
+                    ],
+                }),
+                Definition::Fragment(FragmentDefinition {
+                    name: "FilmDetails".into(),
+                    type_condition: "Film".into(),
+                    selections: vec![
+                        Selection::Field {
+                            alias: None,
+                            name: "director".into(),
+                            arguments: vec![],
+                            directives: vec![],
+                            selections: None,
+                        },
+                        Selection::InlineFragment {
+                            type_condition: Some("Film".into()),
+                            directives: vec![],
+                            selections: vec![Selection::Field {
+                                alias: None,
+                                name: "producers".into(),
+                                arguments: vec![],
+                                directives: vec![],
+                                selections: None,
+                            }],
+                        },
+                    ],
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_pretty_printing() {
+        insta::assert_snapshot!(document().print(Style::Pretty), @r###"
+        query Films($id: ID, $includeIf: Boolean!) {
+          one: film(id: $id) {
+            title @include(if: $includeIf)
+            ...FilmDetails
+          }
+          two: film(id: {a: [1, "b"]}) {
+            title @include(if: $includeIf)
+            ...FilmDetails
+          }
+        }
+
+        fragment FilmDetails on Film {
+          director
+          ... on Film {
+            producers
+          }
+        }
+        "###);
+    }
+
+    #[test]
+    fn test_compact_printing() {
+        assert_eq!(
+            document().print(Style::Compact),
+            concat!(
+                r#"query Films($id:ID $includeIf:Boolean!)"#,
+                r#"{one:film(id:$id){title@include(if:$includeIf) ...FilmDetails} "#,
+                r#"two:film(id:{a:[1 "b"]}){title@include(if:$includeIf) ...FilmDetails}}"#,
+                r#"fragment FilmDetails on Film{director ...on Film{producers}}"#
+            )
+        );
+    }
+}
@@ -0,0 +1,598 @@
+//! Generates mock responses to operations, for use in tests.
+//!
+//! Writing out the JSON for each response a test needs is tedious, and it's
+//! easy for it to drift out of sync with the QueryFragments it's decoded
+//! into.  A `ResponseGenerator` walks the query of an `Operation` alongside
+//! its schema, and generates a response of the right shape & types.  This
+//! includes using any aliases in the query, only going as deep as any
+//! recursive fields were queried and providing a `__typename` that matches
+//! one of the variants of any `InlineFragments`.
+//!
+//! This module is only available with the `testing` feature.
+//!
+//! ```rust
+//! # mod schema {
+//! #   cynic::use_schema!("../schemas/starwars.schema.graphql");
+//! # }
+//! #[derive(cynic::QueryFragment, Debug)]
+//! #[cynic(schema_path = "../schemas/starwars.schema.graphql", graphql_type = "Root")]
+//! struct AllFilmsQuery {
+//!     all_films: Option<FilmsConnection>,
+//! }
+//!
+//! #[derive(cynic::QueryFragment, Debug)]
+//! #[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+//! struct FilmsConnection {
+//!     films: Option<Vec<Option<Film>>>,
+//! }
+//!
+//! #[derive(cynic::QueryFragment, Debug)]
+//! #[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+//! struct Film {
+//!     title: Option<String>,
+//! }
+//!
+//! use cynic::{testing::ResponseGenerator, QueryBuilder};
+//!
+//! let generator =
+//!     ResponseGenerator::new(include_str!("../../schemas/starwars.schema.graphql")).unwrap();
+//!
+//! let operation = AllFilmsQuery::build(());
+//! let response = generator.generate(&operation).unwrap();
+//!
+//! let films = operation.decode_response(response).unwrap().unwrap();
+//! assert_eq!(films.all_films.unwrap().films.unwrap().len(), 1);
+//! ```
+//!
+//! By default every nullable field is present, every list has a single item
+//! and scalars get simple defaults like `0` or `""`.  `with_random_values`
+//! switches to random values instead, which also leaves out some nullable
+//! fields & varies the length of lists.
+
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::{
+    selection_set::printer::{Definition, Directive, FragmentDefinition, Selection, Value},
+    Argument, GraphQlResponse, Operation,
+};
+
+/// Generates mock responses to `Operation`s from a schema.
+///
+/// See the [module documentation](index.html) for more details.
+pub struct ResponseGenerator {
+    schema: Schema,
+    scalars: HashMap<String, serde_json::Value>,
+    seed: Option<u64>,
+}
+
+/// An error that occurred while generating a mock response.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum GeneratorError {
+    #[error("Could not parse schema: {0}")]
+    SchemaParse(String),
+    #[error("Could not find the output type {0} in the schema")]
+    UnknownType(String),
+    #[error("Could not find the field {1} on the type {0}")]
+    UnknownField(String, String),
+    #[error("Could not find the fragment {0} in the query")]
+    UnknownFragment(String),
+    #[error("The type {0} has no possible types")]
+    NoPossibleTypes(String),
+    #[error("The enum {0} has no values")]
+    NoEnumValues(String),
+}
+
+impl ResponseGenerator {
+    /// Creates a ResponseGenerator from the SDL of a schema.
+    pub fn new(schema: &str) -> Result<Self, GeneratorError> {
+        Ok(ResponseGenerator {
+            schema: Schema::parse(schema)?,
+            scalars: HashMap::new(),
+            seed: None,
+        })
+    }
+
+    /// Uses `value` for every instance of the scalar `name`.
+    ///
+    /// Any custom scalars that don't have a value default to a string, which
+    /// won't decode into every type.
+    pub fn with_scalar(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.scalars.insert(name.into(), value);
+        self
+    }
+
+    /// Switches to generating random responses, from the given seed.
+    ///
+    /// The same seed always generates the same response to an operation.
+    pub fn with_random_values(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Generates a response to `operation`.
+    pub fn generate<'a, ResponseData: 'a, ErrorExtensions>(
+        &self,
+        operation: &Operation<'a, ResponseData, ErrorExtensions>,
+    ) -> Result<GraphQlResponse<serde_json::Value, ErrorExtensions>, GeneratorError> {
+        let document = operation.document();
+
+        let mut generation = Generation {
+            schema: &self.schema,
+            scalars: &self.scalars,
+            variables: &operation.variables,
+            fragments: document
+                .definitions
+                .iter()
+                .filter_map(|definition| match definition {
+                    Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+                    _ => None,
+                })
+                .collect(),
+            rng: self.seed.map(Rng),
+        };
+
+        let mut data = None;
+        for definition in &document.definitions {
+            if let Definition::Operation(operation) = definition {
+                let root_type = match operation.keyword {
+                    "mutation" => &self.schema.mutation_type,
+                    "subscription" => &self.schema.subscription_type,
+                    _ => &self.schema.query_type,
+                };
+                let selections = operation.selections.iter().collect::<Vec<_>>();
+                data = Some(generation.object(root_type, &selections)?);
+            }
+        }
+
+        Ok(GraphQlResponse { data, errors: None })
+    }
+}
+
+/// The state of a single call to `ResponseGenerator::generate`.
+struct Generation<'a> {
+    schema: &'a Schema,
+    scalars: &'a HashMap<String, serde_json::Value>,
+    variables: &'a HashMap<String, Argument>,
+    fragments: HashMap<&'a str, &'a FragmentDefinition>,
+    rng: Option<Rng>,
+}
+
+/// The selections of a field, merged with those of any other fields in the
+/// same selection set that have the same response key.
+struct CollectedField<'a> {
+    response_key: &'a str,
+    name: &'a str,
+    selections: Vec<&'a Selection>,
+}
+
+impl<'a> Generation<'a> {
+    /// Generates an object of the concrete type `type_name`.
+    fn object(
+        &mut self,
+        type_name: &str,
+        selections: &[&'a Selection],
+    ) -> Result<serde_json::Value, GeneratorError> {
+        let mut fields = vec![];
+        self.collect_fields(type_name, selections, &mut fields)?;
+
+        let mut output = serde_json::Map::new();
+        for field in fields {
+            let value = if field.name == "__typename" {
+                json!(type_name)
+            } else {
+                let schema = self.schema;
+                let field_type = schema.field_type(type_name, field.name)?;
+                self.value(field_type, &field.selections)?
+            };
+            output.insert(field.response_key.to_string(), value);
+        }
+
+        Ok(serde_json::Value::Object(output))
+    }
+
+    /// Collects the fields that an object of type `type_name` would have for
+    /// `selections`, skipping any fragments that don't apply to it.
+    fn collect_fields(
+        &self,
+        type_name: &str,
+        selections: &[&'a Selection],
+        fields: &mut Vec<CollectedField<'a>>,
+    ) -> Result<(), GeneratorError> {
+        for &selection in selections {
+            match selection {
+                Selection::Field {
+                    alias,
+                    name,
+                    directives,
+                    selections,
+                    ..
+                } => {
+                    if self.is_skipped(directives) {
+                        continue;
+                    }
+
+                    let response_key = alias.as_deref().unwrap_or(name);
+                    let subselections = selections.iter().flatten();
+                    match fields.iter_mut().find(|f| f.response_key == response_key) {
+                        Some(existing) => existing.selections.extend(subselections),
+                        None => fields.push(CollectedField {
+                            response_key,
+                            name,
+                            selections: subselections.collect(),
+                        }),
+                    }
+                }
+                Selection::InlineFragment {
+                    type_condition,
+                    directives,
+                    selections,
+                } => {
+                    let applies = match type_condition {
+                        Some(condition) => self.schema.type_matches(condition, type_name),
+                        None => true,
+                    };
+                    if applies && !self.is_skipped(directives) {
+                        let selections = selections.iter().collect::<Vec<_>>();
+                        self.collect_fields(type_name, &selections, fields)?;
+                    }
+                }
+                Selection::FragmentSpread { name, directives } => {
+                    let fragment = self.fragment(name)?;
+                    if self
+                        .schema
+                        .type_matches(&fragment.type_condition, type_name)
+                        && !self.is_skipped(directives)
+                    {
+                        let selections = fragment.selections.iter().collect::<Vec<_>>();
+                        self.collect_fields(type_name, &selections, fields)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn value(
+        &mut self,
+        field_type: &OutputType,
+        selections: &[&'a Selection],
+    ) -> Result<serde_json::Value, GeneratorError> {
+        match field_type {
+            OutputType::NonNull(inner) => self.value(inner, selections),
+            _ if self.random_below(4) == Some(0) => Ok(serde_json::Value::Null),
+            OutputType::List(inner) => {
+                let length = self.random_below(4).unwrap_or(1);
+                (0..length)
+                    .map(|_| self.value(inner, selections))
+                    .collect::<Result<_, _>>()
+                    .map(serde_json::Value::Array)
+            }
+            OutputType::Named(type_name) => self.named_value(type_name, selections),
+        }
+    }
+
+    fn named_value(
+        &mut self,
+        type_name: &str,
+        selections: &[&'a Selection],
+    ) -> Result<serde_json::Value, GeneratorError> {
+        let schema = self.schema;
+        let type_definition = schema
+            .types
+            .get(type_name)
+            .ok_or_else(|| GeneratorError::UnknownType(type_name.to_string()))?;
+
+        match type_definition {
+            TypeDefinition::Scalar => Ok(self.scalar(type_name)),
+            TypeDefinition::Enum(values) => {
+                if values.is_empty() {
+                    return Err(GeneratorError::NoEnumValues(type_name.to_string()));
+                }
+                let index = self.random_below(values.len() as u64).unwrap_or(0);
+                Ok(json!(values[index]))
+            }
+            TypeDefinition::Object { .. } => self.object(type_name, selections),
+            TypeDefinition::Interface(_) | TypeDefinition::Union(_) => {
+                let concrete_type = self.concrete_type(type_name, selections)?;
+                self.object(concrete_type, selections)
+            }
+            TypeDefinition::InputObject => Err(GeneratorError::UnknownType(type_name.to_string())),
+        }
+    }
+
+    /// Picks the concrete type to generate for the abstract type
+    /// `type_name`.
+    ///
+    /// If the selections have inline fragments on any of the possible types
+    /// then we pick one of those, so the response decodes into one of the
+    /// variants of an `InlineFragments` rather than its fallback.
+    fn concrete_type(
+        &mut self,
+        type_name: &str,
+        selections: &[&'a Selection],
+    ) -> Result<&'a str, GeneratorError> {
+        let schema = self.schema;
+        let possible_types = schema.possible_types(type_name);
+
+        let fragment_types = possible_types
+            .iter()
+            .copied()
+            .filter(|possible_type| {
+                selections.iter().any(|selection| match selection {
+                    Selection::InlineFragment {
+                        type_condition: Some(condition),
+                        ..
+                    } => condition == possible_type,
+                    _ => false,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let candidates = if fragment_types.is_empty() {
+            possible_types
+        } else {
+            fragment_types
+        };
+        if candidates.is_empty() {
+            return Err(GeneratorError::NoPossibleTypes(type_name.to_string()));
+        }
+
+        let index = self.random_below(candidates.len() as u64).unwrap_or(0);
+        Ok(candidates[index])
+    }
+
+    fn scalar(&mut self, type_name: &str) -> serde_json::Value {
+        if let Some(value) = self.scalars.get(type_name) {
+            return value.clone();
+        }
+
+        let rng = match &mut self.rng {
+            Some(rng) => rng,
+            None => {
+                return match type_name {
+                    "Int" => json!(0),
+                    "Float" => json!(0.0),
+                    "Boolean" => json!(false),
+                    _ => json!(""),
+                }
+            }
+        };
+
+        match type_name {
+            "Int" => json!(rng.below(1000)),
+            "Float" => json!(rng.below(100_000) as f64 / 100.0),
+            "Boolean" => json!(rng.below(2) == 1),
+            _ => json!(rng.string(8)),
+        }
+    }
+
+    fn fragment(&self, name: &str) -> Result<&'a FragmentDefinition, GeneratorError> {
+        self.fragments
+            .get(name)
+            .copied()
+            .ok_or_else(|| GeneratorError::UnknownFragment(name.to_string()))
+    }
+
+    /// Checks whether a `@skip` or `@include` directive means a selection
+    /// won't be in the response.
+    fn is_skipped(&self, directives: &[Directive]) -> bool {
+        directives.iter().any(|directive| {
+            let condition = directive
+                .arguments
+                .iter()
+                .find(|(name, _)| name == "if")
+                .and_then(|(_, value)| match value {
+                    Value::Literal(value) => value.as_bool(),
+                    Value::Variable(name) => self
+                        .variables
+                        .get(name)
+                        .and_then(|argument| argument.serialize_result.as_ref().ok())
+                        .and_then(serde_json::Value::as_bool),
+                });
+
+            matches!(
+                (directive.name.as_str(), condition),
+                ("skip", Some(true)) | ("include", Some(false))
+            )
+        })
+    }
+
+    /// A random number below `n` if we're generating random values.
+    fn random_below(&mut self, n: u64) -> Option<usize> {
+        self.rng.as_mut().map(|rng| rng.below(n) as usize)
+    }
+}
+
+/// A small splitmix64 random number generator, which is plenty for
+/// generating test data.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn string(&mut self, length: usize) -> String {
+        const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        (0..length)
+            .map(|_| CHARS[self.below(CHARS.len() as u64) as usize] as char)
+            .collect()
+    }
+}
+
+/// The parts of a schema we need to generate responses.
+struct Schema {
+    query_type: String,
+    mutation_type: String,
+    subscription_type: String,
+    types: HashMap<String, TypeDefinition>,
+    /// The names of the object types, in the order they're defined.
+    object_names: Vec<String>,
+}
+
+enum TypeDefinition {
+    Scalar,
+    Enum(Vec<String>),
+    Object {
+        fields: HashMap<String, OutputType>,
+        interfaces: Vec<String>,
+    },
+    Interface(HashMap<String, OutputType>),
+    Union(Vec<String>),
+    InputObject,
+}
+
+enum OutputType {
+    Named(String),
+    List(Box<OutputType>),
+    NonNull(Box<OutputType>),
+}
+
+impl Schema {
+    fn parse(sdl: &str) -> Result<Schema, GeneratorError> {
+        use graphql_parser::schema::{self as parser, Definition};
+
+        let document = parser::parse_schema::<String>(sdl)
+            .map_err(|error| GeneratorError::SchemaParse(error.to_string()))?;
+
+        let mut schema = Schema {
+            query_type: "Query".into(),
+            mutation_type: "Mutation".into(),
+            subscription_type: "Subscription".into(),
+            types: HashMap::new(),
+            object_names: vec![],
+        };
+        for name in &["Int", "Float", "String", "Boolean", "ID"] {
+            schema
+                .types
+                .insert(name.to_string(), TypeDefinition::Scalar);
+        }
+
+        for definition in document.definitions {
+            match definition {
+                Definition::SchemaDefinition(definition) => {
+                    if let Some(query) = definition.query {
+                        schema.query_type = query;
+                    }
+                    if let Some(mutation) = definition.mutation {
+                        schema.mutation_type = mutation;
+                    }
+                    if let Some(subscription) = definition.subscription {
+                        schema.subscription_type = subscription;
+                    }
+                }
+                Definition::TypeDefinition(definition) => {
+                    let (name, definition) = match definition {
+                        parser::TypeDefinition::Scalar(scalar) => {
+                            (scalar.name, TypeDefinition::Scalar)
+                        }
+                        parser::TypeDefinition::Enum(enum_) => (
+                            enum_.name,
+                            TypeDefinition::Enum(
+                                enum_.values.into_iter().map(|value| value.name).collect(),
+                            ),
+                        ),
+                        parser::TypeDefinition::Object(object) => {
+                            schema.object_names.push(object.name.clone());
+                            (
+                                object.name,
+                                TypeDefinition::Object {
+                                    fields: output_fields(object.fields),
+                                    interfaces: object.implements_interfaces,
+                                },
+                            )
+                        }
+                        parser::TypeDefinition::Interface(interface) => (
+                            interface.name,
+                            TypeDefinition::Interface(output_fields(interface.fields)),
+                        ),
+                        parser::TypeDefinition::Union(union_) => {
+                            (union_.name, TypeDefinition::Union(union_.types))
+                        }
+                        parser::TypeDefinition::InputObject(input_object) => {
+                            (input_object.name, TypeDefinition::InputObject)
+                        }
+                    };
+                    schema.types.insert(name, definition);
+                }
+                Definition::TypeExtension(_) | Definition::DirectiveDefinition(_) => {}
+            }
+        }
+
+        Ok(schema)
+    }
+
+    fn field_type(&self, type_name: &str, field_name: &str) -> Result<&OutputType, GeneratorError> {
+        let fields = match self.types.get(type_name) {
+            Some(TypeDefinition::Object { fields, .. })
+            | Some(TypeDefinition::Interface(fields)) => fields,
+            _ => return Err(GeneratorError::UnknownType(type_name.to_string())),
+        };
+
+        fields.get(field_name).ok_or_else(|| {
+            GeneratorError::UnknownField(type_name.to_string(), field_name.to_string())
+        })
+    }
+
+    /// The object types that an abstract type could be.
+    fn possible_types(&self, type_name: &str) -> Vec<&str> {
+        match self.types.get(type_name) {
+            Some(TypeDefinition::Union(members)) => members.iter().map(String::as_str).collect(),
+            Some(TypeDefinition::Interface(_)) => self
+                .object_names
+                .iter()
+                .map(String::as_str)
+                .filter(|object| self.type_matches(type_name, object))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Checks whether a fragment with `type_condition` applies to an object of
+    /// type `object_type`.
+    fn type_matches(&self, type_condition: &str, object_type: &str) -> bool {
+        if type_condition == object_type {
+            return true;
+        }
+
+        match self.types.get(type_condition) {
+            Some(TypeDefinition::Union(members)) => members.iter().any(|m| m == object_type),
+            Some(TypeDefinition::Interface(_)) => matches!(
+                self.types.get(object_type),
+                Some(TypeDefinition::Object { interfaces, .. })
+                    if interfaces.iter().any(|i| i == type_condition)
+            ),
+            _ => false,
+        }
+    }
+}
+
+fn output_fields(
+    fields: Vec<graphql_parser::schema::Field<'_, String>>,
+) -> HashMap<String, OutputType> {
+    fields
+        .into_iter()
+        .map(|field| (field.name, output_type(field.field_type)))
+        .collect()
+}
+
+fn output_type(field_type: graphql_parser::schema::Type<'_, String>) -> OutputType {
+    use graphql_parser::schema::Type;
+
+    match field_type {
+        Type::NamedType(name) => OutputType::Named(name),
+        Type::ListType(inner) => OutputType::List(Box::new(output_type(*inner))),
+        Type::NonNullType(inner) => OutputType::NonNull(Box::new(output_type(*inner))),
+    }
+}
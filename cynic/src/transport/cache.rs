@@ -0,0 +1,631 @@
+//! A normalised in-memory cache of GraphQL responses.
+//!
+//! `InMemoryCache` stores each object that has an `id` under a key made from
+//! its `__typename` & `id`, e.g. `Film:ZmlsbXM6MQ==`.  Every response that
+//! selects the same object updates the same entry, so a mutation that returns
+//! an updated object also updates any queries that selected it.  Objects
+//! without an `id` are stored inside their parent.
+//!
+//! A cache is added to a `Client` or `BlockingClient` with `with_cache`, after
+//! which the client stores the response of every operation it runs.  The
+//! `run_with_policy` function of a client can then answer queries from the
+//! cache, with a `CachePolicy` chosen for each request:
+//!
+//! ```rust,no_run
+//! # mod schema {
+//! #   cynic::use_schema!("../schemas/starwars.schema.graphql");
+//! # }
+//! #
+//! # #[derive(cynic::QueryFragment)]
+//! # #[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+//! # struct Film {
+//! #    title: Option<String>,
+//! # }
+//! #
+//! # #[derive(cynic::QueryFragment)]
+//! # #[cynic(schema_path = "../schemas/starwars.schema.graphql", graphql_type = "Root")]
+//! # struct FilmQuery {
+//! #     #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+//! #     film: Option<Film>,
+//! # }
+//! # #[cfg(feature = "reqwest-blocking")]
+//! # {
+//! use cynic::{
+//!     transport::{
+//!         cache::{CachePolicy, InMemoryCache},
+//!         BlockingClient, ReqwestBlockingTransport,
+//!     },
+//!     QueryBuilder,
+//! };
+//!
+//! let client = BlockingClient::new(ReqwestBlockingTransport::new(
+//!     reqwest::blocking::Client::new(),
+//!     "https://swapi-graphql.netlify.app/.netlify/functions/index",
+//! ))
+//! .with_cache(InMemoryCache::new());
+//!
+//! // The first request goes to the server...
+//! client.run(FilmQuery::build(())).unwrap();
+//!
+//! // ...but this one can be answered from the cache.
+//! let response = client
+//!     .run_with_policy(FilmQuery::build(()), CachePolicy::CacheFirst)
+//!     .next()
+//!     .unwrap();
+//! # }
+//! ```
+//!
+//! The cache needs the `__typename` of each object, so clients with a cache
+//! add it to the queries they send with `Operation::with_typenames`.  The
+//! cache can also be used directly alongside the `cynic::http` extension
+//! traits, as long as operations are built the same way.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    selection_set::printer::{
+        Definition, Directive, FragmentDefinition, Selection, Value as ArgumentValue,
+    },
+    Argument, GraphQlResponse, Operation,
+};
+
+/// The key of the record that the root fields of queries are stored in.
+const ROOT_QUERY: &str = "ROOT_QUERY";
+
+/// The key that marks a stored value as a reference to another record.
+const REF_KEY: &str = "__ref";
+
+/// Decides whether a `Client` answers a request from its cache or the server.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Returns the cached response if every field of the query is in the
+    /// cache, otherwise sends the request to the server.
+    #[default]
+    CacheFirst,
+    /// Always sends the request to the server.  The response is still
+    /// stored in the cache.
+    NetworkOnly,
+    /// Returns the cached response if every field of the query is in the
+    /// cache, and then sends the request to the server & returns its
+    /// response as well.
+    CacheAndNetwork,
+}
+
+/// A normalised cache of GraphQL responses.
+///
+/// Clones of an `InMemoryCache` share the same storage, so a clone can be
+/// kept to inspect or clear the cache of a client.
+#[derive(Clone, Default)]
+pub struct InMemoryCache {
+    records: Arc<Mutex<HashMap<String, Record>>>,
+}
+
+/// The fields of an object, keyed by their name & arguments.
+type Record = Map<String, Value>;
+
+impl InMemoryCache {
+    pub fn new() -> Self {
+        InMemoryCache::default()
+    }
+
+    /// Stores the `data` of a response to `operation`.
+    ///
+    /// The root fields of mutations & subscriptions aren't stored, but any
+    /// objects inside them are merged into the cache.
+    pub fn write<'a, ResponseData: 'a, ErrorExtensions>(
+        &self,
+        operation: &Operation<'a, ResponseData, ErrorExtensions>,
+        data: &Value,
+    ) {
+        let data = match data {
+            Value::Object(data) => data,
+            _ => return,
+        };
+
+        let mut records = self.records.lock().unwrap();
+        let mut writer = Writer {
+            context: Context::new(operation),
+            records: &mut records,
+        };
+
+        for definition in &operation.document().definitions {
+            if let Definition::Operation(definition) = definition {
+                let mut root = Record::new();
+                writer.selections(&definition.selections, data, &mut root);
+
+                if definition.keyword == "query" {
+                    merge_records(
+                        writer.records.entry(ROOT_QUERY.to_string()).or_default(),
+                        root,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Reads the `data` of a response to `operation` from the cache.
+    ///
+    /// This returns `None` unless every field that `operation` selects is in
+    /// the cache.
+    pub fn read<'a, ResponseData: 'a, ErrorExtensions>(
+        &self,
+        operation: &Operation<'a, ResponseData, ErrorExtensions>,
+    ) -> Option<Value> {
+        let records = self.records.lock().unwrap();
+        let reader = Reader {
+            context: Context::new(operation),
+            records: &records,
+        };
+
+        let definition = operation
+            .document()
+            .definitions
+            .iter()
+            .find_map(|definition| match definition {
+                Definition::Operation(definition) if definition.keyword == "query" => {
+                    Some(definition)
+                }
+                _ => None,
+            })?;
+
+        let root = records.get(ROOT_QUERY)?;
+        let mut data = Map::new();
+        reader.selections(&definition.selections, root, &mut data)?;
+
+        Some(Value::Object(data))
+    }
+
+    /// The stored fields of the object with the given `__typename` & `id`,
+    /// if it's in the cache.
+    ///
+    /// Fields are keyed by their name & any arguments, e.g. `title` or
+    /// `planetConnection({"first":5})`.
+    pub fn entity(&self, typename: &str, id: &str) -> Option<Value> {
+        self.records
+            .lock()
+            .unwrap()
+            .get(&entity_key(typename, id))
+            .cloned()
+            .map(Value::Object)
+    }
+
+    /// Removes the object with the given `__typename` & `id` from the cache.
+    ///
+    /// Any query that selects it will miss the cache until it's fetched
+    /// again.
+    pub fn evict(&self, typename: &str, id: &str) {
+        self.records
+            .lock()
+            .unwrap()
+            .remove(&entity_key(typename, id));
+    }
+
+    /// Removes everything from the cache.
+    pub fn clear(&self) {
+        self.records.lock().unwrap().clear();
+    }
+}
+
+impl std::fmt::Debug for InMemoryCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InMemoryCache")
+            .field("records", &self.records.lock().unwrap().len())
+            .finish()
+    }
+}
+
+/// Adds `__typename` to the query of `operation` if there's a cache to
+/// store its response in.
+pub(super) fn prepare_operation<'a, ResponseData: 'a, ErrorExtensions>(
+    cache: Option<&InMemoryCache>,
+    operation: Operation<'a, ResponseData, ErrorExtensions>,
+) -> Operation<'a, ResponseData, ErrorExtensions> {
+    match cache {
+        Some(_) => operation.with_typenames(),
+        None => operation,
+    }
+}
+
+/// Reads a response to `operation` from `cache`, if `policy` allows it.
+///
+/// Cached data that can't be decoded is treated the same as a cache miss.
+pub(super) fn read_response<'a, ResponseData: 'a, ErrorExtensions>(
+    cache: Option<&InMemoryCache>,
+    operation: &Operation<'a, ResponseData, ErrorExtensions>,
+    policy: CachePolicy,
+) -> Option<GraphQlResponse<ResponseData, ErrorExtensions>> {
+    if policy == CachePolicy::NetworkOnly {
+        return None;
+    }
+
+    let data = cache?.read(operation)?;
+    operation
+        .decode_graphql_response(GraphQlResponse {
+            data: Some(data),
+            errors: None,
+        })
+        .ok()
+}
+
+/// Whether a request should go to the server after checking the cache.
+pub(super) fn should_fetch(policy: CachePolicy, cache_hit: bool) -> bool {
+    !(policy == CachePolicy::CacheFirst && cache_hit)
+}
+
+/// Stores the data of a response in `cache`.
+///
+/// Responses with errors aren't stored, as any fields that failed would be
+/// cached as `null`.
+pub(super) fn write_response<'a, ResponseData: 'a, ErrorExtensions>(
+    cache: Option<&InMemoryCache>,
+    operation: &Operation<'a, ResponseData, ErrorExtensions>,
+    response: &GraphQlResponse<Value, ErrorExtensions>,
+) {
+    let has_errors = response
+        .errors
+        .as_ref()
+        .map(|errors| !errors.is_empty())
+        .unwrap_or(false);
+
+    if let (Some(cache), Some(data), false) = (cache, &response.data, has_errors) {
+        cache.write(operation, data);
+    }
+}
+
+fn entity_key(typename: &str, id: &str) -> String {
+    format!("{}:{}", typename, id)
+}
+
+/// The parts of an operation needed to read or write its selections.
+struct Context<'a> {
+    fragments: HashMap<&'a str, &'a FragmentDefinition>,
+    variables: &'a HashMap<String, Argument>,
+}
+
+impl<'a> Context<'a> {
+    fn new<'b, ResponseData: 'b, ErrorExtensions>(
+        operation: &'a Operation<'b, ResponseData, ErrorExtensions>,
+    ) -> Self {
+        Context {
+            fragments: operation
+                .document()
+                .definitions
+                .iter()
+                .filter_map(|definition| match definition {
+                    Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+                    _ => None,
+                })
+                .collect(),
+            variables: &operation.variables,
+        }
+    }
+
+    fn value(&self, value: &ArgumentValue) -> Value {
+        match value {
+            ArgumentValue::Literal(value) => value.clone(),
+            ArgumentValue::Variable(name) => self
+                .variables
+                .get(name)
+                .and_then(|argument| argument.serialize_result.as_ref().ok())
+                .cloned()
+                .unwrap_or(Value::Null),
+        }
+    }
+
+    /// The key a field is stored under: its name followed by its arguments,
+    /// if it has any.
+    fn storage_key(&self, name: &str, arguments: &[(String, ArgumentValue)]) -> String {
+        if arguments.is_empty() {
+            return name.to_string();
+        }
+
+        let arguments = arguments
+            .iter()
+            .map(|(name, value)| (name.as_str(), self.value(value)))
+            .collect::<BTreeMap<_, _>>();
+
+        format!(
+            "{}({})",
+            name,
+            serde_json::to_string(&arguments).unwrap_or_default()
+        )
+    }
+
+    /// Checks whether a `@skip` or `@include` directive means a selection
+    /// won't be in the response.
+    fn is_skipped(&self, directives: &[Directive]) -> bool {
+        directives.iter().any(|directive| {
+            let condition = directive
+                .arguments
+                .iter()
+                .find(|(name, _)| name == "if")
+                .and_then(|(_, value)| self.value(value).as_bool());
+
+            matches!(
+                (directive.name.as_str(), condition),
+                ("skip", Some(true)) | ("include", Some(false))
+            )
+        })
+    }
+
+    /// The selections of a fragment spread or inline fragment, along with
+    /// its type condition.
+    fn fragment_selections<'s>(
+        &self,
+        selection: &'s Selection,
+    ) -> Option<(Option<&'s str>, &'s [Selection])>
+    where
+        'a: 's,
+    {
+        match selection {
+            Selection::InlineFragment {
+                type_condition,
+                directives,
+                selections,
+            } if !self.is_skipped(directives) => Some((type_condition.as_deref(), selections)),
+            Selection::FragmentSpread { name, directives } if !self.is_skipped(directives) => {
+                self.fragments.get(name.as_str()).map(|fragment| {
+                    (
+                        Some(fragment.type_condition.as_str()),
+                        fragment.selections.as_slice(),
+                    )
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Finds the response key of the `id` field in `selections`, if it's
+    /// selected.
+    fn id_key<'s>(&self, selections: &'s [Selection]) -> Option<&'s str>
+    where
+        'a: 's,
+    {
+        selections.iter().find_map(|selection| match selection {
+            Selection::Field {
+                alias,
+                name,
+                arguments,
+                ..
+            } if name == "id" && arguments.is_empty() => Some(alias.as_deref().unwrap_or(name)),
+            Selection::Field { .. } => None,
+            _ => self
+                .fragment_selections(selection)
+                .and_then(|(_, selections)| self.id_key(selections)),
+        })
+    }
+}
+
+struct Writer<'a, 'r> {
+    context: Context<'a>,
+    records: &'r mut HashMap<String, Record>,
+}
+
+impl Writer<'_, '_> {
+    /// Writes the fields of `data` that `selections` selected into `record`.
+    fn selections(
+        &mut self,
+        selections: &[Selection],
+        data: &Map<String, Value>,
+        record: &mut Record,
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::Field {
+                    alias,
+                    name,
+                    arguments,
+                    directives,
+                    selections,
+                } => {
+                    if self.context.is_skipped(directives) {
+                        continue;
+                    }
+
+                    let response_key = alias.as_deref().unwrap_or(name);
+                    let value = match data.get(response_key) {
+                        Some(value) => value,
+                        None => continue,
+                    };
+
+                    let mut field = Record::new();
+                    field.insert(
+                        self.context.storage_key(name, arguments),
+                        self.value(selections.as_deref(), value),
+                    );
+                    merge_records(record, field);
+                }
+                _ => {
+                    // Fragments write whichever of their fields are in the
+                    // response - we can't tell which types an interface
+                    // or union condition matches without the schema.
+                    if let Some((_, selections)) = self.context.fragment_selections(selection) {
+                        self.selections(selections, data, record);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Converts the value of a field into the value to store.
+    ///
+    /// Objects with an `id` are stored in their own record & replaced with
+    /// a reference to it.
+    fn value(&mut self, selections: Option<&[Selection]>, value: &Value) -> Value {
+        let selections = match selections {
+            Some(selections) => selections,
+            None => return value.clone(),
+        };
+
+        match value {
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| self.value(Some(selections), item))
+                    .collect(),
+            ),
+            Value::Object(object) => {
+                let mut record = Record::new();
+                self.selections(selections, object, &mut record);
+
+                match self.object_key(selections, object) {
+                    Some(key) => {
+                        merge_records(self.records.entry(key.clone()).or_default(), record);
+                        reference(key)
+                    }
+                    None => Value::Object(record),
+                }
+            }
+            _ => value.clone(),
+        }
+    }
+
+    /// The key of the record an object should be normalised into, if it has
+    /// a `__typename` & an `id`.
+    fn object_key(&self, selections: &[Selection], object: &Map<String, Value>) -> Option<String> {
+        let typename = object.get("__typename")?.as_str()?;
+        let id = match object.get(self.context.id_key(selections)?)? {
+            Value::String(id) => id.clone(),
+            Value::Number(id) => id.to_string(),
+            _ => return None,
+        };
+
+        Some(entity_key(typename, &id))
+    }
+}
+
+struct Reader<'a, 'r> {
+    context: Context<'a>,
+    records: &'r HashMap<String, Record>,
+}
+
+impl Reader<'_, '_> {
+    /// Reads the fields that `selections` selects from `record` into
+    /// `output`.  Returns `None` if any of them are missing.
+    fn selections(
+        &self,
+        selections: &[Selection],
+        record: &Record,
+        output: &mut Map<String, Value>,
+    ) -> Option<()> {
+        let typename = record.get("__typename").and_then(Value::as_str);
+
+        for selection in selections {
+            match selection {
+                Selection::Field {
+                    alias,
+                    name,
+                    arguments,
+                    directives,
+                    selections,
+                } => {
+                    if self.context.is_skipped(directives) {
+                        continue;
+                    }
+
+                    let stored = record.get(&self.context.storage_key(name, arguments))?;
+                    let value = self.value(selections.as_deref(), stored)?;
+                    merge_value(output, alias.as_deref().unwrap_or(name), value);
+                }
+                _ => {
+                    let (type_condition, selections) =
+                        match self.context.fragment_selections(selection) {
+                            Some(fragment) => fragment,
+                            None => continue,
+                        };
+
+                    let mut fragment_output = Map::new();
+                    let complete = self
+                        .selections(selections, record, &mut fragment_output)
+                        .is_some();
+
+                    let must_match = match (type_condition, typename) {
+                        (Some(condition), Some(typename)) => condition == typename,
+                        _ => true,
+                    };
+
+                    // Fragments on other types (or on an interface or union
+                    // we can't check) are only included if they're complete.
+                    if complete {
+                        for (key, value) in fragment_output {
+                            merge_value(output, &key, value);
+                        }
+                    } else if must_match {
+                        return None;
+                    }
+                }
+            }
+        }
+
+        Some(())
+    }
+
+    fn value(&self, selections: Option<&[Selection]>, stored: &Value) -> Option<Value> {
+        let selections = match selections {
+            Some(selections) => selections,
+            None => return Some(stored.clone()),
+        };
+
+        match stored {
+            Value::Array(items) => items
+                .iter()
+                .map(|item| self.value(Some(selections), item))
+                .collect::<Option<Vec<_>>>()
+                .map(Value::Array),
+            Value::Object(object) => {
+                let record = match object.get(REF_KEY).and_then(Value::as_str) {
+                    Some(key) => self.records.get(key)?,
+                    None => object,
+                };
+
+                let mut output = Map::new();
+                self.selections(selections, record, &mut output)?;
+                Some(Value::Object(output))
+            }
+            _ => Some(stored.clone()),
+        }
+    }
+}
+
+fn reference(key: String) -> Value {
+    let mut reference = Map::new();
+    reference.insert(REF_KEY.to_string(), Value::String(key));
+    Value::Object(reference)
+}
+
+/// Merges the fields of `source` into `target`, merging any nested records
+/// rather than replacing them.
+fn merge_records(target: &mut Record, source: Record) {
+    for (key, value) in source {
+        match (target.get_mut(&key), value) {
+            (Some(Value::Object(existing)), Value::Object(value))
+                if !existing.contains_key(REF_KEY) && !value.contains_key(REF_KEY) =>
+            {
+                merge_records(existing, value)
+            }
+            (_, value) => {
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Adds a value to an output object, merging it with any existing object
+/// with the same key.
+fn merge_value(output: &mut Map<String, Value>, key: &str, value: Value) {
+    match (output.get_mut(key), value) {
+        (Some(Value::Object(existing)), Value::Object(value)) => {
+            for (key, value) in value {
+                merge_value(existing, &key, value);
+            }
+        }
+        (_, value) => {
+            output.insert(key.to_string(), value);
+        }
+    }
+}
@@ -0,0 +1,549 @@
+use futures_util::stream::Stream;
+use serde::de::DeserializeOwned;
+
+use super::{
+    cache::{self, CachePolicy, InMemoryCache},
+    middleware::{BlockingMiddleware, BlockingNext, Middleware, Next},
+    BlockingTransport, BoxFuture, Request, Transport, TransportError,
+};
+use crate::{
+    relay::{next_page_arguments, CursorArguments, PageInfo},
+    BatchOperation, GraphQlResponse, Operation, QueryBuilder, ResponseDecodeError,
+};
+
+/// An error that occurred while running an operation with a `Client`.
+#[derive(thiserror::Error, Debug)]
+pub enum ClientError {
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    #[error("Error serializing or deserializing GraphQL: {0}")]
+    Serde(#[from] serde_json::Error),
+    #[error("Error decoding GraphQL response: {0}")]
+    Decode(#[from] ResponseDecodeError),
+}
+
+/// A GraphQL client that sends operations over a `Transport`.
+pub struct Client<T> {
+    transport: T,
+    middleware: Vec<Box<dyn Middleware>>,
+    cache: Option<InMemoryCache>,
+}
+
+impl<T: Transport> Client<T> {
+    pub fn new(transport: T) -> Self {
+        Client {
+            transport,
+            middleware: vec![],
+            cache: None,
+        }
+    }
+
+    /// Adds some middleware to the client.  Middleware runs in the order it
+    /// was added.
+    pub fn with(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Box::new(middleware));
+        self
+    }
+
+    /// The transport this client sends requests with.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Adds a cache to the client.
+    ///
+    /// The response to every operation the client runs is stored in the
+    /// cache, and `run_with_policy` can answer queries from it.  See the
+    /// [`cache`](super::cache) module for details.
+    pub fn with_cache(mut self, cache: InMemoryCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// The cache of this client, if it has one.
+    pub fn cache(&self) -> Option<&InMemoryCache> {
+        self.cache.as_ref()
+    }
+
+    /// Sends a request through the middleware & transport.
+    pub fn send(
+        &self,
+        request: Request,
+    ) -> BoxFuture<'_, Result<serde_json::Value, TransportError>> {
+        Next::new(&self.transport, &self.middleware).run(request)
+    }
+
+    /// Runs an operation & decodes the response.
+    ///
+    /// If the operation uses automatic persisted queries and the server
+    /// doesn't recognise the query hash, this retries with the full query.
+    pub async fn run<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned>(
+        &self,
+        operation: Operation<'a, ResponseData, ErrorExtensions>,
+    ) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, ClientError> {
+        let operation = cache::prepare_operation(self.cache(), operation);
+
+        self.run_prepared(&operation).await
+    }
+
+    /// Runs an operation, answering it from the cache of this client
+    /// according to `policy`.
+    ///
+    /// This returns a stream of responses: `CacheAndNetwork` can return
+    /// both a cached response & a response from the server, the other
+    /// policies only ever return one.  Clients without a cache always send
+    /// the request to the server.
+    pub fn run_with_policy<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
+        &'a self,
+        operation: Operation<'a, ResponseData, ErrorExtensions>,
+        policy: CachePolicy,
+    ) -> impl Stream<Item = Result<GraphQlResponse<ResponseData, ErrorExtensions>, ClientError>> + 'a
+    {
+        use futures_util::StreamExt;
+
+        let operation = cache::prepare_operation(self.cache(), operation);
+        let cached = cache::read_response(self.cache(), &operation, policy);
+        let fetch = cache::should_fetch(policy, cached.is_some());
+
+        let network = async move {
+            if fetch {
+                Some(self.run_prepared(&operation).await)
+            } else {
+                None
+            }
+        };
+
+        futures_util::stream::iter(cached.map(Ok))
+            .chain(futures_util::stream::once(network).filter_map(futures_util::future::ready))
+    }
+
+    /// Runs a batch of operations in a single request & decodes each of the
+    /// responses.
+    pub async fn run_batch<'a, ResponseData: 'a>(
+        &self,
+        batch: BatchOperation<'a, ResponseData>,
+    ) -> Result<ResponseData, ClientError> {
+        let responses = self.send_body(&batch).await?;
+
+        Ok(batch.decode_response(responses)?)
+    }
+
+    /// Runs a query for each page of a Relay connection.
+    ///
+    /// The first page is fetched with `arguments`.  `page_info` should find
+    /// the `PageInfo` of the connection in each response: if it has a next
+    /// page the query is run again with the `after` argument set to its
+    /// `endCursor`.  The stream ends after the last page, or after any
+    /// error.  See the [`relay`](crate::relay) module for an example.
+    pub fn paginate<'a, Query, F>(
+        &'a self,
+        arguments: Query::Arguments,
+        page_info: F,
+    ) -> impl Stream<Item = Result<GraphQlResponse<Query::ResponseData>, ClientError>> + 'a
+    where
+        Query: QueryBuilder<'static>,
+        Query::Arguments: CursorArguments + 'a,
+        F: Fn(&Query::ResponseData) -> Option<&PageInfo> + 'a,
+    {
+        futures_util::stream::unfold(
+            (Some(arguments), page_info),
+            move |(arguments, page_info)| async move {
+                let arguments = arguments?;
+                let result = self.run(Query::build(&arguments)).await;
+                let next_arguments = match &result {
+                    Ok(response) => next_page_arguments(arguments, response, &page_info),
+                    Err(_) => None,
+                };
+
+                Some((result, (next_arguments, page_info)))
+            },
+        )
+    }
+
+    async fn run_prepared<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned>(
+        &self,
+        operation: &Operation<'a, ResponseData, ErrorExtensions>,
+    ) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, ClientError> {
+        let mut response: serde_json::Value = self.send_body(&operation.body(false)).await?;
+
+        if operation.should_retry_with_query(&response) {
+            response = self.send_body(&operation.body(true)).await?;
+        }
+
+        let response = serde_json::from_value(response)?;
+        cache::write_response(self.cache(), operation, &response);
+
+        Ok(operation.decode_graphql_response(response)?)
+    }
+
+    async fn send_body<Response: DeserializeOwned>(
+        &self,
+        body: &impl serde::Serialize,
+    ) -> Result<Response, ClientError> {
+        let response = self.send(Request::new(serde_json::to_value(body)?)).await?;
+
+        Ok(serde_json::from_value(response)?)
+    }
+}
+
+/// A GraphQL client that sends operations over a `BlockingTransport`.
+pub struct BlockingClient<T> {
+    transport: T,
+    middleware: Vec<Box<dyn BlockingMiddleware>>,
+    cache: Option<InMemoryCache>,
+}
+
+impl<T: BlockingTransport> BlockingClient<T> {
+    pub fn new(transport: T) -> Self {
+        BlockingClient {
+            transport,
+            middleware: vec![],
+            cache: None,
+        }
+    }
+
+    /// Adds some middleware to the client.  Middleware runs in the order it
+    /// was added.
+    pub fn with(mut self, middleware: impl BlockingMiddleware + 'static) -> Self {
+        self.middleware.push(Box::new(middleware));
+        self
+    }
+
+    /// The transport this client sends requests with.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Adds a cache to the client.
+    ///
+    /// The response to every operation the client runs is stored in the
+    /// cache, and `run_with_policy` can answer queries from it.  See the
+    /// [`cache`](super::cache) module for details.
+    pub fn with_cache(mut self, cache: InMemoryCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// The cache of this client, if it has one.
+    pub fn cache(&self) -> Option<&InMemoryCache> {
+        self.cache.as_ref()
+    }
+
+    /// Sends a request through the middleware & transport.
+    pub fn send(&self, request: Request) -> Result<serde_json::Value, TransportError> {
+        BlockingNext::new(&self.transport, &self.middleware).run(request)
+    }
+
+    /// Runs an operation & decodes the response.
+    ///
+    /// If the operation uses automatic persisted queries and the server
+    /// doesn't recognise the query hash, this retries with the full query.
+    pub fn run<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned>(
+        &self,
+        operation: Operation<'a, ResponseData, ErrorExtensions>,
+    ) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, ClientError> {
+        let operation = cache::prepare_operation(self.cache(), operation);
+
+        self.run_prepared(&operation)
+    }
+
+    /// Runs an operation, answering it from the cache of this client
+    /// according to `policy`.
+    ///
+    /// This returns an iterator of responses: `CacheAndNetwork` can return
+    /// both a cached response & a response from the server, the other
+    /// policies only ever return one.  The request is only sent to the
+    /// server when the iterator reaches it.  Clients without a cache always
+    /// send the request to the server.
+    pub fn run_with_policy<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned + 'a>(
+        &'a self,
+        operation: Operation<'a, ResponseData, ErrorExtensions>,
+        policy: CachePolicy,
+    ) -> impl Iterator<Item = Result<GraphQlResponse<ResponseData, ErrorExtensions>, ClientError>> + 'a
+    {
+        let operation = cache::prepare_operation(self.cache(), operation);
+        let cached = cache::read_response(self.cache(), &operation, policy);
+        let fetch = cache::should_fetch(policy, cached.is_some());
+
+        cached.map(Ok).into_iter().chain(
+            std::iter::once_with(move || fetch.then(|| self.run_prepared(&operation))).flatten(),
+        )
+    }
+
+    /// Runs a batch of operations in a single request & decodes each of the
+    /// responses.
+    pub fn run_batch<'a, ResponseData: 'a>(
+        &self,
+        batch: BatchOperation<'a, ResponseData>,
+    ) -> Result<ResponseData, ClientError> {
+        let responses = self.send_body(&batch)?;
+
+        Ok(batch.decode_response(responses)?)
+    }
+
+    /// Runs a query for each page of a Relay connection.
+    ///
+    /// The first page is fetched with `arguments`.  `page_info` should find
+    /// the `PageInfo` of the connection in each response: if it has a next
+    /// page the query is run again with the `after` argument set to its
+    /// `endCursor`.  The iterator ends after the last page, or after any
+    /// error.  See the [`relay`](crate::relay) module for an example.
+    pub fn paginate<'a, Query, F>(
+        &'a self,
+        arguments: Query::Arguments,
+        page_info: F,
+    ) -> impl Iterator<Item = Result<GraphQlResponse<Query::ResponseData>, ClientError>> + 'a
+    where
+        Query: QueryBuilder<'static>,
+        Query::Arguments: CursorArguments + 'a,
+        F: Fn(&Query::ResponseData) -> Option<&PageInfo> + 'a,
+    {
+        let mut arguments = Some(arguments);
+
+        std::iter::from_fn(move || {
+            let current_arguments = arguments.take()?;
+            let result = self.run(Query::build(&current_arguments));
+            if let Ok(response) = &result {
+                arguments = next_page_arguments(current_arguments, response, &page_info);
+            }
+
+            Some(result)
+        })
+    }
+
+    fn run_prepared<'a, ResponseData: 'a, ErrorExtensions: DeserializeOwned>(
+        &self,
+        operation: &Operation<'a, ResponseData, ErrorExtensions>,
+    ) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, ClientError> {
+        let mut response: serde_json::Value = self.send_body(&operation.body(false))?;
+
+        if operation.should_retry_with_query(&response) {
+            response = self.send_body(&operation.body(true))?;
+        }
+
+        let response = serde_json::from_value(response)?;
+        cache::write_response(self.cache(), operation, &response);
+
+        Ok(operation.decode_graphql_response(response)?)
+    }
+
+    fn send_body<Response: DeserializeOwned>(
+        &self,
+        body: &impl serde::Serialize,
+    ) -> Result<Response, ClientError> {
+        let response = self.send(Request::new(serde_json::to_value(body)?))?;
+
+        Ok(serde_json::from_value(response)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::{middleware, MockTransport};
+    use crate::QueryBuilder;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    mod schema {
+        cynic::use_schema!("../schemas/starwars.schema.graphql");
+    }
+
+    #[derive(cynic::QueryFragment, Debug, PartialEq)]
+    #[cynic(
+        schema_path = "../schemas/starwars.schema.graphql",
+        query_module = "schema",
+        graphql_type = "Root"
+    )]
+    struct FilmQuery {
+        #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+        film: Option<Film>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug, PartialEq)]
+    #[cynic(
+        schema_path = "../schemas/starwars.schema.graphql",
+        query_module = "schema"
+    )]
+    struct Film {
+        title: Option<String>,
+    }
+
+    fn film_response() -> serde_json::Value {
+        json!({"data": {"film": {"title": "A New Hope"}}})
+    }
+
+    fn expected_data() -> Option<FilmQuery> {
+        Some(FilmQuery {
+            film: Some(Film {
+                title: Some("A New Hope".into()),
+            }),
+        })
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    /// Middleware that records the order it was called in.
+    struct Record(&'static str, Arc<Mutex<Vec<&'static str>>>);
+
+    impl Middleware for Record {
+        fn handle<'a>(
+            &'a self,
+            request: Request,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<serde_json::Value, TransportError>> {
+            self.1.lock().unwrap().push(self.0);
+            next.run(request)
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let client = Client::new(MockTransport::new().with_response(film_response()));
+
+        let response = block_on(client.run(FilmQuery::build(()))).unwrap();
+
+        assert_eq!(response.data, expected_data());
+
+        let requests = client.transport().requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].body.get("query").is_some());
+    }
+
+    #[test]
+    fn test_middleware_runs_in_order() {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let client = Client::new(MockTransport::new().with_response(film_response()))
+            .with(Record("first", Arc::clone(&calls)))
+            .with(Record("second", Arc::clone(&calls)));
+
+        block_on(client.run(FilmQuery::build(()))).unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn test_header_middleware() {
+        let client = Client::new(MockTransport::new().with_response(film_response()))
+            .with(middleware::Header::bearer_auth("a-token"));
+
+        block_on(client.run(FilmQuery::build(()))).unwrap();
+
+        assert_eq!(
+            client.transport().requests()[0].headers,
+            vec![("Authorization".to_string(), "Bearer a-token".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_timeout_middleware() {
+        let client = Client::new(MockTransport::new().with_response(film_response()))
+            .with(middleware::Timeout::new(std::time::Duration::from_secs(5)));
+
+        block_on(client.run(FilmQuery::build(()))).unwrap();
+
+        assert_eq!(
+            client.transport().requests()[0].timeout,
+            Some(std::time::Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn test_retry_middleware() {
+        let client = Client::new(
+            MockTransport::new()
+                .with_error(TransportError::Timeout)
+                .with_error(TransportError::Timeout)
+                .with_response(film_response()),
+        )
+        .with(middleware::Retry::new(2));
+
+        let response = block_on(client.run(FilmQuery::build(()))).unwrap();
+
+        assert_eq!(response.data, expected_data());
+        assert_eq!(client.transport().requests().len(), 3);
+    }
+
+    #[test]
+    fn test_retry_middleware_gives_up() {
+        let client = Client::new(
+            MockTransport::new()
+                .with_error(TransportError::Timeout)
+                .with_error(TransportError::Timeout)
+                .with_response(film_response()),
+        )
+        .with(middleware::Retry::new(1));
+
+        let result = block_on(client.run(FilmQuery::build(())));
+
+        assert!(matches!(
+            result,
+            Err(ClientError::Transport(TransportError::Timeout))
+        ));
+        assert_eq!(client.transport().requests().len(), 2);
+    }
+
+    #[test]
+    fn test_persisted_query_retry() {
+        let client = Client::new(
+            MockTransport::new()
+                .with_response(json!({"errors": [{"message": "PersistedQueryNotFound"}]}))
+                .with_response(film_response()),
+        );
+
+        let response =
+            block_on(client.run(FilmQuery::build(()).with_automatic_persisted_queries())).unwrap();
+
+        assert_eq!(response.data, expected_data());
+
+        let requests = client.transport().requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].body.get("query"), None);
+        assert!(requests[1].body.get("query").is_some());
+    }
+
+    #[test]
+    fn test_blocking_client() {
+        let client = BlockingClient::new(
+            MockTransport::new()
+                .with_error(TransportError::Timeout)
+                .with_response(film_response()),
+        )
+        .with(middleware::Retry::new(1))
+        .with(middleware::Header::new("X-Test", "yes"));
+
+        let response = client.run(FilmQuery::build(())).unwrap();
+
+        assert_eq!(response.data, expected_data());
+
+        let requests = client.transport().requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1].headers,
+            vec![("X-Test".to_string(), "yes".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_run_batch() {
+        let client = BlockingClient::new(
+            MockTransport::new().with_response(json!([film_response(), film_response()])),
+        );
+
+        let (first, second) = client
+            .run_batch(BatchOperation::from((
+                FilmQuery::build(()),
+                FilmQuery::build(()),
+            )))
+            .unwrap();
+
+        assert_eq!(first.data, expected_data());
+        assert_eq!(second.data, expected_data());
+        assert!(client.transport().requests()[0].body.is_array());
+    }
+}
@@ -0,0 +1,267 @@
+//! Middleware that can be added to a `Client` or `BlockingClient`.
+//!
+//! Middleware runs in the order it was added to a client, and each middleware
+//! decides whether (and how many times) to pass the request on to the next
+//! middleware in the chain, eventually reaching the transport.
+//!
+//! The middleware provided by cynic implements both `Middleware` &
+//! `BlockingMiddleware`, so it can be used with either kind of client.
+
+use std::time::Duration;
+
+use super::{timer, BlockingTransport, BoxFuture, Request, Transport, TransportError};
+
+type MiddlewareResult = Result<serde_json::Value, TransportError>;
+
+/// Middleware for a `Client`.
+pub trait Middleware: Send + Sync {
+    /// Handles a request, usually by calling `next.run`.
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, MiddlewareResult>;
+}
+
+/// Middleware for a `BlockingClient`.
+pub trait BlockingMiddleware: Send + Sync {
+    /// Handles a request, usually by calling `next.run`.
+    fn handle(&self, request: Request, next: BlockingNext<'_>) -> MiddlewareResult;
+}
+
+/// The remainder of a `Middleware` chain.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    transport: &'a dyn Transport,
+    middleware: &'a [Box<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(super) fn new(transport: &'a dyn Transport, middleware: &'a [Box<dyn Middleware>]) -> Self {
+        Next {
+            transport,
+            middleware,
+        }
+    }
+
+    /// Runs the rest of the chain for a request.
+    pub fn run(self, request: Request) -> BoxFuture<'a, MiddlewareResult> {
+        match self.middleware.split_first() {
+            Some((current, rest)) => current.handle(request, Next::new(self.transport, rest)),
+            None => self.transport.send(request),
+        }
+    }
+}
+
+/// The remainder of a `BlockingMiddleware` chain.
+#[derive(Clone, Copy)]
+pub struct BlockingNext<'a> {
+    transport: &'a dyn BlockingTransport,
+    middleware: &'a [Box<dyn BlockingMiddleware>],
+}
+
+impl<'a> BlockingNext<'a> {
+    pub(super) fn new(
+        transport: &'a dyn BlockingTransport,
+        middleware: &'a [Box<dyn BlockingMiddleware>],
+    ) -> Self {
+        BlockingNext {
+            transport,
+            middleware,
+        }
+    }
+
+    /// Runs the rest of the chain for a request.
+    pub fn run(self, request: Request) -> MiddlewareResult {
+        match self.middleware.split_first() {
+            Some((current, rest)) => {
+                current.handle(request, BlockingNext::new(self.transport, rest))
+            }
+            None => self.transport.send(request),
+        }
+    }
+}
+
+/// Adds a header to every request, e.g. for authentication.
+#[derive(Clone, Debug)]
+pub struct Header {
+    name: String,
+    value: String,
+}
+
+impl Header {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Header {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Sends `token` as a bearer token in the `Authorization` header.
+    pub fn bearer_auth(token: impl AsRef<str>) -> Self {
+        Header::new("Authorization", format!("Bearer {}", token.as_ref()))
+    }
+}
+
+impl Middleware for Header {
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, MiddlewareResult> {
+        next.run(request.header(&self.name, &self.value))
+    }
+}
+
+impl BlockingMiddleware for Header {
+    fn handle(&self, request: Request, next: BlockingNext<'_>) -> MiddlewareResult {
+        next.run(request.header(&self.name, &self.value))
+    }
+}
+
+/// Logs every request & response with the `log` crate.
+#[derive(Clone, Debug)]
+pub struct Logger {
+    level: log::Level,
+}
+
+impl Logger {
+    /// Creates a Logger that logs at the debug level.
+    pub fn new() -> Self {
+        Logger {
+            level: log::Level::Debug,
+        }
+    }
+
+    /// Sets the level to log at.
+    pub fn with_level(level: log::Level) -> Self {
+        Logger { level }
+    }
+
+    fn log_request(&self, request: &Request) {
+        log::log!(self.level, "Sending GraphQL request: {}", request.body);
+    }
+
+    fn log_result(&self, result: &MiddlewareResult) {
+        match result {
+            Ok(response) => log::log!(self.level, "Received GraphQL response: {}", response),
+            Err(error) => log::log!(self.level, "GraphQL request failed: {}", error),
+        }
+    }
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Logger::new()
+    }
+}
+
+impl Middleware for Logger {
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, MiddlewareResult> {
+        Box::pin(async move {
+            self.log_request(&request);
+            let result = next.run(request).await;
+            self.log_result(&result);
+            result
+        })
+    }
+}
+
+impl BlockingMiddleware for Logger {
+    fn handle(&self, request: Request, next: BlockingNext<'_>) -> MiddlewareResult {
+        self.log_request(&request);
+        let result = next.run(request);
+        self.log_result(&result);
+        result
+    }
+}
+
+/// Retries requests that fail with a `TransportError`.
+///
+/// Note that this doesn't retry requests that return GraphQL errors - only
+/// those where we didn't get a response from the server.
+#[derive(Clone, Debug)]
+pub struct Retry {
+    max_retries: u32,
+    initial_backoff: Duration,
+}
+
+impl Retry {
+    /// Creates a Retry that will retry up to `max_retries` times without
+    /// waiting in between.
+    pub fn new(max_retries: u32) -> Self {
+        Retry {
+            max_retries,
+            initial_backoff: Duration::from_secs(0),
+        }
+    }
+
+    /// Waits between retries, starting at `initial_backoff` and doubling
+    /// after each attempt.
+    pub fn with_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff * 2u32.saturating_pow(attempt)
+    }
+}
+
+impl Middleware for Retry {
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, MiddlewareResult> {
+        Box::pin(async move {
+            let mut attempt = 0;
+            loop {
+                match next.run(request.clone()).await {
+                    Err(_) if attempt < self.max_retries => {
+                        let backoff = self.backoff(attempt);
+                        if backoff > Duration::from_secs(0) {
+                            timer::delay(backoff).await;
+                        }
+                        attempt += 1;
+                    }
+                    result => return result,
+                }
+            }
+        })
+    }
+}
+
+impl BlockingMiddleware for Retry {
+    fn handle(&self, request: Request, next: BlockingNext<'_>) -> MiddlewareResult {
+        let mut attempt = 0;
+        loop {
+            match next.run(request.clone()) {
+                Err(_) if attempt < self.max_retries => {
+                    std::thread::sleep(self.backoff(attempt));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Sets a timeout on requests that don't already have one.
+///
+/// It's up to each transport to enforce the timeout.
+#[derive(Clone, Debug)]
+pub struct Timeout {
+    duration: Duration,
+}
+
+impl Timeout {
+    pub fn new(duration: Duration) -> Self {
+        Timeout { duration }
+    }
+
+    fn apply(&self, mut request: Request) -> Request {
+        request.timeout = request.timeout.or(Some(self.duration));
+        request
+    }
+}
+
+impl Middleware for Timeout {
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, MiddlewareResult> {
+        next.run(self.apply(request))
+    }
+}
+
+impl BlockingMiddleware for Timeout {
+    fn handle(&self, request: Request, next: BlockingNext<'_>) -> MiddlewareResult {
+        next.run(self.apply(request))
+    }
+}
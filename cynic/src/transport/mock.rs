@@ -0,0 +1,83 @@
+use std::{
+    collections::VecDeque,
+    sync::{Mutex, MutexGuard},
+};
+
+use super::{BlockingTransport, BoxFuture, Request, Transport, TransportError};
+
+/// A transport that returns canned responses, for testing code that uses a
+/// `Client` without a GraphQL server.
+///
+/// Responses are returned in the order they were added, and every request is
+/// recorded so tests can make assertions about it.
+///
+/// ```rust
+/// use cynic::transport::{BlockingClient, MockTransport};
+/// use serde_json::json;
+///
+/// let client = BlockingClient::new(
+///     MockTransport::new().with_response(json!({"data": {"__typename": "Root"}})),
+/// );
+/// ```
+#[derive(Default)]
+pub struct MockTransport {
+    responses: Mutex<VecDeque<Result<serde_json::Value, TransportError>>>,
+    requests: Mutex<Vec<Request>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        MockTransport::default()
+    }
+
+    /// Adds a response that should be returned by a later request.
+    pub fn with_response(self, response: serde_json::Value) -> Self {
+        self.push_response(Ok(response));
+        self
+    }
+
+    /// Adds an error that should be returned by a later request.
+    pub fn with_error(self, error: TransportError) -> Self {
+        self.push_response(Err(error));
+        self
+    }
+
+    /// Adds a response or error that should be returned by a later request.
+    pub fn push_response(&self, response: Result<serde_json::Value, TransportError>) {
+        lock(&self.responses).push_back(response);
+    }
+
+    /// The requests this transport has received so far.
+    pub fn requests(&self) -> Vec<Request> {
+        lock(&self.requests).clone()
+    }
+
+    fn respond(&self, request: Request) -> Result<serde_json::Value, TransportError> {
+        lock(&self.requests).push(request);
+
+        lock(&self.responses).pop_front().unwrap_or_else(|| {
+            Err(TransportError::Other(
+                "MockTransport received a request but has no responses left".into(),
+            ))
+        })
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<serde_json::Value, TransportError>> {
+        let response = self.respond(request);
+        Box::pin(async move { response })
+    }
+}
+
+impl BlockingTransport for MockTransport {
+    fn send(&self, request: Request) -> Result<serde_json::Value, TransportError> {
+        self.respond(request)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // A panic while holding one of these locks can't leave the data in a bad
+    // state, so we can safely ignore poisoning.
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
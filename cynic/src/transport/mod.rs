@@ -0,0 +1,138 @@
+//! Transport agnostic clients for running GraphQL operations.
+//!
+//! A `Transport` knows how to send a JSON request body to a GraphQL server &
+//! return the JSON it responds with.  `Client` (or `BlockingClient`) sits on
+//! top of a transport, and handles serializing operations, decoding responses
+//! & automatic persisted query retries.  A client can also be given some
+//! [`Middleware`](middleware::Middleware) that runs on every request:
+//!
+//! ```rust,no_run
+//! # mod schema {
+//! #   cynic::use_schema!("../schemas/starwars.schema.graphql");
+//! # }
+//! #
+//! # #[derive(cynic::QueryFragment)]
+//! # #[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+//! # struct Film {
+//! #    title: Option<String>,
+//! # }
+//! #
+//! # #[derive(cynic::QueryFragment)]
+//! # #[cynic(schema_path = "../schemas/starwars.schema.graphql", graphql_type = "Root")]
+//! # struct FilmQuery {
+//! #     #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+//! #     film: Option<Film>,
+//! # }
+//! # #[cfg(feature = "reqwest")]
+//! # async move {
+//! use cynic::{
+//!     transport::{middleware, Client, ReqwestTransport},
+//!     QueryBuilder,
+//! };
+//! use std::time::Duration;
+//!
+//! let client = Client::new(ReqwestTransport::new(
+//!     reqwest::Client::new(),
+//!     "https://swapi-graphql.netlify.app/.netlify/functions/index",
+//! ))
+//! .with(middleware::Header::bearer_auth("a-token"))
+//! .with(middleware::Retry::new(3).with_backoff(Duration::from_millis(100)))
+//! .with(middleware::Timeout::new(Duration::from_secs(10)));
+//!
+//! let response = client.run(FilmQuery::build(())).await.unwrap();
+//! # };
+//! ```
+//!
+//! Clients can also store responses in a normalised
+//! [`InMemoryCache`](cache::InMemoryCache) and answer queries from it.
+//!
+//! Cynic provides transports for reqwest & surf behind the respective
+//! feature flags, and a `MockTransport` for use in tests.
+
+mod client;
+mod mock;
+mod timer;
+
+pub mod cache;
+pub mod middleware;
+
+#[cfg(any(feature = "reqwest", feature = "reqwest-blocking"))]
+mod reqwest;
+#[cfg(feature = "surf")]
+mod surf;
+
+use std::{future::Future, pin::Pin, time::Duration};
+
+pub use client::{BlockingClient, Client, ClientError};
+pub use mock::MockTransport;
+
+#[cfg(feature = "reqwest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
+pub use self::reqwest::ReqwestTransport;
+
+#[cfg(feature = "reqwest-blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "reqwest-blocking")))]
+pub use self::reqwest::ReqwestBlockingTransport;
+
+#[cfg(feature = "surf")]
+#[cfg_attr(docsrs, doc(cfg(feature = "surf")))]
+pub use self::surf::SurfTransport;
+
+#[cfg(any(feature = "reqwest", feature = "reqwest-blocking"))]
+pub(crate) use self::reqwest::RequestBuilderTransport;
+
+#[cfg(feature = "surf")]
+pub(crate) use self::surf::{into_surf_error, SurfRequestBuilderTransport};
+
+/// A boxed future, as returned by `Transport` & `Middleware`.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A request that should be sent to a GraphQL server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Request {
+    /// The JSON body of the request.
+    pub body: serde_json::Value,
+    /// Any headers that should be sent with the request.
+    pub headers: Vec<(String, String)>,
+    /// How long to wait for a response before giving up.
+    pub timeout: Option<Duration>,
+}
+
+impl Request {
+    pub fn new(body: serde_json::Value) -> Self {
+        Request {
+            body,
+            headers: vec![],
+            timeout: None,
+        }
+    }
+
+    /// Adds a header to the request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// An error that occurred while sending a request.
+#[derive(thiserror::Error, Debug)]
+pub enum TransportError {
+    #[error("Error sending request: {0}")]
+    Transport(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("The request timed out")]
+    Timeout,
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Sends requests to a GraphQL server.
+pub trait Transport: Send + Sync {
+    /// Sends a request, returning the JSON the server responded with.
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<serde_json::Value, TransportError>>;
+}
+
+/// Sends requests to a GraphQL server, blocking until it responds.
+pub trait BlockingTransport: Send + Sync {
+    /// Sends a request, returning the JSON the server responded with.
+    fn send(&self, request: Request) -> Result<serde_json::Value, TransportError>;
+}
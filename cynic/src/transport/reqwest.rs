@@ -0,0 +1,174 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use super::{Request, TransportError};
+
+#[cfg(feature = "reqwest-blocking")]
+use super::BlockingTransport;
+#[cfg(feature = "reqwest")]
+use super::{BoxFuture, Transport};
+
+/// Converts a reqwest::Error into a TransportError.
+///
+/// Only timeouts set on the `Request` (e.g. by the `Timeout` middleware) are
+/// reported as `TransportError::Timeout`.  Any other errors - including
+/// timeouts the reqwest client was configured with - keep the reqwest::Error.
+fn transport_error(
+    error: reqwest::Error,
+    timeout: Option<Duration>,
+    started: Instant,
+) -> TransportError {
+    match timeout {
+        Some(timeout) if error.is_timeout() && started.elapsed() >= timeout => {
+            TransportError::Timeout
+        }
+        _ => TransportError::Transport(Box::new(error)),
+    }
+}
+
+/// A `Transport` that sends requests with a `reqwest::Client`.
+#[cfg(feature = "reqwest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+    url: String,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestTransport {
+    /// Creates a transport that POSTs requests to `url` with `client`.
+    pub fn new(client: reqwest::Client, url: impl Into<String>) -> Self {
+        ReqwestTransport {
+            client,
+            url: url.into(),
+        }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl Transport for ReqwestTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<serde_json::Value, TransportError>> {
+        send_async(self.client.post(&self.url), request)
+    }
+}
+
+/// A `BlockingTransport` that sends requests with a
+/// `reqwest::blocking::Client`.
+#[cfg(feature = "reqwest-blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "reqwest-blocking")))]
+pub struct ReqwestBlockingTransport {
+    client: reqwest::blocking::Client,
+    url: String,
+}
+
+#[cfg(feature = "reqwest-blocking")]
+impl ReqwestBlockingTransport {
+    /// Creates a transport that POSTs requests to `url` with `client`.
+    pub fn new(client: reqwest::blocking::Client, url: impl Into<String>) -> Self {
+        ReqwestBlockingTransport {
+            client,
+            url: url.into(),
+        }
+    }
+}
+
+#[cfg(feature = "reqwest-blocking")]
+impl BlockingTransport for ReqwestBlockingTransport {
+    fn send(&self, request: Request) -> Result<serde_json::Value, TransportError> {
+        send_blocking(self.client.post(&self.url), request)
+    }
+}
+
+/// A transport that sends each request with a clone of a RequestBuilder.
+///
+/// This lets the extension traits in `cynic::http` use a `Client` with the
+/// RequestBuilder they're called on.
+pub(crate) struct RequestBuilderTransport<Builder>(Mutex<Option<Builder>>);
+
+impl<Builder> RequestBuilderTransport<Builder> {
+    pub(crate) fn new(builder: Builder) -> Self {
+        RequestBuilderTransport(Mutex::new(Some(builder)))
+    }
+
+    /// Gets a builder for the next request.  If the builder can't be cloned
+    /// then the original is used, but only for a single request.
+    fn builder(&self, try_clone: impl Fn(&Builder) -> Option<Builder>) -> Option<Builder> {
+        let mut builder = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        builder
+            .as_ref()
+            .and_then(try_clone)
+            .or_else(|| builder.take())
+    }
+}
+
+fn no_builder_error() -> TransportError {
+    TransportError::Other("Could not clone the RequestBuilder to send another request".into())
+}
+
+#[cfg(feature = "reqwest")]
+impl Transport for RequestBuilderTransport<reqwest::RequestBuilder> {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<serde_json::Value, TransportError>> {
+        match self.builder(reqwest::RequestBuilder::try_clone) {
+            Some(builder) => send_async(builder, request),
+            None => Box::pin(async { Err(no_builder_error()) }),
+        }
+    }
+}
+
+#[cfg(feature = "reqwest-blocking")]
+impl BlockingTransport for RequestBuilderTransport<reqwest::blocking::RequestBuilder> {
+    fn send(&self, request: Request) -> Result<serde_json::Value, TransportError> {
+        match self.builder(reqwest::blocking::RequestBuilder::try_clone) {
+            Some(builder) => send_blocking(builder, request),
+            None => Err(no_builder_error()),
+        }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+fn send_async(
+    mut builder: reqwest::RequestBuilder,
+    request: Request,
+) -> BoxFuture<'static, Result<serde_json::Value, TransportError>> {
+    for (name, value) in &request.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    if let Some(timeout) = request.timeout {
+        builder = builder.timeout(timeout);
+    }
+
+    let timeout = request.timeout;
+    let started = Instant::now();
+
+    Box::pin(async move {
+        let result = match builder.json(&request.body).send().await {
+            Ok(response) => response.json::<serde_json::Value>().await,
+            Err(error) => Err(error),
+        };
+
+        result.map_err(|error| transport_error(error, timeout, started))
+    })
+}
+
+#[cfg(feature = "reqwest-blocking")]
+fn send_blocking(
+    mut builder: reqwest::blocking::RequestBuilder,
+    request: Request,
+) -> Result<serde_json::Value, TransportError> {
+    for (name, value) in &request.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    if let Some(timeout) = request.timeout {
+        builder = builder.timeout(timeout);
+    }
+
+    let started = Instant::now();
+
+    builder
+        .json(&request.body)
+        .send()
+        .and_then(|response| response.json::<serde_json::Value>())
+        .map_err(|error| transport_error(error, request.timeout, started))
+}
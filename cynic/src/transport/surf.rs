@@ -0,0 +1,182 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use super::{timer, BoxFuture, ClientError, Request, Transport, TransportError};
+
+/// A `Transport` that sends requests with a `surf::Client`.
+#[cfg_attr(docsrs, doc(cfg(feature = "surf")))]
+pub struct SurfTransport {
+    client: surf::Client,
+    url: String,
+}
+
+impl SurfTransport {
+    /// Creates a transport that POSTs requests to `url` with `client`.
+    pub fn new(client: surf::Client, url: impl Into<String>) -> Self {
+        SurfTransport {
+            client,
+            url: url.into(),
+        }
+    }
+}
+
+impl Transport for SurfTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<serde_json::Value, TransportError>> {
+        send(self.client.post(&self.url), request)
+    }
+}
+
+/// A transport that sends requests with a `surf::RequestBuilder`.
+///
+/// This lets the `SurfExt` trait in `cynic::http` use a `Client` with the
+/// RequestBuilder it's called on.  RequestBuilders can't be cloned, so the
+/// first request is sent with the builder itself & records the
+/// `surf::Client` & `surf::Request` it was sent with.  Any further requests
+/// (e.g. automatic persisted query retries) send a copy of that request with
+/// the same client.
+pub(crate) struct SurfRequestBuilderTransport {
+    builder: Mutex<Option<surf::RequestBuilder>>,
+    sent: Arc<Mutex<Option<SentRequest>>>,
+}
+
+type SentRequest = (surf::Client, surf::Request);
+
+impl SurfRequestBuilderTransport {
+    pub(crate) fn new(builder: surf::RequestBuilder) -> Self {
+        SurfRequestBuilderTransport {
+            builder: Mutex::new(Some(builder)),
+            sent: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl Transport for SurfRequestBuilderTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<serde_json::Value, TransportError>> {
+        let builder = self
+            .builder
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+
+        if let Some(builder) = builder {
+            let builder = builder.middleware(RecordRequest(Arc::clone(&self.sent)));
+            return send(builder, request);
+        }
+
+        let sent = self.sent.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        match sent {
+            Some((client, surf_request)) => send_with_client(client, surf_request, request),
+            None => Box::pin(async {
+                Err(TransportError::Other(
+                    "Could not send another request with the surf::RequestBuilder".into(),
+                ))
+            }),
+        }
+    }
+}
+
+/// surf middleware that records the client & request it was run with.
+struct RecordRequest(Arc<Mutex<Option<SentRequest>>>);
+
+#[surf::utils::async_trait]
+impl surf::middleware::Middleware for RecordRequest {
+    async fn handle(
+        &self,
+        request: surf::Request,
+        client: surf::Client,
+        next: surf::middleware::Next<'_>,
+    ) -> surf::Result<surf::Response> {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some((client.clone(), request.clone()));
+
+        next.run(request, client).await
+    }
+}
+
+fn send(
+    mut builder: surf::RequestBuilder,
+    request: Request,
+) -> BoxFuture<'static, Result<serde_json::Value, TransportError>> {
+    for (name, value) in &request.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+
+    with_timeout(
+        request.timeout,
+        builder.body(request.body).recv_json::<serde_json::Value>(),
+    )
+}
+
+fn send_with_client(
+    client: surf::Client,
+    mut surf_request: surf::Request,
+    request: Request,
+) -> BoxFuture<'static, Result<serde_json::Value, TransportError>> {
+    for (name, value) in &request.headers {
+        surf_request.insert_header(name.as_str(), value.as_str());
+    }
+    surf_request.set_body(request.body);
+
+    with_timeout(request.timeout, async move {
+        client
+            .send(surf_request)
+            .await?
+            .body_json::<serde_json::Value>()
+            .await
+    })
+}
+
+fn with_timeout(
+    timeout: Option<Duration>,
+    response: impl Future<Output = surf::Result<serde_json::Value>> + Send + 'static,
+) -> BoxFuture<'static, Result<serde_json::Value, TransportError>> {
+    let response = Box::pin(response);
+
+    Box::pin(async move {
+        let result = match timeout {
+            Some(duration) => timer::timeout(duration, response)
+                .await
+                .ok_or(TransportError::Timeout)?,
+            None => response.await,
+        };
+
+        result.map_err(|e| TransportError::Transport(Box::new(SurfError(e))))
+    })
+}
+
+/// Converts a `ClientError` into a `surf::Error`, for the `SurfExt` trait.
+///
+/// Errors from surf itself are returned as they were, and any other errors
+/// can be obtained via `downcast_ref` on the `surf::Error`.
+pub(crate) fn into_surf_error(error: ClientError) -> surf::Error {
+    match error {
+        ClientError::Transport(TransportError::Transport(error)) => {
+            match error.downcast::<SurfError>() {
+                Ok(error) => error.0,
+                Err(error) => surf::Error::from_str(surf::StatusCode::InternalServerError, error),
+            }
+        }
+        ClientError::Transport(error) => error.into(),
+        ClientError::Serde(error) => error.into(),
+        ClientError::Decode(error) => error.into(),
+    }
+}
+
+/// Wraps a surf::Error so it can be used as a std::error::Error, without
+/// losing the status code.
+#[derive(Debug)]
+struct SurfError(surf::Error);
+
+impl std::fmt::Display for SurfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for SurfError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(AsRef::<dyn std::error::Error>::as_ref(&self.0))
+    }
+}
@@ -0,0 +1,27 @@
+//! Runtime agnostic timers, used for backoff & timeouts.
+//!
+//! These use `futures-timer`, which runs every timer from a single background
+//! thread (or the browsers timers on wasm) so they work with any executor.
+
+use std::time::Duration;
+
+/// A future that completes after `duration` has elapsed.
+pub(crate) fn delay(duration: Duration) -> futures_timer::Delay {
+    futures_timer::Delay::new(duration)
+}
+
+/// Runs a future, returning None if it doesn't complete within `duration`.
+///
+/// The timer is cancelled if the future completes first.
+#[cfg(feature = "surf")]
+pub(crate) async fn timeout<F: std::future::Future + Unpin>(
+    duration: Duration,
+    future: F,
+) -> Option<F::Output> {
+    use futures_util::future::{select, Either};
+
+    match select(future, delay(duration)).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
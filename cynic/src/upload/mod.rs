@@ -0,0 +1,232 @@
+//! Support for uploading files with the [GraphQL multipart request spec][1].
+//!
+//! [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+
+use std::{cell::RefCell, sync::Arc};
+
+use serde::ser::SerializeMap;
+use serde_json::Value;
+
+#[cfg(any(feature = "surf", feature = "reqwest", feature = "reqwest-blocking"))]
+mod multipart;
+
+#[cfg(any(feature = "surf", feature = "reqwest", feature = "reqwest-blocking"))]
+pub(crate) use multipart::MultipartBody;
+
+/// A file to upload as the value of an `Upload` scalar.
+///
+/// Uploads can be provided as arguments or inside `InputObject`s, like any
+/// other input type.  To use them with a schema call `impl_input_type!` with
+/// the `Upload` scalar from the `schema` module:
+///
+/// ```rust
+/// # mod schema { pub struct Upload {} }
+/// cynic::impl_input_type!(cynic::Upload, schema::Upload);
+/// ```
+///
+/// Files can only be sent by an operation that's run with one of the
+/// `run_graphql_multipart` functions in `cynic::http`.  Each upload is
+/// replaced with `null` in the operations variables & sent as a separate
+/// part of the request.  Serializing an operation that contains uploads any
+/// other way is an error, so the files aren't silently dropped.
+#[derive(Clone)]
+pub struct Upload {
+    filename: String,
+    content_type: Option<String>,
+    content: Arc<Vec<u8>>,
+}
+
+impl Upload {
+    /// Creates an Upload of a file with the given name & contents.
+    pub fn new(filename: impl Into<String>, content: impl Into<Vec<u8>>) -> Self {
+        Upload {
+            filename: filename.into(),
+            content_type: None,
+            content: Arc::new(content.into()),
+        }
+    }
+
+    /// Sets the MIME type of this file.
+    ///
+    /// If this isn't set the file is sent as `application/octet-stream`.
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// The name of this file.
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// The MIME type of this file, if one was set.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// The contents of this file.
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+}
+
+impl std::fmt::Debug for Upload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Upload")
+            .field("filename", &self.filename)
+            .field("content_type", &self.content_type)
+            .field("content_length", &self.content.len())
+            .finish()
+    }
+}
+
+thread_local! {
+    /// The uploads found by the current `to_value_with_uploads` call, if any.
+    static COLLECTED_UPLOADS: RefCell<Option<Vec<Upload>>> = const { RefCell::new(None) };
+}
+
+/// The key of the placeholder object that an `Upload` is serialized as while
+/// `to_value_with_uploads` is collecting them.
+const PLACEHOLDER_KEY: &str = "$cynicUpload";
+
+impl serde::Serialize for Upload {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let index = COLLECTED_UPLOADS.with(|collected| {
+            collected.borrow_mut().as_mut().map(|uploads| {
+                uploads.push(self.clone());
+                uploads.len() - 1
+            })
+        });
+
+        match index {
+            Some(index) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(PLACEHOLDER_KEY, &index)?;
+                map.end()
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+/// The path to an `Upload` inside the value of an argument, as a list of
+/// object keys & list indices.
+pub(crate) type UploadPath = Vec<String>;
+
+/// Serializes `value` into JSON, replacing any `Upload`s inside it with
+/// `null` and returning them alongside their paths.
+pub(crate) fn to_value_with_uploads<T>(
+    value: &T,
+) -> (Result<Value, serde_json::Error>, Vec<(UploadPath, Upload)>)
+where
+    T: serde::Serialize + ?Sized,
+{
+    let previous = COLLECTED_UPLOADS.with(|collected| collected.replace(Some(vec![])));
+    let result = serde_json::to_value(value);
+    let collected = COLLECTED_UPLOADS
+        .with(|collected| collected.replace(previous))
+        .unwrap_or_default();
+
+    match result {
+        Ok(mut value) => {
+            let mut uploads = vec![];
+            if !collected.is_empty() {
+                extract_uploads(&mut value, &mut vec![], &collected, &mut uploads);
+            }
+            (Ok(value), uploads)
+        }
+        Err(error) => (Err(error), vec![]),
+    }
+}
+
+fn extract_uploads(
+    value: &mut Value,
+    path: &mut UploadPath,
+    collected: &[Upload],
+    uploads_out: &mut Vec<(UploadPath, Upload)>,
+) {
+    let placeholder = match value {
+        Value::Object(map) if map.len() == 1 => map
+            .get(PLACEHOLDER_KEY)
+            .and_then(Value::as_u64)
+            .and_then(|index| collected.get(index as usize)),
+        _ => None,
+    };
+
+    if let Some(upload) = placeholder {
+        uploads_out.push((path.clone(), upload.clone()));
+        *value = Value::Null;
+        return;
+    }
+
+    match value {
+        Value::Object(map) => {
+            for (key, inner) in map.iter_mut() {
+                path.push(key.clone());
+                extract_uploads(inner, path, collected, uploads_out);
+                path.pop();
+            }
+        }
+        Value::Array(items) => {
+            for (index, inner) in items.iter_mut().enumerate() {
+                path.push(index.to_string());
+                extract_uploads(inner, path, collected, uploads_out);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(serde::Serialize)]
+    struct Attachment {
+        name: String,
+        file: Upload,
+    }
+
+    #[test]
+    fn test_to_value_with_uploads() {
+        let attachments = vec![
+            Attachment {
+                name: "one".into(),
+                file: Upload::new("one.txt", "1"),
+            },
+            Attachment {
+                name: "two".into(),
+                file: Upload::new("two.txt", "2"),
+            },
+        ];
+
+        let (value, uploads) = to_value_with_uploads(&attachments);
+
+        assert_eq!(
+            value.unwrap(),
+            json!([{"name": "one", "file": null}, {"name": "two", "file": null}])
+        );
+        assert_eq!(
+            uploads
+                .iter()
+                .map(|(path, upload)| (path.join("."), upload.filename()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("0.file".to_string(), "one.txt"),
+                ("1.file".to_string(), "two.txt")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uploads_serialize_as_null_outside_arguments() {
+        let upload = Upload::new("file.txt", "hello");
+
+        assert_eq!(serde_json::to_value(&upload).unwrap(), json!(null));
+    }
+}
@@ -0,0 +1,173 @@
+//! Writes `multipart/form-data` request bodies for operations that contain
+//! `Upload`s.
+
+use std::{borrow::Cow, collections::BTreeMap};
+
+use super::Upload;
+
+/// A `multipart/form-data` request body.
+pub(crate) struct MultipartBody {
+    pub content_type: String,
+    pub body: Vec<u8>,
+}
+
+impl MultipartBody {
+    /// Builds a body with the `operations` & `map` fields of the multipart
+    /// request spec, followed by a part for each of `files`.
+    ///
+    /// `files` should contain the path to each file in `operations`, e.g.
+    /// `variables.input.files.0`.
+    pub(crate) fn new(
+        operations: &impl serde::Serialize,
+        files: &[(String, &Upload)],
+    ) -> Result<Self, serde_json::Error> {
+        let operations = serde_json::to_vec(operations)?;
+        let map = serde_json::to_vec(
+            &files
+                .iter()
+                .enumerate()
+                .map(|(index, (path, _))| (index.to_string(), vec![path]))
+                .collect::<BTreeMap<_, _>>(),
+        )?;
+
+        let mut parts = vec![Part::json("operations", operations), Part::json("map", map)];
+        parts.extend(files.iter().enumerate().map(|(index, (_, upload))| Part {
+            name: index.to_string(),
+            filename: Some(upload.filename()),
+            content_type: upload.content_type().unwrap_or("application/octet-stream"),
+            content: Cow::Borrowed(upload.content()),
+        }));
+
+        let boundary = choose_boundary(&parts);
+        let mut body = vec![];
+        for part in &parts {
+            part.write(&boundary, &mut body);
+        }
+        body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+
+        Ok(MultipartBody {
+            content_type: format!("multipart/form-data; boundary={}", boundary),
+            body,
+        })
+    }
+}
+
+struct Part<'a> {
+    name: String,
+    filename: Option<&'a str>,
+    content_type: &'a str,
+    content: Cow<'a, [u8]>,
+}
+
+impl Part<'_> {
+    fn json(name: &str, content: Vec<u8>) -> Self {
+        Part {
+            name: name.to_string(),
+            filename: None,
+            content_type: "application/json",
+            content: Cow::Owned(content),
+        }
+    }
+
+    fn write(&self, boundary: &str, out: &mut Vec<u8>) {
+        out.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+        out.extend_from_slice(
+            format!(
+                "Content-Disposition: form-data; name=\"{}\"",
+                escape_quoted(&self.name)
+            )
+            .as_bytes(),
+        );
+        if let Some(filename) = self.filename {
+            out.extend_from_slice(format!("; filename=\"{}\"", escape_quoted(filename)).as_bytes());
+        }
+        out.extend_from_slice(
+            format!(
+                "\r\nContent-Type: {}\r\n\r\n",
+                escape_quoted(self.content_type)
+            )
+            .as_bytes(),
+        );
+        out.extend_from_slice(&self.content);
+        out.extend_from_slice(b"\r\n");
+    }
+}
+
+/// Picks a boundary that doesn't appear in the content of any of `parts`.
+fn choose_boundary(parts: &[Part<'_>]) -> String {
+    let mut boundary = "cynic-multipart-boundary".to_string();
+    let mut suffix = 0;
+    while parts
+        .iter()
+        .any(|part| contains(&part.content, boundary.as_bytes()))
+    {
+        suffix += 1;
+        boundary = format!("cynic-multipart-boundary-{}", suffix);
+    }
+    boundary
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+/// Escapes a value for use inside the quotes of a header parameter, the same
+/// way that browsers do.
+fn escape_quoted(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_multipart_body() {
+        let upload = Upload::new("a \"file\".txt", "hello").with_content_type("text/plain");
+        let body = MultipartBody::new(
+            &json!({"query": "mutation", "variables": {"file": null}}),
+            &[("variables.file".into(), &upload)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            body.content_type,
+            "multipart/form-data; boundary=cynic-multipart-boundary"
+        );
+        insta::assert_snapshot!(String::from_utf8(body.body).unwrap().replace("\r\n", "\n"), @r###"
+        --cynic-multipart-boundary
+        Content-Disposition: form-data; name="operations"
+        Content-Type: application/json
+
+        {"query":"mutation","variables":{"file":null}}
+        --cynic-multipart-boundary
+        Content-Disposition: form-data; name="map"
+        Content-Type: application/json
+
+        {"0":["variables.file"]}
+        --cynic-multipart-boundary
+        Content-Disposition: form-data; name="0"; filename="a %22file%22.txt"
+        Content-Type: text/plain
+
+        hello
+        --cynic-multipart-boundary--
+        "###);
+    }
+
+    #[test]
+    fn test_multipart_boundary_avoids_content() {
+        let upload = Upload::new("file.txt", "--cynic-multipart-boundary");
+        let body = MultipartBody::new(&json!({}), &[("variables.file".into(), &upload)]).unwrap();
+
+        assert_eq!(
+            body.content_type,
+            "multipart/form-data; boundary=cynic-multipart-boundary-1"
+        );
+    }
+}
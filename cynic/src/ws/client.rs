@@ -0,0 +1,426 @@
+use futures_channel::mpsc;
+use futures_util::{
+    sink::{Sink, SinkExt},
+    stream::{self, Stream, StreamExt},
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tungstenite::Message;
+
+use super::protocol::{ClientMessage, ServerMessage, WebsocketProtocol};
+use crate::{GraphQlResponse, StreamingOperation};
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+type SubscriptionSender =
+    mpsc::UnboundedSender<Result<GraphQlResponse<serde_json::Value>, SubscriptionError>>;
+
+/// An error that can occur while running a subscription.
+#[derive(thiserror::Error, Debug)]
+pub enum SubscriptionError {
+    #[error("Websocket error: {0}")]
+    WebsocketError(Box<tungstenite::Error>),
+    #[error("Error serializing or deserializing a websocket message: {0}")]
+    SerdeError(#[from] serde_json::Error),
+    #[error("Error decoding GraphQL response: {0}")]
+    DecodeError(#[from] crate::ResponseDecodeError),
+    #[error("The server rejected the connection: {0:?}")]
+    ConnectionRejected(Option<serde_json::Value>),
+    #[error("Received an unexpected message from the server: {0}")]
+    UnexpectedMessage(String),
+    #[error("The websocket connection was closed")]
+    ConnectionClosed,
+    #[error("The websocket connection failed: {0}")]
+    ConnectionFailed(String),
+}
+
+impl From<tungstenite::Error> for SubscriptionError {
+    fn from(error: tungstenite::Error) -> Self {
+        SubscriptionError::WebsocketError(Box::new(error))
+    }
+}
+
+/// A builder for a `SubscriptionClient`.
+#[derive(Default)]
+pub struct SubscriptionClientBuilder {
+    protocol: WebsocketProtocol,
+    payload: Option<serde_json::Value>,
+}
+
+impl SubscriptionClientBuilder {
+    /// Sets the protocol the client will use.  Defaults to
+    /// `WebsocketProtocol::GraphQlTransportWs`.
+    ///
+    /// Note that the websocket should have been opened with the matching
+    /// `Sec-WebSocket-Protocol` header - see `WebsocketProtocol::subprotocol`.
+    pub fn protocol(mut self, protocol: WebsocketProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Sets the payload to send with the `connection_init` message.
+    ///
+    /// Servers often use this for authentication.
+    pub fn payload(mut self, payload: serde_json::Value) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+
+    /// Initialises a GraphQL connection over an already open websocket.
+    ///
+    /// This sends a `connection_init` message and waits for the server to
+    /// acknowledge it.  On success it returns a `SubscriptionClient` and a
+    /// future that drives the connection.  The future must be spawned onto
+    /// an executor (or otherwise polled) for any subscriptions to make
+    /// progress, and will complete when the connection closes.
+    pub async fn build<Connection>(
+        self,
+        mut connection: Connection,
+    ) -> Result<(SubscriptionClient, BoxFuture<'static, ()>), SubscriptionError>
+    where
+        Connection: Stream<Item = Result<Message, tungstenite::Error>>
+            + Sink<Message, Error = tungstenite::Error>
+            + Unpin
+            + Send
+            + 'static,
+    {
+        let protocol = self.protocol;
+
+        connection
+            .send(Message::Text(
+                ClientMessage::ConnectionInit {
+                    payload: self.payload,
+                }
+                .to_json(),
+            ))
+            .await?;
+
+        loop {
+            let message = match connection.next().await {
+                Some(message) => message?,
+                None => return Err(SubscriptionError::ConnectionClosed),
+            };
+
+            match decode_message(message)? {
+                Some(ServerMessage::ConnectionAck) => break,
+                Some(ServerMessage::ConnectionError { payload }) => {
+                    return Err(SubscriptionError::ConnectionRejected(payload))
+                }
+                Some(ServerMessage::Ping) => {
+                    connection
+                        .send(Message::Text(ClientMessage::Pong.to_json()))
+                        .await?;
+                }
+                Some(ServerMessage::Pong) | Some(ServerMessage::KeepAlive) | None => {}
+                Some(other) => {
+                    return Err(SubscriptionError::UnexpectedMessage(format!("{:?}", other)))
+                }
+            }
+        }
+
+        let (sender, receiver) = mpsc::unbounded();
+
+        let client = SubscriptionClient {
+            sender,
+            protocol,
+            next_id: Arc::new(AtomicU64::new(1)),
+        };
+
+        Ok((
+            client,
+            Box::pin(run_connection(connection, receiver, protocol)),
+        ))
+    }
+}
+
+/// A client that can run many subscriptions over a single websocket.
+///
+/// ```rust,no_run
+/// # mod schema {
+/// #   cynic::use_schema!("../schemas/books.graphql");
+/// # }
+/// #
+/// # #[derive(cynic::QueryFragment)]
+/// # #[cynic(
+/// #     schema_path = "../schemas/books.graphql",
+/// #     schema_module = "schema",
+/// #     graphql_type = "SubscriptionRoot"
+/// # )]
+/// # struct IntervalSubscription {
+/// #     #[arguments(n = 1)]
+/// #     interval: i32,
+/// # }
+/// use cynic::{ws::SubscriptionClient, SubscriptionBuilder};
+/// use futures_util::StreamExt;
+///
+/// # async fn run() {
+/// let (connection, _) = tokio_tungstenite::connect_async("ws://localhost:8000")
+///     .await
+///     .unwrap();
+///
+/// let (client, driver) = SubscriptionClient::builder()
+///     .build(connection)
+///     .await
+///     .unwrap();
+/// tokio::spawn(driver);
+///
+/// let mut stream = client.subscribe(IntervalSubscription::build(()));
+/// while let Some(response) = stream.next().await {
+///     println!("{:?}", response.unwrap().data.map(|d| d.interval));
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SubscriptionClient {
+    sender: mpsc::UnboundedSender<Command>,
+    protocol: WebsocketProtocol,
+    next_id: Arc<AtomicU64>,
+}
+
+impl SubscriptionClient {
+    /// Creates a `SubscriptionClientBuilder`.
+    pub fn builder() -> SubscriptionClientBuilder {
+        SubscriptionClientBuilder::default()
+    }
+
+    /// The protocol this client is using.
+    pub fn protocol(&self) -> WebsocketProtocol {
+        self.protocol
+    }
+
+    /// Starts a subscription, returning a stream of its responses.
+    ///
+    /// The data of each response is decoded with the `StreamingOperation`.
+    /// The stream finishes when the server completes the subscription or the
+    /// connection closes.  Dropping the stream stops the subscription.
+    pub fn subscribe<'a, ResponseData: 'a>(
+        &self,
+        operation: StreamingOperation<'a, ResponseData>,
+    ) -> SubscriptionStream<'a, ResponseData> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed).to_string();
+        let (sender, receiver) = mpsc::unbounded();
+
+        match serde_json::to_value(&operation) {
+            Ok(payload) => {
+                // If this fails the connection has closed, and the sender will
+                // be dropped - which closes our receiver.
+                self.sender
+                    .unbounded_send(Command::Subscribe {
+                        id: id.clone(),
+                        payload,
+                        sender,
+                    })
+                    .ok();
+            }
+            Err(error) => {
+                sender.unbounded_send(Err(error.into())).ok();
+            }
+        }
+
+        SubscriptionStream {
+            id,
+            operation,
+            receiver,
+            commands: self.sender.clone(),
+            finished: false,
+        }
+    }
+
+    /// Closes the connection, ending any running subscriptions.
+    pub fn close(&self) {
+        self.sender.unbounded_send(Command::Close).ok();
+    }
+}
+
+/// A stream of responses to a subscription.
+pub struct SubscriptionStream<'a, ResponseData> {
+    id: String,
+    operation: StreamingOperation<'a, ResponseData>,
+    receiver:
+        mpsc::UnboundedReceiver<Result<GraphQlResponse<serde_json::Value>, SubscriptionError>>,
+    commands: mpsc::UnboundedSender<Command>,
+    finished: bool,
+}
+
+impl<'a, ResponseData: 'a> Stream for SubscriptionStream<'a, ResponseData> {
+    type Item = Result<GraphQlResponse<ResponseData>, SubscriptionError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        match this.receiver.poll_next_unpin(cx) {
+            Poll::Ready(Some(response)) => Poll::Ready(Some(response.and_then(|response| {
+                this.operation
+                    .decode_graphql_response(response)
+                    .map_err(SubscriptionError::from)
+            }))),
+            Poll::Ready(None) => {
+                this.finished = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<'a, ResponseData> Drop for SubscriptionStream<'a, ResponseData> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.commands
+                .unbounded_send(Command::Stop {
+                    id: self.id.clone(),
+                })
+                .ok();
+        }
+    }
+}
+
+enum Command {
+    Subscribe {
+        id: String,
+        payload: serde_json::Value,
+        sender: SubscriptionSender,
+    },
+    Stop {
+        id: String,
+    },
+    Close,
+}
+
+enum Event {
+    Incoming(Result<Message, tungstenite::Error>),
+    Command(Command),
+}
+
+async fn run_connection<Connection>(
+    connection: Connection,
+    commands: mpsc::UnboundedReceiver<Command>,
+    protocol: WebsocketProtocol,
+) where
+    Connection: Stream<Item = Result<Message, tungstenite::Error>>
+        + Sink<Message, Error = tungstenite::Error>
+        + Unpin
+        + Send
+        + 'static,
+{
+    let (mut sink, incoming) = connection.split();
+
+    let mut events = stream::select(incoming.map(Event::Incoming), commands.map(Event::Command));
+    let mut subscriptions = HashMap::<String, SubscriptionSender>::new();
+    let mut failure = None;
+
+    while let Some(event) = events.next().await {
+        let outgoing = match event {
+            Event::Command(Command::Subscribe {
+                id,
+                payload,
+                sender,
+            }) => {
+                subscriptions.insert(id.clone(), sender);
+                Some(ClientMessage::subscribe(protocol, id, payload))
+            }
+            Event::Command(Command::Stop { id }) => subscriptions
+                .remove(&id)
+                .map(|_| ClientMessage::stop(protocol, id)),
+            Event::Command(Command::Close) => {
+                if protocol == WebsocketProtocol::SubscriptionsTransportWs {
+                    sink.send(Message::Text(ClientMessage::ConnectionTerminate.to_json()))
+                        .await
+                        .ok();
+                }
+                sink.close().await.ok();
+                break;
+            }
+            Event::Incoming(Err(error)) => {
+                failure = Some(error.to_string());
+                break;
+            }
+            Event::Incoming(Ok(Message::Close(_))) => break,
+            Event::Incoming(Ok(message)) => match decode_message(message) {
+                Ok(Some(message)) => handle_server_message(message, &mut subscriptions),
+                Ok(None) => None,
+                Err(error) => {
+                    // Both protocols expect the connection to be closed if
+                    // either party receives an invalid message.
+                    failure = Some(error.to_string());
+                    sink.close().await.ok();
+                    break;
+                }
+            },
+        };
+
+        if let Some(outgoing) = outgoing {
+            if let Err(error) = sink.send(Message::Text(outgoing.to_json())).await {
+                failure = Some(error.to_string());
+                break;
+            }
+        }
+    }
+
+    for (_, sender) in subscriptions {
+        let error = match &failure {
+            Some(message) => SubscriptionError::ConnectionFailed(message.clone()),
+            None => SubscriptionError::ConnectionClosed,
+        };
+        sender.unbounded_send(Err(error)).ok();
+    }
+}
+
+/// Handles a message from the server, returning any reply that should be
+/// sent.
+fn handle_server_message(
+    message: ServerMessage,
+    subscriptions: &mut HashMap<String, SubscriptionSender>,
+) -> Option<ClientMessage> {
+    match message {
+        ServerMessage::Ping => Some(ClientMessage::Pong),
+        ServerMessage::Next { id, payload } => {
+            if let Some(sender) = subscriptions.get(&id) {
+                sender.unbounded_send(Ok(payload)).ok();
+            }
+            None
+        }
+        ServerMessage::Error { id, payload } => {
+            // An error message is the last message we'll receive for a
+            // subscription, so we forward the errors & then close the stream.
+            if let Some(sender) = subscriptions.remove(&id) {
+                sender
+                    .unbounded_send(Ok(GraphQlResponse {
+                        data: None,
+                        errors: Some(payload.into_errors()),
+                    }))
+                    .ok();
+            }
+            None
+        }
+        ServerMessage::Complete { id } => {
+            subscriptions.remove(&id);
+            None
+        }
+        ServerMessage::Pong
+        | ServerMessage::KeepAlive
+        | ServerMessage::ConnectionAck
+        | ServerMessage::ConnectionError { .. } => None,
+    }
+}
+
+/// Decodes a websocket message into a ServerMessage.
+///
+/// Returns `None` for messages that are handled at the websocket layer
+/// (e.g. websocket pings)
+fn decode_message(message: Message) -> Result<Option<ServerMessage>, SubscriptionError> {
+    match message {
+        Message::Text(text) => Ok(Some(serde_json::from_str(&text)?)),
+        Message::Binary(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        Message::Close(_) => Err(SubscriptionError::ConnectionClosed),
+        Message::Ping(_) | Message::Pong(_) => Ok(None),
+    }
+}
@@ -0,0 +1,15 @@
+//! GraphQL subscriptions over websockets.
+//!
+//! This supports both the `graphql-transport-ws` protocol and the legacy
+//! `graphql-ws` protocol from `subscriptions-transport-ws`.  It's built on top
+//! of the message types from the [`tungstenite`](https://docs.rs/tungstenite)
+//! crate, so can be used with either `tokio-tungstenite` or
+//! `async-tungstenite`.
+
+mod client;
+mod protocol;
+
+pub use client::{
+    SubscriptionClient, SubscriptionClientBuilder, SubscriptionError, SubscriptionStream,
+};
+pub use protocol::WebsocketProtocol;
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{GraphQlError, GraphQlResponse};
+
+/// The websocket protocols that cynic can use to run subscriptions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WebsocketProtocol {
+    /// The `graphql-transport-ws` protocol, as implemented by the
+    /// [graphql-ws](https://github.com/enisdenjo/graphql-ws) library.
+    #[default]
+    GraphQlTransportWs,
+    /// The legacy `graphql-ws` protocol, as implemented by the
+    /// [subscriptions-transport-ws](https://github.com/apollographql/subscriptions-transport-ws)
+    /// library.
+    SubscriptionsTransportWs,
+}
+
+impl WebsocketProtocol {
+    /// The value of the `Sec-WebSocket-Protocol` header that should be sent
+    /// when opening a websocket that uses this protocol.
+    pub fn subprotocol(&self) -> &'static str {
+        match self {
+            WebsocketProtocol::GraphQlTransportWs => "graphql-transport-ws",
+            WebsocketProtocol::SubscriptionsTransportWs => "graphql-ws",
+        }
+    }
+}
+
+/// A message sent from the client to the server.
+///
+/// This covers the messages of both protocols - it's up to the caller to
+/// only send messages that the current protocol understands.
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(super) enum ClientMessage {
+    ConnectionInit {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<serde_json::Value>,
+    },
+    Pong,
+    Subscribe {
+        id: String,
+        payload: serde_json::Value,
+    },
+    Complete {
+        id: String,
+    },
+    Start {
+        id: String,
+        payload: serde_json::Value,
+    },
+    Stop {
+        id: String,
+    },
+    ConnectionTerminate,
+}
+
+impl ClientMessage {
+    pub(super) fn subscribe(
+        protocol: WebsocketProtocol,
+        id: String,
+        payload: serde_json::Value,
+    ) -> Self {
+        match protocol {
+            WebsocketProtocol::GraphQlTransportWs => ClientMessage::Subscribe { id, payload },
+            WebsocketProtocol::SubscriptionsTransportWs => ClientMessage::Start { id, payload },
+        }
+    }
+
+    pub(super) fn stop(protocol: WebsocketProtocol, id: String) -> Self {
+        match protocol {
+            WebsocketProtocol::GraphQlTransportWs => ClientMessage::Complete { id },
+            WebsocketProtocol::SubscriptionsTransportWs => ClientMessage::Stop { id },
+        }
+    }
+
+    pub(super) fn to_json(&self) -> String {
+        // Serializing these messages can't fail: they only contain strings &
+        // already serialized JSON values.
+        serde_json::to_string(self).expect("client messages to serialize")
+    }
+}
+
+/// A message sent from the server to the client, in either protocol.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(super) enum ServerMessage {
+    ConnectionAck,
+    ConnectionError {
+        #[serde(default)]
+        payload: Option<serde_json::Value>,
+    },
+    Ping,
+    Pong,
+    #[serde(rename = "ka")]
+    KeepAlive,
+    #[serde(alias = "data")]
+    Next {
+        id: String,
+        payload: GraphQlResponse<serde_json::Value>,
+    },
+    Error {
+        id: String,
+        payload: ErrorPayload,
+    },
+    Complete {
+        id: String,
+    },
+}
+
+/// The payload of an error message.
+///
+/// graphql-transport-ws sends a list of GraphQL errors, whereas the legacy
+/// protocol sends a single error object.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub(super) enum ErrorPayload {
+    Errors(Vec<GraphQlError>),
+    Error(GraphQlError),
+}
+
+impl ErrorPayload {
+    pub(super) fn into_errors(self) -> Vec<GraphQlError> {
+        match self {
+            ErrorPayload::Errors(errors) => errors,
+            ErrorPayload::Error(error) => vec![error],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use serde_json::json;
+
+    #[test]
+    fn test_client_message_serialization() {
+        assert_eq!(
+            ClientMessage::ConnectionInit { payload: None }.to_json(),
+            r#"{"type":"connection_init"}"#
+        );
+        assert_eq!(
+            ClientMessage::subscribe(
+                WebsocketProtocol::GraphQlTransportWs,
+                "1".into(),
+                json!({"query": "subscription { x }"})
+            )
+            .to_json(),
+            r#"{"type":"subscribe","id":"1","payload":{"query":"subscription { x }"}}"#
+        );
+        assert_eq!(
+            ClientMessage::stop(WebsocketProtocol::SubscriptionsTransportWs, "1".into()).to_json(),
+            r#"{"type":"stop","id":"1"}"#
+        );
+    }
+
+    #[test]
+    fn test_server_message_deserialization() {
+        let legacy_data: ServerMessage =
+            serde_json::from_value(json!({"type": "data", "id": "1", "payload": {"data": 1}}))
+                .unwrap();
+        assert_matches!(legacy_data, ServerMessage::Next { id, payload } if id == "1" && payload.data == Some(json!(1)));
+
+        let keep_alive: ServerMessage = serde_json::from_value(json!({"type": "ka"})).unwrap();
+        assert_matches!(keep_alive, ServerMessage::KeepAlive);
+
+        let legacy_error: ServerMessage = serde_json::from_value(
+            json!({"type": "error", "id": "2", "payload": {"message": "oops"}}),
+        )
+        .unwrap();
+        let errors = assert_matches!(legacy_error, ServerMessage::Error { payload, .. } => payload.into_errors());
+        assert_eq!(errors.len(), 1);
+    }
+}
@@ -34,11 +34,11 @@ fn test_explicit_alias_query_output() {
     let operation = FilmQueryWithExplicitAlias::build(());
 
     insta::assert_display_snapshot!(operation.query, @r###"
-    query Query($_0: ID, $_1: ID) {
-      a_new_hope:   film(id: $_0) {
+    query FilmQueryWithExplicitAlias($id: ID, $id2: ID) {
+      a_new_hope: film(id: $id) {
         title
       }
-      empire_strikes_back:   film(id: $_1) {
+      empire_strikes_back: film(id: $id2) {
         title
       }
     }
@@ -59,7 +59,6 @@ fn test_explicit_alias_decoding() {
                 }))
             })
             .unwrap()
-            .data
             .unwrap(),
         FilmQueryWithExplicitAlias {
             a_new_hope: Some(Film {
@@ -92,11 +91,11 @@ fn test_implicit_alias_query_output() {
     let operation = FilmQueryWithImplicitAlias::build(());
 
     insta::assert_display_snapshot!(operation.query, @r###"
-    query Query($_0: ID, $_1: ID) {
-      film(id: $_0) {
+    query FilmQueryWithImplicitAlias($id: ID, $id2: ID) {
+      film(id: $id) {
         title
       }
-      empire_strikes_back:   film(id: $_1) {
+      empire_strikes_back: film(id: $id2) {
         title
       }
     }
@@ -117,7 +116,6 @@ fn test_implicit_alias_decoding() {
                 }))
             })
             .unwrap()
-            .data
             .unwrap(),
         FilmQueryWithImplicitAlias {
             a_new_hope: Some(Film {
@@ -0,0 +1,155 @@
+// The fields of these fragments are never read, only their queries.
+#![allow(dead_code)]
+
+use cynic::{analysis::analyse, relay::Connection, QueryBuilder, QueryFragment};
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::FragmentArguments)]
+struct FilmsArguments {
+    first: Option<i32>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root",
+    argument_struct = "FilmsArguments",
+    max_depth = 7
+)]
+struct AllFilmsQuery {
+    #[arguments(first = args.first)]
+    all_films: Option<Connection<Film, schema::FilmsConnection>>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Film {
+    title: Option<String>,
+
+    #[arguments(first = 5)]
+    planet_connection: Option<Connection<Planet, schema::FilmPlanetsConnection>>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Planet {
+    name: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root"
+)]
+struct NodeQuery {
+    #[arguments(id = cynic::Id::new("cGxhbmV0czox"))]
+    node: Option<Node>,
+}
+
+#[derive(cynic::InlineFragments, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+enum Node {
+    Planet(Planet),
+    Film(Film),
+
+    #[cynic(fallback)]
+    Other,
+}
+
+#[test]
+fn test_analysing_connections() {
+    let analysis = analyse(&AllFilmsQuery::build(FilmsArguments { first: Some(10) }));
+
+    assert_eq!(analysis.depth, 7);
+    assert_eq!(analysis.field_count, 14);
+    assert_eq!(analysis.node_count, 10 + 10 * 5);
+}
+
+#[test]
+fn test_analysing_without_page_sizes() {
+    let analysis = analyse(&AllFilmsQuery::build(FilmsArguments { first: None }));
+
+    assert_eq!(analysis.node_count, 5);
+}
+
+#[test]
+fn test_analysing_inline_fragments() {
+    let analysis = analyse(&NodeQuery::build(()));
+
+    // `node { ... on Film { planetConnection { edges { node { name } } } } }`
+    assert_eq!(analysis.depth, 5);
+    assert_eq!(analysis.node_count, 5);
+}
+
+#[test]
+fn test_fragment_depth_matches_analysis() {
+    assert_eq!(
+        AllFilmsQuery::DEPTH,
+        analyse(&AllFilmsQuery::build(FilmsArguments { first: None })).depth
+    );
+    assert_eq!(NodeQuery::DEPTH, analyse(&NodeQuery::build(())).depth);
+}
+
+mod recursive {
+    use super::*;
+
+    mod schema {
+        cynic::use_schema!("tests/test-schema.graphql");
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(
+        graphql_type = "Query",
+        schema_path = "tests/test-schema.graphql",
+        query_module = "schema",
+        max_depth = 9
+    )]
+    struct AllPostsQuery {
+        all_posts: Vec<Post>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(
+        graphql_type = "BlogPost",
+        schema_path = "tests/test-schema.graphql",
+        query_module = "schema"
+    )]
+    struct Post {
+        comments: Vec<Comment>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(schema_path = "tests/test-schema.graphql", query_module = "schema")]
+    struct Comment {
+        author: Author,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(schema_path = "tests/test-schema.graphql", query_module = "schema")]
+    struct Author {
+        #[cynic(recurse = "2")]
+        posts: Option<Vec<Post>>,
+    }
+
+    #[test]
+    fn test_recursive_fields_depth_is_a_lower_bound() {
+        // `posts` is counted as recursing twice, but only one level deeper
+        // each time, rather than through `comments { author }` again.
+        assert_eq!(AllPostsQuery::DEPTH, 7);
+        assert_eq!(analyse(&AllPostsQuery::build(())).depth, 9);
+    }
+}
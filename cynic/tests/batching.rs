@@ -0,0 +1,184 @@
+use cynic::{BatchOperation, GraphQlResponse, QueryBuilder};
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root"
+)]
+struct FilmQuery {
+    #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+    film: Option<Film>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Film {
+    title: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root"
+)]
+struct PersonQuery {
+    #[arguments(id = cynic::Id::new("cGVvcGxlOjE="))]
+    person: Option<Person>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Person {
+    name: Option<String>,
+}
+
+fn responses() -> Vec<GraphQlResponse<serde_json::Value>> {
+    serde_json::from_value(json!([
+        {"data": {"film": {"title": "A New Hope"}}},
+        {"data": {"person": {"name": "Luke Skywalker"}}}
+    ]))
+    .unwrap()
+}
+
+#[test]
+fn test_batch_serialization() {
+    let batch = BatchOperation::from((FilmQuery::build(()), PersonQuery::build(())));
+
+    let serialized = serde_json::to_value(&batch).unwrap();
+
+    assert_eq!(
+        serialized,
+        json!([
+            serde_json::to_value(FilmQuery::build(())).unwrap(),
+            serde_json::to_value(PersonQuery::build(())).unwrap(),
+        ])
+    );
+}
+
+#[test]
+fn test_batch_decoding() {
+    let batch = BatchOperation::from((FilmQuery::build(()), PersonQuery::build(())));
+
+    let (film, person) = batch.decode_response(responses()).unwrap();
+
+    assert_eq!(
+        film.data.unwrap().film.unwrap().title.as_deref(),
+        Some("A New Hope")
+    );
+    assert_eq!(
+        person.data.unwrap().person.unwrap().name.as_deref(),
+        Some("Luke Skywalker")
+    );
+}
+
+#[test]
+fn test_vec_batch_decoding() {
+    let batch = BatchOperation::from(vec![FilmQuery::build(()), FilmQuery::build(())]);
+    assert_eq!(batch.len(), 2);
+
+    let responses = serde_json::from_value(json!([
+        {"data": {"film": {"title": "A New Hope"}}},
+        {"data": null, "errors": [{"message": "Oh no"}]}
+    ]))
+    .unwrap();
+
+    let results = batch.decode_response(responses).unwrap();
+
+    assert_eq!(
+        results[0].data,
+        Some(FilmQuery {
+            film: Some(Film {
+                title: Some("A New Hope".into())
+            })
+        })
+    );
+    assert_eq!(results[1].data, None);
+    assert_eq!(results[1].errors.as_ref().unwrap()[0].message, "Oh no");
+}
+
+#[test]
+fn test_batch_decoding_wrong_number_of_responses() {
+    let batch = BatchOperation::from((FilmQuery::build(()),));
+
+    assert_eq!(
+        batch.decode_response(responses()).map(|_| ()),
+        Err(cynic::DecodeError::Other("Expected 1 responses to a batch but got 2".into()).into())
+    );
+}
+
+#[cfg(feature = "reqwest-blocking")]
+#[test]
+fn test_run_graphql_batch_blocking() {
+    use cynic::http::ReqwestBlockingExt;
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                content_length = length.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+
+        let response = serde_json::to_string(&json!([
+            {"data": {"film": {"title": "A New Hope"}}},
+            {"data": {"person": {"name": "Luke Skywalker"}}}
+        ]))
+        .unwrap();
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            response.len(),
+            response
+        )
+        .unwrap();
+
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+    });
+
+    let (film, person) = reqwest::blocking::Client::new()
+        .post(&url)
+        .run_graphql_batch(BatchOperation::from((
+            FilmQuery::build(()),
+            PersonQuery::build(()),
+        )))
+        .unwrap();
+
+    let request_body = server.join().unwrap();
+    assert_eq!(request_body.as_array().unwrap().len(), 2);
+
+    assert_eq!(
+        film.data.unwrap().film.unwrap().title.as_deref(),
+        Some("A New Hope")
+    );
+    assert_eq!(
+        person.data.unwrap().person.unwrap().name.as_deref(),
+        Some("Luke Skywalker")
+    );
+}
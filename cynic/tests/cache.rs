@@ -0,0 +1,315 @@
+use cynic::{
+    transport::{
+        cache::{CachePolicy, InMemoryCache},
+        BlockingClient, Client, MockTransport,
+    },
+    MutationBuilder, QueryBuilder,
+};
+use futures_util::StreamExt;
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("tests/cache-schema.graphql");
+}
+
+#[derive(cynic::FragmentArguments)]
+struct FilmArguments {
+    id: cynic::Id,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/cache-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Query",
+    argument_struct = "FilmArguments"
+)]
+struct FilmQuery {
+    #[arguments(id = args.id.clone())]
+    film: Option<Film>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(schema_path = "tests/cache-schema.graphql", query_module = "schema")]
+struct Film {
+    id: cynic::Id,
+    title: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/cache-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Query"
+)]
+struct FilmsQuery {
+    films: Vec<Film>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/cache-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Query"
+)]
+struct DirectorsQuery {
+    films: Vec<FilmDirector>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/cache-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Film"
+)]
+struct FilmDirector {
+    id: cynic::Id,
+    director: Option<String>,
+}
+
+#[derive(cynic::FragmentArguments)]
+struct UpdateFilmArguments {
+    id: cynic::Id,
+    title: String,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/cache-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Mutation",
+    argument_struct = "UpdateFilmArguments"
+)]
+struct UpdateFilm {
+    #[arguments(id = args.id.clone(), title = args.title.clone())]
+    update_film: Film,
+}
+
+fn film(id: &str, title: &str) -> serde_json::Value {
+    json!({"__typename": "Film", "id": id, "title": title})
+}
+
+fn film_query(id: &str) -> cynic::Operation<'static, FilmQuery> {
+    FilmQuery::build(FilmArguments {
+        id: cynic::Id::new(id),
+    })
+}
+
+fn client(transport: MockTransport) -> BlockingClient<MockTransport> {
+    BlockingClient::new(transport).with_cache(InMemoryCache::new())
+}
+
+#[test]
+fn test_cached_query_output() {
+    let client = client(MockTransport::new().with_response(json!({"data": {"film": null}})));
+
+    client.run(film_query("1")).unwrap();
+
+    insta::assert_display_snapshot!(client.transport().requests()[0].body["query"].as_str().unwrap(), @r###"
+    query FilmQuery($id: ID!) {
+      film(id: $id) {
+        __typename
+        id
+        title
+      }
+    }
+    "###);
+}
+
+#[test]
+fn test_cache_first() {
+    let client = client(
+        MockTransport::new().with_response(json!({"data": {"film": film("1", "A New Hope")}})),
+    );
+
+    let network = client.run(film_query("1")).unwrap();
+    let cached = client
+        .run_with_policy(film_query("1"), CachePolicy::CacheFirst)
+        .collect::<Vec<_>>();
+
+    assert_eq!(cached.len(), 1);
+    assert_eq!(cached[0].as_ref().unwrap().data, network.data);
+    assert_eq!(client.transport().requests().len(), 1);
+}
+
+#[test]
+fn test_cache_first_misses_with_different_arguments() {
+    let client = client(
+        MockTransport::new()
+            .with_response(json!({"data": {"film": film("1", "A New Hope")}}))
+            .with_response(json!({"data": {"film": film("2", "The Empire Strikes Back")}})),
+    );
+
+    client.run(film_query("1")).unwrap();
+    let response = client
+        .run_with_policy(film_query("2"), CachePolicy::CacheFirst)
+        .next()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        response.data.unwrap().film.unwrap().title.as_deref(),
+        Some("The Empire Strikes Back")
+    );
+    assert_eq!(client.transport().requests().len(), 2);
+}
+
+#[test]
+fn test_cache_first_misses_with_missing_fields() {
+    let client = client(
+        MockTransport::new()
+            .with_response(json!({"data": {"films": [film("1", "A New Hope")]}}))
+            .with_response(json!({
+                "data": {
+                    "films": [{"__typename": "Film", "id": "1", "director": "George Lucas"}]
+                }
+            })),
+    );
+
+    client.run(FilmsQuery::build(())).unwrap();
+    client
+        .run_with_policy(DirectorsQuery::build(()), CachePolicy::CacheFirst)
+        .for_each(|response| {
+            response.unwrap();
+        });
+
+    assert_eq!(client.transport().requests().len(), 2);
+
+    // Both responses are merged into the same entity.
+    assert_eq!(
+        client.cache().unwrap().entity("Film", "1"),
+        Some(json!({
+            "__typename": "Film",
+            "id": "1",
+            "title": "A New Hope",
+            "director": "George Lucas"
+        }))
+    );
+}
+
+#[test]
+fn test_network_only() {
+    let client = client(
+        MockTransport::new()
+            .with_response(json!({"data": {"film": film("1", "A New Hope")}}))
+            .with_response(json!({"data": {"film": film("1", "Star Wars")}})),
+    );
+
+    client.run(film_query("1")).unwrap();
+    let responses = client
+        .run_with_policy(film_query("1"), CachePolicy::NetworkOnly)
+        .map(|response| response.unwrap().data.unwrap().film.unwrap().title)
+        .collect::<Vec<_>>();
+
+    assert_eq!(responses, vec![Some("Star Wars".to_string())]);
+    assert_eq!(client.transport().requests().len(), 2);
+
+    // The cache is still updated with the new response
+    assert_eq!(
+        client.cache().unwrap().entity("Film", "1").unwrap()["title"],
+        json!("Star Wars")
+    );
+}
+
+#[tokio::test]
+async fn test_cache_and_network() {
+    let client = Client::new(
+        MockTransport::new()
+            .with_response(json!({"data": {"film": film("1", "A New Hope")}}))
+            .with_response(json!({"data": {"film": film("1", "Star Wars")}})),
+    )
+    .with_cache(InMemoryCache::new());
+
+    client.run(film_query("1")).await.unwrap();
+    let titles = client
+        .run_with_policy(film_query("1"), CachePolicy::CacheAndNetwork)
+        .map(|response| response.unwrap().data.unwrap().film.unwrap().title.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(titles, vec!["A New Hope", "Star Wars"]);
+    assert_eq!(client.transport().requests().len(), 2);
+}
+
+#[test]
+fn test_mutations_update_entities() {
+    let client = client(
+        MockTransport::new()
+            .with_response(json!({
+                "data": {"films": [film("1", "A New Hope"), film("2", "The Empire Strikes Back")]}
+            }))
+            .with_response(json!({"data": {"updateFilm": film("1", "Star Wars")}})),
+    );
+
+    client.run(FilmsQuery::build(())).unwrap();
+    client
+        .run(UpdateFilm::build(UpdateFilmArguments {
+            id: cynic::Id::new("1"),
+            title: "Star Wars".into(),
+        }))
+        .unwrap();
+
+    let titles = client
+        .run_with_policy(FilmsQuery::build(()), CachePolicy::CacheFirst)
+        .next()
+        .unwrap()
+        .unwrap()
+        .data
+        .unwrap()
+        .films
+        .into_iter()
+        .map(|film| film.title.unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(titles, vec!["Star Wars", "The Empire Strikes Back"]);
+    assert_eq!(client.transport().requests().len(), 2);
+}
+
+#[test]
+fn test_evicted_entities_miss() {
+    let client = client(
+        MockTransport::new()
+            .with_response(json!({"data": {"film": film("1", "A New Hope")}}))
+            .with_response(json!({"data": {"film": film("1", "A New Hope")}})),
+    );
+
+    client.run(film_query("1")).unwrap();
+    client.cache().unwrap().evict("Film", "1");
+    client
+        .run_with_policy(film_query("1"), CachePolicy::CacheFirst)
+        .for_each(|response| {
+            response.unwrap();
+        });
+
+    assert_eq!(client.transport().requests().len(), 2);
+}
+
+#[test]
+fn test_responses_with_errors_are_not_cached() {
+    let client = client(MockTransport::new().with_response(json!({
+        "data": {"film": null},
+        "errors": [{"message": "Something went wrong"}]
+    })));
+
+    client.run(film_query("1")).unwrap();
+
+    assert_eq!(client.cache().unwrap().read(&film_query("1")), None);
+}
+
+#[test]
+fn test_clients_without_a_cache_always_fetch() {
+    let client = BlockingClient::new(
+        MockTransport::new().with_response(json!({"data": {"film": film("1", "A New Hope")}})),
+    );
+
+    let responses = client
+        .run_with_policy(film_query("1"), CachePolicy::CacheFirst)
+        .collect::<Vec<_>>();
+
+    assert_eq!(responses.len(), 1);
+    assert_eq!(client.transport().requests().len(), 1);
+    assert!(!client.transport().requests()[0].body["query"]
+        .as_str()
+        .unwrap()
+        .contains("__typename"));
+}
@@ -0,0 +1,121 @@
+use cynic::{GraphQlResponse, PathSegment, QueryBuilder};
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root"
+)]
+struct AllFilmsQuery {
+    all_films: Option<FilmsConnection>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct FilmsConnection {
+    films: Option<Vec<Option<Film>>>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Film {
+    title: Option<String>,
+    #[cynic(rename = "episodeID")]
+    episode_id: Option<i32>,
+}
+
+#[test]
+fn test_decoding_valid_response() {
+    let operation = AllFilmsQuery::build(());
+
+    let data = operation
+        .decode_response(GraphQlResponse {
+            data: Some(json!({
+                "allFilms": {
+                    "films": [{"title": "A New Hope", "episodeID": 4}]
+                }
+            })),
+            errors: None,
+        })
+        .unwrap()
+        .unwrap();
+
+    let films = data
+        .all_films
+        .and_then(|connection| connection.films)
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .map(|film| (film.title, film.episode_id))
+        .collect::<Vec<_>>();
+
+    assert_eq!(films, vec![(Some("A New Hope".to_string()), Some(4))]);
+}
+
+#[test]
+fn test_decode_error_has_path_and_types() {
+    let operation = AllFilmsQuery::build(());
+
+    let error = operation
+        .decode_response(GraphQlResponse {
+            data: Some(json!({
+                "allFilms": {
+                    "films": [
+                        {"title": "A New Hope", "episodeID": 4},
+                        {"title": "The Empire Strikes Back", "episodeID": "five"}
+                    ]
+                }
+            })),
+            errors: None,
+        })
+        .unwrap_err();
+
+    assert_eq!(
+        error.path(),
+        &[
+            PathSegment::Field("allFilms".into()),
+            PathSegment::Field("films".into()),
+            PathSegment::Index(1),
+            PathSegment::Field("episodeID".into()),
+        ]
+    );
+    assert_eq!(error.path_string(), "allFilms.films[1].episodeID");
+    assert_eq!(error.graphql_type(), Some("Int"));
+    assert_eq!(error.rust_type(), Some("core::option::Option<i32>"));
+    assert_eq!(
+        error.to_string(),
+        "Error decoding allFilms.films[1].episodeID (GraphQL type Int into core::option::Option<i32>): Serde error: invalid type: string \"five\", expected i32"
+    );
+}
+
+#[test]
+fn test_missing_field_error_does_not_include_response() {
+    let operation = AllFilmsQuery::build(());
+
+    let error = operation
+        .decode_response(GraphQlResponse {
+            data: Some(json!({
+                "allFilms": {
+                    "films": [{"title": "A New Hope"}]
+                }
+            })),
+            errors: None,
+        })
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Error decoding allFilms.films[0].episodeID (GraphQL type Int into core::option::Option<i32>): Could not find field episodeID in Object"
+    );
+}
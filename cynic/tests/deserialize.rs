@@ -0,0 +1,223 @@
+use cynic::{GraphQlResponse, QueryBuilder};
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::FragmentArguments)]
+struct FilmArguments {
+    with_director: bool,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root",
+    argument_struct = "FilmArguments"
+)]
+struct FilmsQuery {
+    #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+    #[cynic(rename = "film", alias)]
+    a_new_hope: Option<Film>,
+
+    #[arguments(id = cynic::Id::new("ZmlsbXM6Mg=="))]
+    #[cynic(rename = "film", alias)]
+    empire_strikes_back: Option<Film>,
+
+    #[arguments(id = cynic::Id::new("cGxhbmV0czox"))]
+    node: Option<Node>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    argument_struct = "FilmArguments"
+)]
+struct Film {
+    #[cynic(spread)]
+    details: FilmDetails,
+
+    #[cynic(flatten)]
+    producers: Vec<String>,
+
+    #[directives(include(if = args.with_director))]
+    director: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Film"
+)]
+struct FilmDetails {
+    title: Option<String>,
+    #[cynic(rename = "episodeID")]
+    episode_id: Option<i32>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Planet {
+    name: Option<String>,
+}
+
+#[derive(cynic::InlineFragments, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+enum Node {
+    Planet(Planet),
+
+    #[cynic(fallback)]
+    Other,
+}
+
+fn response() -> serde_json::Value {
+    json!({
+        "data": {
+            "a_new_hope": {
+                "title": "A New Hope",
+                "episodeID": 4,
+                "producers": ["Gary Kurtz", null, "Rick McCallum"],
+                "director": "George Lucas"
+            },
+            "empire_strikes_back": {
+                "title": "The Empire Strikes Back",
+                "episodeID": 5,
+                "producers": null
+            },
+            "node": {
+                "__typename": "Planet",
+                "name": "Tatooine"
+            }
+        }
+    })
+}
+
+#[test]
+fn test_deserializing_matches_decoding() {
+    let operation = FilmsQuery::build(FilmArguments {
+        with_director: true,
+    });
+    let body = serde_json::to_vec(&response()).unwrap();
+
+    let deserialized = operation.decode_response_slice(&body).unwrap();
+    let decoded = operation
+        .decode_response(serde_json::from_value(response()).unwrap())
+        .unwrap();
+
+    assert_eq!(deserialized.data, decoded.ok());
+    assert_eq!(
+        deserialized.data.unwrap(),
+        FilmsQuery {
+            a_new_hope: Some(Film {
+                details: FilmDetails {
+                    title: Some("A New Hope".into()),
+                    episode_id: Some(4),
+                },
+                producers: vec!["Gary Kurtz".into(), "Rick McCallum".into()],
+                director: Some("George Lucas".into()),
+            }),
+            empire_strikes_back: Some(Film {
+                details: FilmDetails {
+                    title: Some("The Empire Strikes Back".into()),
+                    episode_id: Some(5),
+                },
+                producers: vec![],
+                director: None,
+            }),
+            node: Some(Node::Planet(Planet {
+                name: Some("Tatooine".into())
+            })),
+        }
+    );
+}
+
+#[test]
+fn test_deserializing_inline_fragment_fallback() {
+    let node: Node = serde_json::from_value(json!({"__typename": "Film"})).unwrap();
+
+    assert_eq!(node, Node::Other);
+}
+
+#[test]
+fn test_deserializing_missing_field() {
+    let result = serde_json::from_value::<GraphQlResponse<FilmDetails>>(json!({
+        "data": { "title": "A New Hope" }
+    }));
+
+    assert_eq!(result.unwrap_err().to_string(), "missing field `episodeID`");
+}
+
+#[test]
+fn test_deserializing_inline_fragments_without_leading_typename() {
+    let node: Node = serde_json::from_value(json!({
+        "name": "Tatooine",
+        "__typename": "Planet"
+    }))
+    .unwrap();
+
+    assert_eq!(
+        node,
+        Node::Planet(Planet {
+            name: Some("Tatooine".into())
+        })
+    );
+}
+
+/// A scalar without a `Deserialize` impl, that can only be decoded via
+/// `Scalar::from_deserialize`
+#[derive(serde::Serialize, Debug, PartialEq)]
+struct Shouty(String);
+
+impl cynic::Scalar<String> for Shouty {
+    type Deserialize = String;
+
+    fn from_deserialize(x: String) -> Result<Self, cynic::DecodeError> {
+        Ok(Shouty(x.to_uppercase()))
+    }
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Film"
+)]
+struct ShoutyFilm {
+    title: Option<Shouty>,
+    #[cynic(rename = "episodeID")]
+    episode_id: Option<u8>,
+}
+
+#[test]
+fn test_deserializing_scalars_uses_from_deserialize() {
+    let film: ShoutyFilm = serde_json::from_value(json!({
+        "title": "A New Hope",
+        "episodeID": 4
+    }))
+    .unwrap();
+
+    assert_eq!(
+        film,
+        ShoutyFilm {
+            title: Some(Shouty("A NEW HOPE".into())),
+            episode_id: Some(4)
+        }
+    );
+
+    let result = serde_json::from_value::<ShoutyFilm>(json!({
+        "title": "A New Hope",
+        "episodeID": 400
+    }));
+
+    assert!(result.is_err());
+}
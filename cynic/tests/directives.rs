@@ -0,0 +1,114 @@
+use cynic::{FragmentArguments, QueryBuilder};
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(FragmentArguments)]
+struct FilmArguments {
+    hide_id: bool,
+    show_director: bool,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root",
+    argument_struct = "FilmArguments"
+)]
+struct FilmQuery {
+    #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+    film: Option<Film>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    argument_struct = "FilmArguments"
+)]
+struct Film {
+    #[directives(skip(if = args.hide_id))]
+    id: Option<cynic::Id>,
+
+    #[directives(include(if = args.show_director))]
+    director: Option<String>,
+
+    #[directives(skip(if = false), include(if = true))]
+    title: Option<String>,
+}
+
+#[test]
+fn test_directive_query_output() {
+    let operation = FilmQuery::build(FilmArguments {
+        hide_id: true,
+        show_director: false,
+    });
+
+    insta::assert_display_snapshot!(operation.query, @r###"
+    query FilmQuery($id: ID, $hideId: Boolean!, $showDirector: Boolean!) {
+      film(id: $id) {
+        id @skip(if: $hideId)
+        director @include(if: $showDirector)
+        title @skip(if: false) @include(if: true)
+      }
+    }
+    "###);
+
+    assert_eq!(
+        serde_json::to_value(&operation.variables).unwrap(),
+        json!({
+            "id": "ZmlsbXM6MQ==",
+            "hideId": true,
+            "showDirector": false
+        })
+    );
+}
+
+#[test]
+fn test_directive_decoding() {
+    let operation = FilmQuery::build(FilmArguments {
+        hide_id: true,
+        show_director: true,
+    });
+
+    assert_eq!(
+        operation
+            .decode_response(cynic::GraphQlResponse {
+                errors: None,
+                data: Some(json!({
+                    "film": {"director": null, "title": "A New Hope"}
+                }))
+            })
+            .unwrap()
+            .unwrap(),
+        FilmQuery {
+            film: Some(Film {
+                id: None,
+                director: None,
+                title: Some("A New Hope".into())
+            })
+        }
+    );
+
+    assert_eq!(
+        operation
+            .decode_response(cynic::GraphQlResponse {
+                errors: None,
+                data: Some(json!({
+                    "film": {"id": "ZmlsbXM6MQ==", "director": "George Lucas"}
+                }))
+            })
+            .unwrap()
+            .unwrap(),
+        FilmQuery {
+            film: Some(Film {
+                id: Some(cynic::Id::new("ZmlsbXM6MQ==")),
+                director: Some("George Lucas".into()),
+                title: None
+            })
+        }
+    );
+}
@@ -0,0 +1,119 @@
+use cynic::QueryBuilder;
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("src/bin/simple.graphql");
+}
+
+#[derive(cynic::Enum, Clone, Debug, PartialEq)]
+#[cynic(schema_path = "src/bin/simple.graphql", graphql_type = "Dessert")]
+pub enum Dessert {
+    Cheesecake,
+    IceCream,
+
+    #[cynic(fallback)]
+    Other(String),
+}
+
+#[derive(cynic::Enum, Clone, Debug, PartialEq)]
+#[cynic(schema_path = "src/bin/simple.graphql", graphql_type = "Dessert")]
+pub enum UnitDessert {
+    Cheesecake,
+    IceCream,
+
+    #[cynic(fallback)]
+    Unknown,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(schema_path = "src/bin/simple.graphql", graphql_type = "Query")]
+struct DessertQuery {
+    test_struct: Option<DessertStruct>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(schema_path = "src/bin/simple.graphql", graphql_type = "TestStruct")]
+struct DessertStruct {
+    dessert: Option<Dessert>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(schema_path = "src/bin/simple.graphql", graphql_type = "Query")]
+struct UnitDessertQuery {
+    test_struct: Option<UnitDessertStruct>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(schema_path = "src/bin/simple.graphql", graphql_type = "TestStruct")]
+struct UnitDessertStruct {
+    dessert: Option<UnitDessert>,
+}
+
+/// Decodes a response containing `dessert` with both the `SelectionSet`
+/// decoder & the `serde::Deserialize` impl, checking they agree.
+fn decode<T>(operation: cynic::Operation<'static, T>, dessert: &str) -> T
+where
+    T: serde::de::DeserializeOwned + PartialEq + std::fmt::Debug + 'static,
+{
+    let response = json!({"data": {"testStruct": {"dessert": dessert}}});
+
+    let decoded = operation
+        .decode_response(serde_json::from_value(response.clone()).unwrap())
+        .unwrap()
+        .unwrap();
+    let deserialized = operation
+        .decode_response_slice(response.to_string().as_bytes())
+        .unwrap()
+        .data
+        .unwrap();
+
+    assert_eq!(decoded, deserialized);
+    decoded
+}
+
+#[test]
+fn test_decoding_known_values() {
+    assert_eq!(
+        decode(DessertQuery::build(()), "ICE_CREAM").test_struct,
+        Some(DessertStruct {
+            dessert: Some(Dessert::IceCream)
+        })
+    );
+    assert_eq!(
+        decode(UnitDessertQuery::build(()), "ICE_CREAM").test_struct,
+        Some(UnitDessertStruct {
+            dessert: Some(UnitDessert::IceCream)
+        })
+    );
+}
+
+#[test]
+fn test_decoding_unknown_values() {
+    assert_eq!(
+        decode(DessertQuery::build(()), "TIRAMISU").test_struct,
+        Some(DessertStruct {
+            dessert: Some(Dessert::Other("TIRAMISU".into()))
+        })
+    );
+    assert_eq!(
+        decode(UnitDessertQuery::build(()), "TIRAMISU").test_struct,
+        Some(UnitDessertStruct {
+            dessert: Some(UnitDessert::Unknown)
+        })
+    );
+}
+
+#[test]
+fn test_serializing_fallbacks_fails() {
+    assert_eq!(
+        serde_json::to_value(Dessert::Cheesecake).unwrap(),
+        json!("CHEESECAKE")
+    );
+    assert_eq!(
+        serde_json::to_value(Dessert::Other("TIRAMISU".into()))
+            .unwrap_err()
+            .to_string(),
+        "Dessert::Other is a fallback variant and can't be serialized"
+    );
+    assert!(serde_json::to_value(UnitDessert::Unknown).is_err());
+}
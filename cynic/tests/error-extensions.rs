@@ -0,0 +1,110 @@
+use cynic::{
+    transport::{BlockingClient, MockTransport},
+    BatchOperation, GraphQlResponse, PossiblyParsedData, QueryBuilder,
+};
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root"
+)]
+struct FilmQuery {
+    #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+    film: Option<Film>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Film {
+    title: Option<String>,
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Extensions {
+    code: String,
+    retry_after: Option<u32>,
+}
+
+fn not_found() -> serde_json::Value {
+    json!({
+        "data": {"film": null},
+        "errors": [{
+            "message": "Film not found",
+            "path": ["film"],
+            "extensions": {"code": "NOT_FOUND", "retryAfter": 3}
+        }]
+    })
+}
+
+#[test]
+fn test_decode_response_with_extensions() {
+    let operation = FilmQuery::build(()).with_error_extensions::<Extensions>();
+
+    let response: GraphQlResponse<serde_json::Value, Extensions> =
+        serde_json::from_value(not_found()).unwrap();
+    let (data, errors) = operation.decode_response(response).unwrap().unwrap_err();
+
+    assert_eq!(
+        data,
+        PossiblyParsedData::ParsedData(FilmQuery { film: None })
+    );
+    assert_eq!(
+        errors[0].extensions,
+        Some(Extensions {
+            code: "NOT_FOUND".into(),
+            retry_after: Some(3)
+        })
+    );
+}
+
+#[test]
+fn test_client_with_extensions() {
+    let client = BlockingClient::new(MockTransport::new().with_response(not_found()));
+
+    let response = client
+        .run(FilmQuery::build(()).with_error_extensions::<Extensions>())
+        .unwrap();
+
+    assert_eq!(
+        response.errors.unwrap()[0]
+            .extensions
+            .as_ref()
+            .unwrap()
+            .code,
+        "NOT_FOUND"
+    );
+}
+
+#[test]
+fn test_batch_with_extensions() {
+    let batch = BatchOperation::from((
+        FilmQuery::build(()).with_error_extensions::<Extensions>(),
+        FilmQuery::build(()),
+    ));
+
+    let responses = serde_json::from_value(json!([not_found(), not_found()])).unwrap();
+    let (typed, untyped) = batch.decode_response(responses).unwrap();
+
+    assert_eq!(
+        typed.errors.unwrap()[0]
+            .extensions
+            .as_ref()
+            .unwrap()
+            .retry_after,
+        Some(3)
+    );
+    assert_eq!(
+        untyped.errors.unwrap()[0].extensions,
+        Some(json!({"code": "NOT_FOUND", "retryAfter": 3}))
+    );
+}
@@ -60,3 +60,40 @@ fn test_input_object_skip_serializing() {
 
     assert_eq!(with_author, json!({ "content": "hi", "author": "Me" }));
 }
+
+#[test]
+fn test_input_object_maybe_undefined() {
+    use cynic::MaybeUndefined;
+
+    #[derive(cynic::InputObject)]
+    #[cynic(
+        graphql_type = "BlogPostInput",
+        schema_path = "tests/test-schema.graphql",
+        query_module = "schema"
+    )]
+    struct BlogPost {
+        content: String,
+        author: MaybeUndefined<String>,
+    }
+
+    let serialize = |author| {
+        serde_json::to_value(BlogPost {
+            content: "hi".into(),
+            author,
+        })
+        .unwrap()
+    };
+
+    assert_eq!(
+        serialize(MaybeUndefined::Undefined),
+        json!({ "content": "hi" })
+    );
+    assert_eq!(
+        serialize(MaybeUndefined::Null),
+        json!({ "content": "hi", "author": null })
+    );
+    assert_eq!(
+        serialize(MaybeUndefined::Value("Me".into())),
+        json!({ "content": "hi", "author": "Me" })
+    );
+}
@@ -0,0 +1,209 @@
+use cynic::{DecodeError, QueryBuilder};
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("tests/integers-schema.graphql");
+
+    impl cynic::IsIntegerScalar for Long {}
+    impl cynic::IsIntegerScalar for BigInt {}
+}
+
+// Int64 doesn't implement IsIntegerScalar, so we're free to use impl_scalar!
+cynic::impl_scalar!(i64, schema::Int64);
+
+#[derive(cynic::FragmentArguments)]
+struct StatsArguments {
+    since: Option<u64>,
+    limit: Option<usize>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/integers-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Query",
+    argument_struct = "StatsArguments"
+)]
+struct StatsQuery {
+    #[arguments(since = args.since, limit = args.limit)]
+    stats: Stats,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(schema_path = "tests/integers-schema.graphql", query_module = "schema")]
+struct Stats {
+    count: u32,
+    percentage: Option<u8>,
+    views: i64,
+    downloads: Option<u64>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/integers-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Query"
+)]
+struct LiteralArgumentsQuery {
+    #[arguments(since = 1_600_000_000, limit = 10)]
+    stats: SmallStats,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/integers-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Stats"
+)]
+struct SmallStats {
+    count: i16,
+    views: i32,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/integers-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Query"
+)]
+struct UpdatedAtQuery {
+    stats: UpdatedAtStats,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/integers-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Stats"
+)]
+struct UpdatedAtStats {
+    updated_at: i64,
+}
+
+fn stats_query() -> cynic::Operation<'static, StatsQuery> {
+    StatsQuery::build(StatsArguments {
+        since: Some(1_600_000_000_000),
+        limit: Some(10),
+    })
+}
+
+fn decode<T: std::fmt::Debug + 'static>(
+    operation: &cynic::Operation<'static, T>,
+    data: serde_json::Value,
+) -> Result<T, cynic::ResponseDecodeError> {
+    operation
+        .decode_response(cynic::GraphQlResponse {
+            data: Some(data),
+            errors: None,
+        })
+        .map(|result| result.unwrap())
+}
+
+#[test]
+fn test_decoding_wider_integers() {
+    let data = decode(
+        &stats_query(),
+        json!({
+            "stats": {
+                "count": 2_000_000_000,
+                "percentage": 99,
+                "views": 9_000_000_000i64,
+                "downloads": 18_000_000_000_000_000_000u64
+            }
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        data.stats,
+        Stats {
+            count: 2_000_000_000,
+            percentage: Some(99),
+            views: 9_000_000_000,
+            downloads: Some(18_000_000_000_000_000_000),
+        }
+    );
+}
+
+#[test]
+fn test_impl_scalar_for_integer_scalars() {
+    let data = decode(
+        &UpdatedAtQuery::build(()),
+        json!({"stats": {"updatedAt": 1_600_000_000_000i64}}),
+    )
+    .unwrap();
+
+    assert_eq!(data.stats.updated_at, 1_600_000_000_000);
+}
+
+#[test]
+fn test_decoding_out_of_range_integers() {
+    let result = decode(
+        &stats_query(),
+        json!({
+            "stats": {"count": -1, "percentage": null, "views": 0, "downloads": null}
+        }),
+    );
+
+    assert_eq!(
+        result.unwrap_err().decode_error(),
+        &DecodeError::IntegerOverflow("-1".into(), "u32")
+    );
+
+    let result = decode(
+        &LiteralArgumentsQuery::build(()),
+        json!({"stats": {"count": 1, "views": 9_000_000_000i64}}),
+    );
+
+    assert_eq!(
+        result.unwrap_err().decode_error(),
+        &DecodeError::IntegerOverflow("9000000000".into(), "i32")
+    );
+}
+
+#[test]
+fn test_decoding_non_integers() {
+    let result = decode(
+        &stats_query(),
+        json!({
+            "stats": {"count": 1.5, "percentage": null, "views": 0, "downloads": null}
+        }),
+    );
+
+    assert_eq!(
+        result.unwrap_err().decode_error(),
+        &DecodeError::InvalidInteger("1.5".into())
+    );
+}
+
+#[test]
+fn test_wider_integer_arguments() {
+    let operation = stats_query();
+
+    insta::assert_display_snapshot!(operation.query, @r###"
+    query StatsQuery($since: Long, $limit: Int) {
+      stats(since: $since, limit: $limit) {
+        count
+        percentage
+        views
+        downloads
+      }
+    }
+    "###);
+    assert_eq!(
+        serde_json::to_value(&operation.variables).unwrap(),
+        json!({"since": 1_600_000_000_000u64, "limit": 10})
+    );
+}
+
+#[test]
+fn test_literal_integer_arguments() {
+    insta::assert_display_snapshot!(LiteralArgumentsQuery::build(()).query, @r###"
+    query LiteralArgumentsQuery {
+      stats(since: 1600000000, limit: 10) {
+        count
+        views
+      }
+    }
+    "###);
+}
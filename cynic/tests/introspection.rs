@@ -0,0 +1,70 @@
+use cynic::{introspection::IntrospectionQuery, GraphQlResponse, QueryBuilder};
+
+mod schema {
+    cynic::use_schema!("../schemas/books.introspection.json");
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/books.introspection.json",
+    query_module = "schema",
+    graphql_type = "QueryRoot"
+)]
+struct BooksQuery {
+    books: Vec<Book>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/books.introspection.json",
+    query_module = "schema"
+)]
+struct Book {
+    id: String,
+    name: String,
+}
+
+#[test]
+fn test_querying_with_an_introspection_schema() {
+    let operation = BooksQuery::build(());
+
+    insta::assert_snapshot!(operation.query, @r###"
+    query BooksQuery {
+      books {
+        id
+        name
+      }
+    }
+    "###);
+}
+
+#[test]
+fn test_introspection_query_round_trip() {
+    let json = include_str!("../../schemas/books.introspection.json");
+
+    let operation = IntrospectionQuery::build(());
+    let data = operation
+        .decode_response(serde_json::from_str::<GraphQlResponse<serde_json::Value>>(json).unwrap())
+        .unwrap()
+        .unwrap();
+
+    let reserialized = serde_json::to_string(&data).unwrap();
+
+    assert_eq!(
+        cynic_codegen::parse_introspection_json(&reserialized).unwrap(),
+        cynic_codegen::parse_introspection_json(json).unwrap()
+    );
+}
+
+#[test]
+fn test_introspection_query_deserialize() {
+    let json = include_str!("../../schemas/books.introspection.json");
+
+    let operation = IntrospectionQuery::build(());
+    let decoded = operation
+        .decode_response(serde_json::from_str::<GraphQlResponse<serde_json::Value>>(json).unwrap())
+        .unwrap();
+    let deserialized = operation.decode_response_slice(json.as_bytes()).unwrap();
+
+    assert_eq!(deserialized.data, decoded.ok());
+}
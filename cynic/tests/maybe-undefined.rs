@@ -0,0 +1,90 @@
+use cynic::{GraphQlResponse, MaybeUndefined, QueryBuilder};
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::FragmentArguments)]
+struct FilmsArguments {
+    after: MaybeUndefined<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root",
+    argument_struct = "FilmsArguments"
+)]
+struct AllFilmsQuery {
+    #[arguments(after = args.after.clone(), first = MaybeUndefined::<i32>::Undefined)]
+    all_films: Option<FilmsConnection>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct FilmsConnection {
+    total_count: Option<i32>,
+}
+
+fn build(after: MaybeUndefined<String>) -> cynic::Operation<'static, AllFilmsQuery> {
+    AllFilmsQuery::build(FilmsArguments { after })
+}
+
+#[test]
+fn test_undefined_arguments_are_omitted() {
+    let operation = build(MaybeUndefined::Undefined);
+
+    insta::assert_display_snapshot!(operation.query, @r###"
+    query AllFilmsQuery {
+      allFilms {
+        totalCount
+      }
+    }
+    "###);
+    assert_eq!(
+        serde_json::to_value(&operation.variables).unwrap(),
+        json!({})
+    );
+}
+
+#[test]
+fn test_null_arguments() {
+    let operation = build(MaybeUndefined::Null);
+
+    insta::assert_display_snapshot!(operation.query, @r###"
+    query AllFilmsQuery($after: String) {
+      allFilms(after: $after) {
+        totalCount
+      }
+    }
+    "###);
+    assert_eq!(
+        serde_json::to_value(&operation.variables).unwrap(),
+        json!({ "after": null })
+    );
+}
+
+#[test]
+fn test_defined_arguments() {
+    let operation = build(MaybeUndefined::Value("YXJyYXljb25uZWN0aW9uOjI=".into()));
+
+    assert_eq!(
+        serde_json::to_value(&operation.variables).unwrap(),
+        json!({ "after": "YXJyYXljb25uZWN0aW9uOjI=" })
+    );
+
+    let data = operation
+        .decode_response(GraphQlResponse {
+            data: Some(json!({"allFilms": {"totalCount": 6}})),
+            errors: None,
+        })
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(data.all_films.and_then(|films| films.total_count), Some(6));
+}
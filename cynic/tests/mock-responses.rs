@@ -0,0 +1,266 @@
+#![cfg(feature = "testing")]
+
+use cynic::{
+    testing::{GeneratorError, ResponseGenerator},
+    QueryBuilder,
+};
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::FragmentArguments)]
+struct FilmArguments {
+    with_director: bool,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root",
+    argument_struct = "FilmArguments"
+)]
+struct FilmsQuery {
+    #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+    #[cynic(rename = "film", alias)]
+    a_new_hope: Option<Film>,
+
+    #[arguments(id = cynic::Id::new("cGxhbmV0czox"))]
+    node: Option<Node>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    argument_struct = "FilmArguments"
+)]
+struct Film {
+    #[cynic(spread)]
+    details: FilmDetails,
+
+    producers: Option<Vec<Option<String>>>,
+
+    #[directives(include(if = args.with_director))]
+    director: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Film"
+)]
+struct FilmDetails {
+    title: Option<String>,
+    #[cynic(rename = "episodeID")]
+    episode_id: Option<i32>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Planet {
+    name: Option<String>,
+}
+
+#[derive(cynic::InlineFragments, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+enum Node {
+    Planet(Planet),
+
+    #[cynic(fallback)]
+    Other,
+}
+
+fn generator() -> ResponseGenerator {
+    ResponseGenerator::new(include_str!("../../schemas/starwars.schema.graphql")).unwrap()
+}
+
+#[test]
+fn test_generating_default_response() {
+    let operation = FilmsQuery::build(FilmArguments {
+        with_director: true,
+    });
+
+    let response = generator().generate(&operation).unwrap();
+
+    assert_eq!(
+        response.data,
+        Some(json!({
+            "a_new_hope": {
+                "title": "",
+                "episodeID": 0,
+                "producers": [""],
+                "director": ""
+            },
+            "node": {
+                "__typename": "Planet",
+                "name": ""
+            }
+        }))
+    );
+
+    assert_eq!(
+        operation.decode_response(response).unwrap().unwrap(),
+        FilmsQuery {
+            a_new_hope: Some(Film {
+                details: FilmDetails {
+                    title: Some("".into()),
+                    episode_id: Some(0),
+                },
+                producers: Some(vec![Some("".into())]),
+                director: Some("".into()),
+            }),
+            node: Some(Node::Planet(Planet {
+                name: Some("".into())
+            })),
+        }
+    );
+}
+
+#[test]
+fn test_generating_respects_directives() {
+    let operation = FilmsQuery::build(FilmArguments {
+        with_director: false,
+    });
+
+    let response = generator().generate(&operation).unwrap();
+
+    assert!(response.data.as_ref().unwrap()["a_new_hope"]
+        .get("director")
+        .is_none());
+    operation.decode_response(response).unwrap().unwrap();
+}
+
+#[test]
+fn test_random_responses_decode() {
+    for seed in 0..50 {
+        let operation = FilmsQuery::build(FilmArguments {
+            with_director: true,
+        });
+
+        let generator = generator().with_random_values(seed);
+        let response = generator.generate(&operation).unwrap();
+        assert_eq!(
+            response.data,
+            generator.generate(&operation).unwrap().data,
+            "the same seed should generate the same response"
+        );
+
+        match operation.decode_response(response) {
+            Ok(Ok(_)) => {}
+            Ok(Err((_, errors))) => panic!("Response for seed {} had errors: {:?}", seed, errors),
+            Err(error) => panic!("Could not decode response for seed {}: {}", seed, error),
+        }
+    }
+}
+
+#[test]
+fn test_generating_with_custom_scalars() {
+    let generator = generator().with_scalar("String", json!("Hello"));
+    let operation = FilmsQuery::build(FilmArguments {
+        with_director: true,
+    });
+
+    let data = generator.generate(&operation).unwrap().data.unwrap();
+
+    assert_eq!(data["a_new_hope"]["title"], json!("Hello"));
+    assert_eq!(data["node"]["name"], json!("Hello"));
+}
+
+#[test]
+fn test_generating_with_invalid_schema() {
+    assert_matches::assert_matches!(
+        ResponseGenerator::new("type Query {").err(),
+        Some(GeneratorError::SchemaParse(_))
+    );
+}
+
+#[test]
+fn test_generating_enum_without_values() {
+    // Film.title is the first `title: String` in the schema
+    let schema = include_str!("../../schemas/starwars.schema.graphql").replacen(
+        "title: String",
+        "title: Title",
+        1,
+    ) + "\nenum Title\n";
+    let generator = ResponseGenerator::new(&schema).unwrap();
+    let operation = FilmsQuery::build(FilmArguments {
+        with_director: true,
+    });
+
+    assert_matches::assert_matches!(
+        generator.generate(&operation).err(),
+        Some(GeneratorError::NoEnumValues(name)) if name == "Title"
+    );
+}
+
+mod recursive {
+    use super::*;
+
+    mod schema {
+        cynic::use_schema!("tests/test-schema.graphql");
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(
+        graphql_type = "Query",
+        schema_path = "tests/test-schema.graphql",
+        query_module = "schema"
+    )]
+    struct AllPostsQuery {
+        all_posts: Vec<Post>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(
+        graphql_type = "BlogPost",
+        schema_path = "tests/test-schema.graphql",
+        query_module = "schema"
+    )]
+    struct Post {
+        comments: Vec<Comment>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(schema_path = "tests/test-schema.graphql", query_module = "schema")]
+    struct Comment {
+        author: Author,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(schema_path = "tests/test-schema.graphql", query_module = "schema")]
+    struct Author {
+        #[cynic(recurse = "2")]
+        posts: Option<Vec<Post>>,
+    }
+
+    fn depth(posts: &[Post]) -> usize {
+        posts
+            .iter()
+            .flat_map(|post| &post.comments)
+            .filter_map(|comment| comment.author.posts.as_deref())
+            .map(|posts| depth(posts) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_generating_recursive_response() {
+        let generator = ResponseGenerator::new(include_str!("test-schema.graphql")).unwrap();
+        let operation = AllPostsQuery::build(());
+
+        let response = generator.generate(&operation).unwrap();
+        let data = operation.decode_response(response).unwrap().unwrap();
+
+        assert_eq!(depth(&data.all_posts), 2);
+    }
+}
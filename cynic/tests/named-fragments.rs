@@ -0,0 +1,108 @@
+use cynic::{GraphQlResponse, QueryBuilder};
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root"
+)]
+struct FilmsQuery {
+    #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+    #[cynic(rename = "film", alias)]
+    a_new_hope: Option<Film>,
+
+    #[arguments(id = cynic::Id::new("ZmlsbXM6Mg=="))]
+    #[cynic(rename = "film", alias)]
+    empire_strikes_back: Option<Film>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Film {
+    title: Option<String>,
+    producers: Option<Vec<Option<String>>>,
+    #[arguments(first = 1)]
+    planet_connection: Option<FilmPlanetsConnection>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct FilmPlanetsConnection {
+    total_count: Option<i32>,
+}
+
+#[test]
+fn test_named_fragments_query_output() {
+    let operation = FilmsQuery::build_with_named_fragments(());
+
+    insta::assert_display_snapshot!(operation.query, @r###"
+    query FilmsQuery($id: ID, $id2: ID) {
+      a_new_hope: film(id: $id) {
+        ...Film
+      }
+      empire_strikes_back: film(id: $id2) {
+        ...Film
+      }
+    }
+
+    fragment Film on Film {
+      title
+      producers
+      planetConnection(first: 1) {
+        totalCount
+      }
+    }
+    "###);
+}
+
+#[test]
+fn test_build_inlines_fragments() {
+    let operation = FilmsQuery::build(());
+
+    assert!(!operation.query.contains("fragment"));
+    assert_eq!(operation.query.matches("producers").count(), 2);
+}
+
+#[test]
+fn test_named_fragments_decoding() {
+    let operation = FilmsQuery::build_with_named_fragments(());
+
+    let film = |title: &str| {
+        json!({
+            "title": title,
+            "producers": ["Gary Kurtz"],
+            "planetConnection": {"totalCount": 3}
+        })
+    };
+    let response: GraphQlResponse<serde_json::Value> = serde_json::from_value(json!({
+        "data": {
+            "a_new_hope": film("A New Hope"),
+            "empire_strikes_back": film("The Empire Strikes Back")
+        }
+    }))
+    .unwrap();
+
+    let data = operation.decode_response(response).unwrap().unwrap();
+
+    assert_eq!(
+        data.empire_strikes_back.unwrap().title,
+        Some("The Empire Strikes Back".into())
+    );
+    assert_eq!(
+        data.a_new_hope.unwrap().planet_connection,
+        Some(FilmPlanetsConnection {
+            total_count: Some(3)
+        })
+    );
+}
@@ -0,0 +1,94 @@
+use cynic::{GraphQlResponse, QueryBuilder};
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("tests/one-of-schema.graphql");
+}
+
+#[derive(cynic::InputObject, Clone, Debug)]
+#[cynic(schema_path = "tests/one-of-schema.graphql")]
+enum UserBy {
+    Id(cynic::Id),
+    Email(String),
+    #[cynic(rename = "username")]
+    Name(String),
+    Profile(ProfileInput),
+}
+
+#[derive(cynic::InputObject, Clone, Debug)]
+#[cynic(schema_path = "tests/one-of-schema.graphql")]
+struct ProfileInput {
+    name: String,
+}
+
+#[derive(cynic::FragmentArguments)]
+struct UserArguments {
+    by: UserBy,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "tests/one-of-schema.graphql",
+    graphql_type = "Query",
+    argument_struct = "UserArguments"
+)]
+struct UserQuery {
+    #[arguments(by = args.by.clone())]
+    user: Option<User>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "tests/one-of-schema.graphql")]
+struct User {
+    id: cynic::Id,
+}
+
+#[test]
+fn test_one_of_serialization() {
+    assert_eq!(
+        serde_json::to_value(UserBy::Id(cynic::Id::new("1"))).unwrap(),
+        json!({"id": "1"})
+    );
+    assert_eq!(
+        serde_json::to_value(UserBy::Email("me@example.com".into())).unwrap(),
+        json!({"email": "me@example.com"})
+    );
+    assert_eq!(
+        serde_json::to_value(UserBy::Name("me".into())).unwrap(),
+        json!({"username": "me"})
+    );
+    assert_eq!(
+        serde_json::to_value(UserBy::Profile(ProfileInput { name: "Me".into() })).unwrap(),
+        json!({"profile": {"name": "Me"}})
+    );
+}
+
+#[test]
+fn test_one_of_arguments() {
+    let operation = UserQuery::build(UserArguments {
+        by: UserBy::Email("me@example.com".into()),
+    });
+
+    insta::assert_display_snapshot!(operation.query, @r###"
+    query UserQuery($by: UserBy!) {
+      user(by: $by) {
+        id
+      }
+    }
+
+    "###);
+    assert_eq!(
+        serde_json::to_value(&operation.variables).unwrap(),
+        json!({"by": {"email": "me@example.com"}})
+    );
+
+    let data = operation
+        .decode_response(GraphQlResponse {
+            data: Some(json!({"user": {"id": "1"}})),
+            errors: None,
+        })
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(data.user.map(|user| user.id), Some(cynic::Id::new("1")));
+}
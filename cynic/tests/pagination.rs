@@ -0,0 +1,185 @@
+use cynic::{
+    relay::{Connection, CursorArguments, Edge, PageInfo},
+    transport::{BlockingClient, Client, MockTransport},
+    QueryBuilder,
+};
+use futures_util::StreamExt;
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::FragmentArguments, Clone)]
+struct FilmsArguments {
+    after: Option<String>,
+}
+
+impl CursorArguments for FilmsArguments {
+    fn set_after(&mut self, cursor: Option<String>) {
+        self.after = cursor;
+    }
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root",
+    argument_struct = "FilmsArguments"
+)]
+struct AllFilmsQuery {
+    #[arguments(first = 2, after = args.after.clone())]
+    all_films: Option<Connection<Film, schema::FilmsConnection>>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Film {
+    title: Option<String>,
+}
+
+fn page(titles: &[&str], end_cursor: &str, has_next_page: bool) -> serde_json::Value {
+    json!({
+        "data": {
+            "allFilms": {
+                "edges": titles
+                    .iter()
+                    .map(|title| json!({"node": {"title": title}}))
+                    .collect::<Vec<_>>(),
+                "pageInfo": {"endCursor": end_cursor, "hasNextPage": has_next_page}
+            }
+        }
+    })
+}
+
+fn transport() -> MockTransport {
+    MockTransport::new()
+        .with_response(page(&["A New Hope", "The Empire Strikes Back"], "2", true))
+        .with_response(page(&["Return of the Jedi"], "3", false))
+}
+
+fn page_info(query: &AllFilmsQuery) -> Option<&PageInfo> {
+    query.all_films.as_ref().map(|films| &films.page_info)
+}
+
+fn titles(pages: Vec<AllFilmsQuery>) -> Vec<String> {
+    pages
+        .into_iter()
+        .flat_map(|page| page.all_films.unwrap().into_nodes())
+        .flat_map(|film| film.title)
+        .collect()
+}
+
+#[test]
+fn test_connection_query_output() {
+    let operation = AllFilmsQuery::build(FilmsArguments { after: None });
+
+    insta::assert_display_snapshot!(operation.query, @r###"
+    query AllFilmsQuery($after: String) {
+      allFilms(after: $after, first: 2) {
+        edges {
+          node {
+            title
+          }
+        }
+        pageInfo {
+          hasNextPage
+          endCursor
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn test_connection_decoding() {
+    let operation = AllFilmsQuery::build(FilmsArguments { after: None });
+    let body = page(&["A New Hope"], "1", false);
+
+    let decoded = operation
+        .decode_response(serde_json::from_value(body.clone()).unwrap())
+        .unwrap();
+    let deserialized = operation
+        .decode_response_slice(&serde_json::to_vec(&body).unwrap())
+        .unwrap();
+
+    let expected = AllFilmsQuery {
+        all_films: Some(Connection::new(
+            vec![Edge {
+                node: Some(Film {
+                    title: Some("A New Hope".into()),
+                }),
+            }],
+            PageInfo {
+                has_next_page: false,
+                end_cursor: Some("1".into()),
+            },
+        )),
+    };
+    assert_eq!(decoded.unwrap(), expected);
+    assert_eq!(deserialized.data.unwrap(), expected);
+}
+
+#[test]
+fn test_blocking_pagination() {
+    let client = BlockingClient::new(transport());
+
+    let pages = client
+        .paginate::<AllFilmsQuery, _>(FilmsArguments { after: None }, page_info)
+        .map(|page| page.unwrap().data.unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        titles(pages),
+        vec![
+            "A New Hope",
+            "The Empire Strikes Back",
+            "Return of the Jedi"
+        ]
+    );
+
+    let afters = client
+        .transport()
+        .requests()
+        .iter()
+        .map(|request| request.body["variables"]["after"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(afters, vec![json!(null), json!("2")]);
+}
+
+#[tokio::test]
+async fn test_async_pagination() {
+    let client = Client::new(transport());
+
+    let pages = client
+        .paginate::<AllFilmsQuery, _>(FilmsArguments { after: None }, page_info)
+        .map(|page| page.unwrap().data.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(
+        titles(pages),
+        vec![
+            "A New Hope",
+            "The Empire Strikes Back",
+            "Return of the Jedi"
+        ]
+    );
+    assert_eq!(client.transport().requests().len(), 2);
+}
+
+#[test]
+fn test_pagination_stops_without_page_info() {
+    let client = BlockingClient::new(MockTransport::new().with_response(json!({"data": null})));
+
+    let pages = client
+        .paginate::<AllFilmsQuery, _>(FilmsArguments { after: None }, page_info)
+        .collect::<Vec<_>>();
+
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].as_ref().unwrap().data, None);
+}
@@ -0,0 +1,120 @@
+use cynic::{GraphQlResponse, PossiblyParsedData, QueryBuilder};
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root"
+)]
+struct AllFilmsQuery {
+    all_films: Option<FilmsConnection>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct FilmsConnection {
+    films: Option<Vec<Option<Film>>>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Film {
+    id: cynic::Id,
+    title: Option<String>,
+}
+
+fn response(value: serde_json::Value) -> GraphQlResponse<serde_json::Value> {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_decode_result_without_errors() {
+    let result = AllFilmsQuery::build(())
+        .decode_response(response(json!({
+            "data": {"allFilms": {"films": [{"id": "1", "title": "A New Hope"}]}}
+        })))
+        .unwrap();
+
+    assert_eq!(
+        result,
+        Ok(AllFilmsQuery {
+            all_films: Some(FilmsConnection {
+                films: Some(vec![Some(Film {
+                    id: cynic::Id::new("1"),
+                    title: Some("A New Hope".into())
+                })])
+            })
+        })
+    );
+}
+
+#[test]
+fn test_decode_result_nulls_errored_fields() {
+    let (data, errors) = AllFilmsQuery::build(())
+        .decode_response(response(json!({
+            "data": {
+                "allFilms": {
+                    "films": [
+                        {"id": "1", "title": null},
+                        {"id": null, "title": "The Empire Strikes Back"}
+                    ]
+                }
+            },
+            "errors": [
+                {"message": "No title", "path": ["allFilms", "films", 0, "title"]},
+                {"message": "No id", "path": ["allFilms", "films", 1, "id"]}
+            ]
+        })))
+        .unwrap()
+        .unwrap_err();
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(
+        data,
+        PossiblyParsedData::ParsedData(AllFilmsQuery {
+            all_films: Some(FilmsConnection {
+                films: Some(vec![
+                    Some(Film {
+                        id: cynic::Id::new("1"),
+                        title: None
+                    }),
+                    None
+                ])
+            })
+        })
+    );
+}
+
+#[test]
+fn test_decode_result_keeps_unparsed_data() {
+    let data = json!({"allFilms": {"films": [{"title": "A New Hope"}]}});
+
+    let (possibly_parsed, errors) = AllFilmsQuery::build(())
+        .decode_response(response(json!({
+            "data": data,
+            "errors": [{"message": "Something went wrong"}]
+        })))
+        .unwrap()
+        .unwrap_err();
+
+    assert_eq!(errors[0].message, "Something went wrong");
+    assert_eq!(possibly_parsed, PossiblyParsedData::UnparsedData(data));
+}
+
+#[test]
+fn test_decode_result_without_data_or_errors() {
+    assert!(AllFilmsQuery::build(())
+        .decode_response(response(json!({ "data": null })))
+        .is_err());
+}
@@ -0,0 +1,195 @@
+#![cfg(feature = "reqwest-blocking")]
+
+use cynic::{http::ReqwestBlockingExt, QueryBuilder};
+use serde_json::{json, Value};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::mpsc,
+};
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root"
+)]
+struct FilmQuery {
+    #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+    film: Option<Film>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Film {
+    title: Option<String>,
+}
+
+/// Starts a server that behaves like a server with an empty persisted query
+/// cache: it rejects any request without a query, and records the body of
+/// every request it receives.
+fn start_server() -> (String, mpsc::Receiver<Value>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut reader = BufReader::new(stream.unwrap());
+            while let Some(body) = read_request_body(&mut reader) {
+                let response = respond(&body).to_string();
+                sender.send(body).unwrap();
+
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        }
+    });
+
+    (url, receiver)
+}
+
+/// The response of a server with an empty persisted query cache.
+fn respond(body: &Value) -> Value {
+    if body.get("query").is_some() {
+        json!({"data": {"film": {"title": "A New Hope"}}})
+    } else {
+        json!({"errors": [{"message": "PersistedQueryNotFound"}]})
+    }
+}
+
+fn read_request_body(reader: &mut impl BufRead) -> Option<Value> {
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap() == 0 {
+            return None;
+        }
+        if line == "\r\n" {
+            break;
+        }
+        if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+            content_length = length.trim().parse().unwrap();
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    Some(serde_json::from_slice(&body).unwrap())
+}
+
+#[test]
+fn test_persisted_query_retries_with_query() {
+    let (url, requests) = start_server();
+
+    let operation = FilmQuery::build(()).with_automatic_persisted_queries();
+    let hash = operation.persisted_query_hash().unwrap().to_string();
+    let query = operation.query.clone();
+
+    let response = reqwest::blocking::Client::new()
+        .post(&url)
+        .run_graphql(operation)
+        .unwrap();
+
+    assert_eq!(
+        response.data,
+        Some(FilmQuery {
+            film: Some(Film {
+                title: Some("A New Hope".into())
+            })
+        })
+    );
+
+    let extensions = json!({"persistedQuery": {"version": 1, "sha256Hash": hash}});
+
+    let first_request = requests.recv().unwrap();
+    assert_eq!(first_request.get("query"), None);
+    assert_eq!(first_request["extensions"], extensions);
+
+    let second_request = requests.recv().unwrap();
+    assert_eq!(second_request["query"], json!(query));
+    assert_eq!(second_request["extensions"], extensions);
+}
+
+#[test]
+fn test_no_retry_without_persisted_queries() {
+    let (url, requests) = start_server();
+
+    reqwest::blocking::Client::new()
+        .post(&url)
+        .run_graphql(FilmQuery::build(()))
+        .unwrap();
+
+    let request = requests.recv().unwrap();
+    assert!(request.get("query").is_some());
+    assert_eq!(request.get("extensions"), None);
+    assert!(requests.try_recv().is_err());
+}
+
+/// A surf backend that responds like `start_server`, recording the
+/// `Authorization` header & body of every request it receives.
+#[cfg(feature = "surf")]
+#[derive(Clone, Debug, Default)]
+struct SurfBackend(std::sync::Arc<std::sync::Mutex<Vec<(String, Value)>>>);
+
+#[cfg(feature = "surf")]
+#[surf::utils::async_trait]
+impl surf::HttpClient for SurfBackend {
+    async fn send(
+        &self,
+        mut request: surf::http::Request,
+    ) -> Result<surf::http::Response, surf::Error> {
+        let authorization = request["Authorization"].as_str().to_string();
+        let body: Value = request.body_json().await?;
+        let mut response = surf::http::Response::new(surf::StatusCode::Ok);
+        response.set_body(respond(&body));
+        self.0.lock().unwrap().push((authorization, body));
+
+        Ok(response)
+    }
+}
+
+#[cfg(feature = "surf")]
+#[tokio::test]
+async fn test_surf_ext_retries_with_query() {
+    use cynic::http::SurfExt;
+
+    let backend = SurfBackend::default();
+    let operation = FilmQuery::build(()).with_automatic_persisted_queries();
+    let query = operation.query.clone();
+
+    let response = surf::Client::with_http_client(backend.clone())
+        .post("http://example.com/graphql")
+        .header("Authorization", "Bearer a-token")
+        .run_graphql(operation)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response.data,
+        Some(FilmQuery {
+            film: Some(Film {
+                title: Some("A New Hope".into())
+            })
+        })
+    );
+
+    let requests = backend.0.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].0, "Bearer a-token");
+    assert_eq!(requests[0].1.get("query"), None);
+    assert_eq!(requests[1].0, "Bearer a-token");
+    assert_eq!(requests[1].1["query"], json!(query));
+}
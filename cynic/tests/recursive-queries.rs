@@ -61,7 +61,7 @@ mod recursive_lists {
 
         let query = Operation::query(AllPostsQuery::fragment(FragmentContext::empty()));
 
-        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().data)
+        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().ok())
     }
 
     #[test]
@@ -77,7 +77,7 @@ mod recursive_lists {
 
         let query = Operation::query(AllPostsQuery::fragment(FragmentContext::empty()));
 
-        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().data)
+        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().ok())
     }
 
     fn posts(inner_posts: serde_json::Value) -> serde_json::Value {
@@ -148,7 +148,36 @@ mod optional_recursive_types {
 
         let query = Operation::query(FriendsQuery::fragment(FragmentContext::empty()));
 
-        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().data)
+        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().ok())
+    }
+
+    #[test]
+    fn test_friends_deserializing_with_matching_depth() {
+        use cynic::{FragmentContext, GraphQlResponse, Operation};
+
+        let response = json!({
+            "data": {
+                "allAuthors":
+                    authors(
+                        authors(authors(null!(), null!()), author(null!(), null!())),
+                        author(authors(null!(), null!()), author(null!(), null!())),
+                    )
+            }
+        });
+
+        let query = Operation::query(FriendsQuery::fragment(FragmentContext::empty()));
+
+        let deserialized = query
+            .decode_response_slice(response.to_string().as_bytes())
+            .unwrap();
+        let decoded = query
+            .decode_response(serde_json::from_value::<GraphQlResponse<_>>(response).unwrap())
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(deserialized.data).unwrap(),
+            serde_json::to_value(decoded.ok()).unwrap()
+        );
     }
 
     #[test]
@@ -165,7 +194,7 @@ mod optional_recursive_types {
 
         let query = Operation::query(FriendsQuery::fragment(FragmentContext::empty()));
 
-        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().data)
+        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().ok())
     }
 
     #[test]
@@ -182,7 +211,7 @@ mod optional_recursive_types {
 
         let query = Operation::query(FriendsQuery::fragment(FragmentContext::empty()));
 
-        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().data)
+        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().ok())
     }
 
     fn authors(
@@ -247,7 +276,7 @@ mod required_recursive_types {
 
         let query = Operation::query(FriendsQuery::fragment(FragmentContext::empty()));
 
-        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().data)
+        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().ok())
     }
 
     #[test]
@@ -262,7 +291,7 @@ mod required_recursive_types {
 
         let query = Operation::query(FriendsQuery::fragment(FragmentContext::empty()));
 
-        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().data)
+        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().ok())
     }
 
     #[test]
@@ -278,7 +307,7 @@ mod required_recursive_types {
 
         let query = Operation::query(FriendsQuery::fragment(FragmentContext::empty()));
 
-        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().data)
+        insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().ok())
     }
 
     fn authors(me: serde_json::Value) -> serde_json::Value {
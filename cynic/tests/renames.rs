@@ -56,7 +56,7 @@ fn test_decoding() {
 
     let query = Operation::query(AllPostsQuery::fragment(FragmentContext::empty()));
 
-    insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().data)
+    insta::assert_yaml_snapshot!(query.decode_response(data).unwrap().ok())
 }
 
 fn posts() -> serde_json::Value {
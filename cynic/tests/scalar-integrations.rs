@@ -0,0 +1,208 @@
+#![cfg(all(
+    feature = "chrono",
+    feature = "uuid",
+    feature = "url",
+    feature = "rust_decimal",
+    feature = "bigdecimal",
+    feature = "time",
+    feature = "bytes"
+))]
+
+use bigdecimal::BigDecimal;
+use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
+use cynic::{Base64Bytes, QueryBuilder, Rfc3339DateTime};
+use rust_decimal::Decimal;
+use serde_json::json;
+use url::Url;
+use uuid::Uuid;
+
+mod schema {
+    cynic::use_schema!("tests/scalars-schema.graphql");
+
+    impl cynic::IsScalar for DateTime {}
+    impl cynic::IsScalar for Date {}
+    impl cynic::IsScalar for Uuid {}
+    impl cynic::IsScalar for Url {}
+    impl cynic::IsScalar for Decimal {}
+    impl cynic::IsScalar for Base64 {}
+}
+
+type DateTime = chrono::DateTime<Utc>;
+
+// Timestamp doesn't implement IsScalar, so we're free to use impl_scalar!
+// with a type that the chrono feature supports.
+cynic::impl_scalar!(NaiveDateTime, schema::Timestamp);
+
+#[derive(cynic::FragmentArguments)]
+struct OrderArguments {
+    id: Uuid,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/scalars-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Query",
+    argument_struct = "OrderArguments"
+)]
+struct OrderQuery {
+    #[arguments(id = args.id)]
+    order: Option<Order>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(schema_path = "tests/scalars-schema.graphql", query_module = "schema")]
+struct Order {
+    id: Uuid,
+    placed_at: DateTime,
+    delivery_date: Option<NaiveDate>,
+    receipt: Option<Url>,
+    total: Decimal,
+    signature: Option<Base64Bytes>,
+    updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(cynic::FragmentArguments)]
+struct OrdersSinceArguments {
+    since: DateTime,
+    ids: Option<Vec<Uuid>>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/scalars-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Query",
+    argument_struct = "OrdersSinceArguments"
+)]
+struct OrdersSinceQuery {
+    #[arguments(since = args.since, ids = args.ids.clone())]
+    orders_since: Vec<OrderId>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/scalars-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Order"
+)]
+struct OrderId {
+    id: Uuid,
+}
+
+const ORDER_ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+#[test]
+fn test_decoding_integration_scalars() {
+    let operation = OrderQuery::build(OrderArguments {
+        id: ORDER_ID.parse().unwrap(),
+    });
+
+    let data = operation
+        .decode_response(cynic::GraphQlResponse {
+            errors: None,
+            data: Some(json!({
+                "order": {
+                    "id": ORDER_ID,
+                    "placedAt": "2021-03-04T12:30:00Z",
+                    "deliveryDate": "2021-03-08",
+                    "receipt": "https://example.com/receipts/1",
+                    "total": "12.50",
+                    "signature": "aGVsbG8=",
+                    "updatedAt": "2021-03-05T09:00:00"
+                }
+            })),
+        })
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        data.order,
+        Some(Order {
+            id: ORDER_ID.parse().unwrap(),
+            placed_at: Utc.ymd(2021, 3, 4).and_hms(12, 30, 0),
+            delivery_date: Some(NaiveDate::from_ymd(2021, 3, 8)),
+            receipt: Some("https://example.com/receipts/1".parse().unwrap()),
+            total: "12.50".parse().unwrap(),
+            signature: Some(Base64Bytes(b"hello".to_vec())),
+            updated_at: Some(NaiveDate::from_ymd(2021, 3, 5).and_hms(9, 0, 0)),
+        })
+    );
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "tests/scalars-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Order"
+)]
+struct TimeOrder {
+    placed_at: Rfc3339DateTime,
+    total: BigDecimal,
+}
+
+fn march_fourth() -> time::OffsetDateTime {
+    time::Date::from_calendar_date(2021, time::Month::March, 4)
+        .unwrap()
+        .with_hms(12, 30, 0)
+        .unwrap()
+        .assume_utc()
+}
+
+#[test]
+fn test_decoding_time_and_bigdecimal_scalars() {
+    let order = serde_json::from_value::<TimeOrder>(json!({
+        "placedAt": "2021-03-04T12:30:00Z",
+        "total": "12.50"
+    }))
+    .unwrap();
+
+    assert_eq!(
+        order,
+        TimeOrder {
+            placed_at: Rfc3339DateTime(march_fourth()),
+            total: "12.50".parse().unwrap(),
+        }
+    );
+}
+
+#[test]
+fn test_rfc3339_datetimes_serialize_as_rfc3339() {
+    assert_eq!(
+        serde_json::to_value(Rfc3339DateTime(march_fourth())).unwrap(),
+        json!("2021-03-04T12:30:00Z")
+    );
+}
+
+#[test]
+fn test_integration_scalars_as_arguments() {
+    let operation = OrdersSinceQuery::build(OrdersSinceArguments {
+        since: Utc.ymd(2021, 3, 4).and_hms(12, 30, 0),
+        ids: Some(vec![ORDER_ID.parse().unwrap()]),
+    });
+
+    insta::assert_display_snapshot!(operation.query, @r###"
+    query OrdersSinceQuery($since: DateTime!, $ids: [UUID!]) {
+      ordersSince(since: $since, ids: $ids) {
+        id
+      }
+    }
+    "###);
+    assert_eq!(
+        serde_json::to_value(&operation.variables).unwrap(),
+        json!({
+            "since": "2021-03-04T12:30:00Z",
+            "ids": [ORDER_ID]
+        })
+    );
+}
+
+#[test]
+fn test_invalid_base64_fails_to_decode() {
+    let result = serde_json::from_value::<Base64Bytes>(json!("not base64!"));
+
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .starts_with("Invalid base64 data"));
+}
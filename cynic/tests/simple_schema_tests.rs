@@ -58,7 +58,7 @@ fn run_test(input: serde_json::Value, expected_result: TestQuery) {
         errors: None,
         data: Some(input),
     };
-    let data = query.decode_response(test_data).unwrap().data.unwrap();
+    let data = query.decode_response(test_data).unwrap().unwrap();
     assert_eq!(data, expected_result);
 }
 
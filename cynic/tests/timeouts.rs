@@ -0,0 +1,138 @@
+#![cfg(feature = "reqwest-blocking")]
+
+use cynic::{
+    http::{CynicReqwestError, ReqwestBlockingExt},
+    transport::{
+        middleware, BlockingClient, ClientError, ReqwestBlockingTransport, TransportError,
+    },
+    QueryBuilder,
+};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    time::Duration,
+};
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema",
+    graphql_type = "Root"
+)]
+struct FilmQuery {
+    #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+    film: Option<Film>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    query_module = "schema"
+)]
+struct Film {
+    title: Option<String>,
+}
+
+/// Starts a server that accepts connections but never responds.
+fn start_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    std::thread::spawn(move || {
+        let connections = listener.incoming().collect::<Vec<_>>();
+        drop(connections);
+    });
+
+    url
+}
+
+/// Starts a server that responds to a single request straight away.
+fn start_responding_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                content_length = length.trim().parse().unwrap();
+            }
+        }
+        reader.read_exact(&mut vec![0; content_length]).unwrap();
+
+        let response = r#"{"data": {"film": {"title": "A New Hope"}}}"#;
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            response.len(),
+            response
+        )
+        .unwrap();
+    });
+
+    url
+}
+
+#[test]
+fn test_reqwest_ext_timeouts_are_reqwest_errors() {
+    let url = start_server();
+
+    let error = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap()
+        .post(&url)
+        .run_graphql(FilmQuery::build(()))
+        .unwrap_err();
+
+    match error {
+        CynicReqwestError::ReqwestError(error) => assert!(error.is_timeout()),
+        error => panic!("Unexpected error: {:?}", error),
+    }
+}
+
+#[test]
+fn test_timeout_middleware_timeouts() {
+    let url = start_server();
+
+    let client = BlockingClient::new(ReqwestBlockingTransport::new(
+        reqwest::blocking::Client::new(),
+        url,
+    ))
+    .with(middleware::Timeout::new(Duration::from_millis(100)));
+
+    assert_matches::assert_matches!(
+        client.run(FilmQuery::build(())),
+        Err(ClientError::Transport(TransportError::Timeout))
+    );
+}
+
+#[test]
+fn test_timeout_middleware_allows_quick_responses() {
+    let url = start_responding_server();
+
+    let client = BlockingClient::new(ReqwestBlockingTransport::new(
+        reqwest::blocking::Client::new(),
+        url,
+    ))
+    .with(middleware::Timeout::new(Duration::from_secs(10)));
+
+    let film = client.run(FilmQuery::build(())).unwrap().data.unwrap().film;
+
+    assert_eq!(
+        film.and_then(|film| film.title).as_deref(),
+        Some("A New Hope")
+    );
+}
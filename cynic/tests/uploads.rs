@@ -0,0 +1,273 @@
+#![cfg(all(feature = "reqwest", feature = "reqwest-blocking"))]
+
+use cynic::{
+    http::{CynicReqwestError, ReqwestBlockingExt, ReqwestExt},
+    MutationBuilder, Upload,
+};
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+mod schema {
+    cynic::use_schema!("tests/upload-schema.graphql");
+}
+
+cynic::impl_input_type!(Upload, schema::Upload);
+
+#[derive(cynic::FragmentArguments)]
+struct UploadFileArguments {
+    file: Upload,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "tests/upload-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Mutation",
+    argument_struct = "UploadFileArguments"
+)]
+struct UploadFile {
+    #[arguments(file = args.file.clone())]
+    upload_file: String,
+}
+
+#[derive(cynic::InputObject, Clone)]
+#[cynic(schema_path = "tests/upload-schema.graphql", query_module = "schema")]
+struct AttachmentInput {
+    name: String,
+    file: Upload,
+}
+
+#[derive(cynic::FragmentArguments)]
+struct UploadAttachmentsArguments {
+    attachments: Vec<AttachmentInput>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "tests/upload-schema.graphql",
+    query_module = "schema",
+    graphql_type = "Mutation",
+    argument_struct = "UploadAttachmentsArguments"
+)]
+struct UploadAttachments {
+    #[arguments(attachments = args.attachments.clone())]
+    upload_attachments: Vec<String>,
+}
+
+/// Starts a server that reads a single multipart request, checks that it
+/// follows the spec & responds with the result of `resolve`.
+///
+/// `resolve` is given the variables of the request, with each file replaced
+/// by a string of its filename, content type & contents.
+async fn start_server(resolve: fn(&Value) -> Value) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let (headers, body) = read_request(&mut stream).await;
+
+        let boundary = headers
+            .lines()
+            .find_map(|line| line.strip_prefix("content-type: multipart/form-data; boundary="))
+            .expect("a multipart content type")
+            .to_string();
+        let parts = parse_multipart(&body, &boundary);
+
+        assert_eq!(parts[0].0, "operations");
+        assert_eq!(parts[1].0, "map");
+        let mut operations: Value = serde_json::from_str(&parts[0].1).unwrap();
+        let map: Value = serde_json::from_str(&parts[1].1).unwrap();
+
+        for (name, paths) in map.as_object().unwrap() {
+            let (_, file) = parts.iter().find(|(part, _)| part == name).unwrap();
+            for path in paths.as_array().unwrap() {
+                let pointer = format!("/{}", path.as_str().unwrap().replace('.', "/"));
+                let value = operations.pointer_mut(&pointer).unwrap();
+                assert_eq!(*value, Value::Null);
+                *value = Value::String(file.clone());
+            }
+        }
+
+        let response = json!({ "data": resolve(&operations["variables"]) }).to_string();
+        stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+    });
+
+    address
+}
+
+/// Reads a HTTP request, returning its lowercased headers & its body.
+async fn read_request(stream: &mut TcpStream) -> (String, String) {
+    let mut request = vec![];
+    let mut buffer = [0; 1024];
+    loop {
+        let read = stream.read(&mut buffer).await.unwrap();
+        request.extend_from_slice(&buffer[..read]);
+
+        let request = String::from_utf8_lossy(&request);
+        if let Some(header_end) = request.find("\r\n\r\n") {
+            let headers = request[..header_end].to_lowercase();
+            let content_length = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length: "))
+                .map(|length| length.parse::<usize>().unwrap())
+                .unwrap();
+
+            let body = &request[header_end + 4..];
+            if body.len() >= content_length {
+                return (headers, body.to_string());
+            }
+        }
+    }
+}
+
+/// Parses a multipart body into the name & contents of each part.  The
+/// contents of files are prefixed with their filename & content type.
+fn parse_multipart(body: &str, boundary: &str) -> Vec<(String, String)> {
+    let delimiter = format!("--{}", boundary);
+    assert!(body.ends_with(&format!("{}--\r\n", delimiter)));
+
+    body.split(&delimiter)
+        .filter(|part| part.starts_with("\r\n"))
+        .map(|part| {
+            let (headers, contents) = part[2..].split_once("\r\n\r\n").unwrap();
+            let contents = contents.strip_suffix("\r\n").unwrap();
+            let disposition = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("Content-Disposition: form-data; "))
+                .unwrap();
+            let content_type = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("Content-Type: "))
+                .unwrap();
+
+            let name = disposition
+                .split("; ")
+                .find_map(|param| param.strip_prefix("name="))
+                .unwrap()
+                .trim_matches('"');
+            let filename = disposition
+                .split("; ")
+                .find_map(|param| param.strip_prefix("filename="));
+
+            match filename {
+                Some(filename) => (
+                    name.to_string(),
+                    format!(
+                        "{} ({}): {}",
+                        filename.trim_matches('"'),
+                        content_type,
+                        contents
+                    ),
+                ),
+                None => (name.to_string(), contents.to_string()),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_upload_query_output() {
+    let operation = UploadFile::build(UploadFileArguments {
+        file: Upload::new("hello.txt", "hello"),
+    });
+
+    insta::assert_display_snapshot!(operation.query, @r###"
+    mutation UploadFile($file: Upload!) {
+      uploadFile(file: $file)
+    }
+    "###);
+}
+
+#[tokio::test]
+async fn test_uploads_require_multipart_requests() {
+    let operation = UploadFile::build(UploadFileArguments {
+        file: Upload::new("hello.txt", "hello"),
+    });
+
+    let error = serde_json::to_value(&operation).unwrap_err();
+    assert!(error.to_string().contains("multipart"));
+
+    // Nothing is listening on this port, but the error should come from
+    // serializing the operation before we ever try to send it.
+    let result = reqwest::Client::new()
+        .post("http://127.0.0.1:1")
+        .run_graphql(operation)
+        .await;
+
+    assert_matches::assert_matches!(result, Err(CynicReqwestError::SerdeError(_)));
+}
+
+#[tokio::test]
+async fn test_upload_file() {
+    let url = start_server(|variables| json!({ "uploadFile": variables["file"] })).await;
+
+    let operation = UploadFile::build(UploadFileArguments {
+        file: Upload::new("hello.txt", "Hello, world!").with_content_type("text/plain"),
+    });
+
+    let response = reqwest::Client::new()
+        .post(&url)
+        .run_graphql_multipart(operation)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response.data.unwrap().upload_file,
+        "hello.txt (text/plain): Hello, world!"
+    );
+}
+
+#[test]
+fn test_upload_files_in_input_objects() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let url = runtime.block_on(start_server(|variables| {
+        json!({
+            "uploadAttachments": variables["attachments"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|attachment| format!("{} = {}", attachment["name"], attachment["file"]))
+                .collect::<Vec<_>>()
+        })
+    }));
+
+    let operation = UploadAttachments::build(UploadAttachmentsArguments {
+        attachments: vec![
+            AttachmentInput {
+                name: "first".into(),
+                file: Upload::new("one.txt", "1"),
+            },
+            AttachmentInput {
+                name: "second".into(),
+                file: Upload::new("two.csv", "2,2").with_content_type("text/csv"),
+            },
+        ],
+    });
+
+    let response = reqwest::blocking::Client::new()
+        .post(&url)
+        .run_graphql_multipart(operation)
+        .unwrap();
+
+    assert_eq!(
+        response.data.unwrap().upload_attachments,
+        vec![
+            "\"first\" = \"one.txt (application/octet-stream): 1\"",
+            "\"second\" = \"two.csv (text/csv): 2,2\""
+        ]
+    );
+}
@@ -0,0 +1,182 @@
+#![cfg(feature = "ws")]
+
+use cynic::{
+    ws::{SubscriptionClient, SubscriptionError, WebsocketProtocol},
+    FragmentArguments, SubscriptionBuilder,
+};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{tungstenite::Message, WebSocketStream};
+
+mod schema {
+    cynic::use_schema!("../schemas/books.graphql");
+}
+
+#[derive(FragmentArguments)]
+struct IntervalArguments {
+    n: i32,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/books.graphql",
+    query_module = "schema",
+    graphql_type = "SubscriptionRoot",
+    argument_struct = "IntervalArguments"
+)]
+struct IntervalSubscription {
+    #[arguments(n = args.n)]
+    interval: i32,
+}
+
+/// Starts a server that speaks just enough of either protocol to echo the
+/// variables of each subscription back to the client twice.
+async fn start_server(protocol: WebsocketProtocol) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("ws://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+        run_server(socket, protocol).await;
+    });
+
+    address
+}
+
+async fn run_server(mut socket: WebSocketStream<TcpStream>, protocol: WebsocketProtocol) {
+    let (next_type, subscribe_type) = match protocol {
+        WebsocketProtocol::GraphQlTransportWs => ("next", "subscribe"),
+        WebsocketProtocol::SubscriptionsTransportWs => ("data", "start"),
+    };
+
+    let init = read_json(&mut socket).await.unwrap();
+    assert_eq!(init["type"], "connection_init");
+    if init["payload"]["token"] != "secret" {
+        send_json(&mut socket, json!({"type": "connection_error"})).await;
+        return;
+    }
+
+    if protocol == WebsocketProtocol::GraphQlTransportWs {
+        send_json(&mut socket, json!({"type": "ping"})).await;
+        assert_eq!(read_json(&mut socket).await.unwrap()["type"], "pong");
+    } else {
+        send_json(&mut socket, json!({"type": "ka"})).await;
+    }
+    send_json(&mut socket, json!({"type": "connection_ack"})).await;
+
+    let mut subscriptions = vec![];
+    while subscriptions.len() < 2 {
+        let message = read_json(&mut socket).await.unwrap();
+        assert_eq!(message["type"], subscribe_type);
+        assert!(message["payload"]["query"]
+            .as_str()
+            .unwrap()
+            .starts_with("subscription"));
+        subscriptions.push(message);
+    }
+
+    // Interleave the responses so we're testing the multiplexing.
+    for round in 0..2 {
+        for subscription in &subscriptions {
+            let n = subscription["payload"]["variables"]
+                .as_object()
+                .unwrap()
+                .values()
+                .next()
+                .unwrap()
+                .as_i64()
+                .unwrap();
+
+            send_json(
+                &mut socket,
+                json!({
+                    "type": next_type,
+                    "id": subscription["id"],
+                    "payload": {"data": {"interval": n * 10 + round}}
+                }),
+            )
+            .await;
+        }
+    }
+
+    for subscription in &subscriptions {
+        send_json(
+            &mut socket,
+            json!({"type": "complete", "id": subscription["id"]}),
+        )
+        .await;
+    }
+
+    socket.close(None).await.ok();
+}
+
+async fn read_json(socket: &mut WebSocketStream<TcpStream>) -> Option<Value> {
+    while let Some(message) = socket.next().await {
+        if let Message::Text(text) = message.unwrap() {
+            return Some(serde_json::from_str(&text).unwrap());
+        }
+    }
+    None
+}
+
+async fn send_json(socket: &mut WebSocketStream<TcpStream>, value: Value) {
+    socket.send(Message::Text(value.to_string())).await.unwrap();
+}
+
+async fn subscription_results(protocol: WebsocketProtocol) -> (Vec<i32>, Vec<i32>) {
+    let address = start_server(protocol).await;
+    let (connection, _) = tokio_tungstenite::connect_async(address).await.unwrap();
+
+    let (client, driver) = SubscriptionClient::builder()
+        .protocol(protocol)
+        .payload(json!({"token": "secret"}))
+        .build(connection)
+        .await
+        .unwrap();
+    tokio::spawn(driver);
+
+    let one = client.subscribe(IntervalSubscription::build(IntervalArguments { n: 1 }));
+    let two = client.subscribe(IntervalSubscription::build(IntervalArguments { n: 2 }));
+
+    let collect = |stream: cynic::ws::SubscriptionStream<'static, IntervalSubscription>| {
+        stream
+            .map(|response| response.unwrap().data.unwrap().interval)
+            .collect::<Vec<_>>()
+    };
+
+    futures_util::future::join(collect(one), collect(two)).await
+}
+
+#[tokio::test]
+async fn test_graphql_transport_ws_subscriptions() {
+    assert_eq!(
+        subscription_results(WebsocketProtocol::GraphQlTransportWs).await,
+        (vec![10, 11], vec![20, 21])
+    );
+}
+
+#[tokio::test]
+async fn test_subscriptions_transport_ws_subscriptions() {
+    assert_eq!(
+        subscription_results(WebsocketProtocol::SubscriptionsTransportWs).await,
+        (vec![10, 11], vec![20, 21])
+    );
+}
+
+#[tokio::test]
+async fn test_rejected_connection_init() {
+    let address = start_server(WebsocketProtocol::GraphQlTransportWs).await;
+    let (connection, _) = tokio_tungstenite::connect_async(address).await.unwrap();
+
+    let result = SubscriptionClient::builder()
+        .payload(json!({"token": "wrong"}))
+        .build(connection)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(SubscriptionError::ConnectionRejected(_))
+    ));
+}
@@ -0,0 +1,315 @@
+//! Compares decoding a large GitHub response with the `json_decode` based
+//! decoders against the generated `serde::Deserialize` impls.
+//!
+//! The `comment-authors` group covers the cases where the `Deserialize` impls
+//! have to buffer: spreads, and `InlineFragments` where `__typename` isn't
+//! the first field of an object.
+//!
+//! Run this with `cargo bench --features github`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use cynic::{GraphQlResponse, QueryBuilder};
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("../schemas/github.graphql");
+}
+
+#[cynic::schema_for_derives(file = "../schemas/github.graphql", module = "schema")]
+#[allow(dead_code)]
+mod queries {
+    use super::schema;
+
+    pub type DateTime = chrono::DateTime<chrono::Utc>;
+
+    cynic::impl_scalar!(DateTime, schema::DateTime);
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "Query")]
+    pub struct PullRequestsQuery {
+        #[arguments(name = "cynic".to_string(), owner = "obmarg".to_string())]
+        pub repository: Option<Repository>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct Repository {
+        #[arguments(first = 100)]
+        pub pull_requests: PullRequestConnection,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct PullRequestConnection {
+        #[cynic(flatten)]
+        pub nodes: Vec<PullRequest>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct PullRequest {
+        pub number: i32,
+        pub title: String,
+        pub state: PullRequestState,
+        pub created_at: DateTime,
+        pub author: Option<Actor>,
+        #[arguments(first = 10)]
+        pub labels: Option<LabelConnection>,
+        #[arguments(first = 20)]
+        pub comments: IssueCommentConnection,
+    }
+
+    #[derive(cynic::Enum, Clone, Copy, Debug)]
+    pub enum PullRequestState {
+        Closed,
+        Merged,
+        Open,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct Actor {
+        pub login: String,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct LabelConnection {
+        #[cynic(flatten)]
+        pub nodes: Vec<Label>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct Label {
+        pub name: String,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct IssueCommentConnection {
+        #[cynic(flatten)]
+        pub nodes: Vec<IssueComment>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct IssueComment {
+        pub body: String,
+        pub created_at: DateTime,
+        pub author: Option<Actor>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "Query")]
+    pub struct CommentAuthorsQuery {
+        #[arguments(name = "cynic".to_string(), owner = "obmarg".to_string())]
+        pub repository: Option<CommentAuthorsRepository>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "Repository")]
+    pub struct CommentAuthorsRepository {
+        #[arguments(first = 100)]
+        pub pull_requests: CommentAuthorsPullRequestConnection,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "PullRequestConnection")]
+    pub struct CommentAuthorsPullRequestConnection {
+        #[cynic(flatten)]
+        pub nodes: Vec<CommentAuthorsPullRequest>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "PullRequest")]
+    pub struct CommentAuthorsPullRequest {
+        #[cynic(spread)]
+        pub details: PullRequestDetails,
+        #[arguments(first = 20)]
+        pub comments: AuthoredCommentConnection,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "PullRequest")]
+    pub struct PullRequestDetails {
+        pub number: i32,
+        pub title: String,
+        pub state: PullRequestState,
+        pub created_at: DateTime,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "IssueCommentConnection")]
+    pub struct AuthoredCommentConnection {
+        #[cynic(flatten)]
+        pub nodes: Vec<AuthoredComment>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "IssueComment")]
+    pub struct AuthoredComment {
+        pub body: String,
+        pub created_at: DateTime,
+        pub author: Option<Author>,
+    }
+
+    #[derive(cynic::InlineFragments, Debug)]
+    #[cynic(graphql_type = "Actor")]
+    pub enum Author {
+        User(User),
+        Bot(Bot),
+
+        #[cynic(fallback)]
+        Other,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct User {
+        pub login: String,
+        pub name: Option<String>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct Bot {
+        pub login: String,
+    }
+}
+
+/// Builds a response with 100 pull requests, each of which has 20 comments.
+fn response() -> String {
+    let comment = |i: usize| {
+        json!({
+            "body": format!("Comment number {}. {}", i, "Lorem ipsum dolor sit amet. ".repeat(10)),
+            "createdAt": "2021-06-06T12:00:00Z",
+            "author": {"login": "obmarg"}
+        })
+    };
+    let pull_request = |i: usize| {
+        json!({
+            "number": i,
+            "title": format!("Pull request number {}", i),
+            "state": "MERGED",
+            "createdAt": "2021-06-06T12:00:00Z",
+            "author": {"login": "obmarg"},
+            "labels": {"nodes": [{"name": "enhancement"}, {"name": "bug"}]},
+            "comments": {"nodes": (0..20).map(comment).collect::<Vec<_>>()}
+        })
+    };
+
+    json!({
+        "data": {
+            "repository": {
+                "pullRequests": {
+                    "nodes": (0..100).map(pull_request).collect::<Vec<_>>()
+                }
+            }
+        }
+    })
+    .to_string()
+}
+
+/// Builds a response with 100 pull requests, each of which has 20 comments
+/// by a mix of users & bots.
+///
+/// If `typename_first` is false the `__typename` of each author comes after
+/// the rest of its fields.
+fn comment_authors_response(typename_first: bool) -> String {
+    let comment = |i: usize| {
+        let author = if i < 10 {
+            json!({"__typename": "User", "login": "obmarg", "name": "Graeme"})
+        } else {
+            json!({"__typename": "Bot", "login": "dependabot"})
+        };
+        json!({
+            "body": format!("Comment number {}. {}", i, "Lorem ipsum dolor sit amet. ".repeat(10)),
+            "createdAt": "2021-06-06T12:00:00Z",
+            "author": author
+        })
+    };
+    let pull_request = |i: usize| {
+        json!({
+            "number": i,
+            "title": format!("Pull request number {}", i),
+            "state": "MERGED",
+            "createdAt": "2021-06-06T12:00:00Z",
+            "comments": {"nodes": (0..20).map(comment).collect::<Vec<_>>()}
+        })
+    };
+
+    let body = json!({
+        "data": {
+            "repository": {
+                "pullRequests": {
+                    "nodes": (0..100).map(pull_request).collect::<Vec<_>>()
+                }
+            }
+        }
+    })
+    .to_string();
+
+    if typename_first {
+        return body;
+    }
+
+    // serde_json sorts the keys of objects, so `__typename` always comes
+    // first unless we move it ourselves.
+    body.replace(
+        r#"{"__typename":"User","login":"obmarg","name":"Graeme"}"#,
+        r#"{"login":"obmarg","name":"Graeme","__typename":"User"}"#,
+    )
+    .replace(
+        r#"{"__typename":"Bot","login":"dependabot"}"#,
+        r#"{"login":"dependabot","__typename":"Bot"}"#,
+    )
+}
+
+fn decoding(c: &mut Criterion) {
+    let operation = queries::PullRequestsQuery::build(());
+    let body = response();
+
+    let mut group = c.benchmark_group("github-decoding");
+
+    group.bench_function("json_decode", |b| {
+        b.iter(|| {
+            let response =
+                serde_json::from_str::<GraphQlResponse<serde_json::Value>>(black_box(&body))
+                    .unwrap();
+            operation.decode_response(response).unwrap()
+        })
+    });
+
+    group.bench_function("deserialize", |b| {
+        b.iter(|| {
+            operation
+                .decode_response_slice(black_box(body.as_bytes()))
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+fn comment_authors_decoding(c: &mut Criterion) {
+    let operation = queries::CommentAuthorsQuery::build(());
+
+    let mut group = c.benchmark_group("comment-authors");
+
+    for (name, typename_first) in &[("typename-first", true), ("typename-last", false)] {
+        let body = comment_authors_response(*typename_first);
+
+        group.bench_function(format!("json_decode/{}", name), |b| {
+            b.iter(|| {
+                let response =
+                    serde_json::from_str::<GraphQlResponse<serde_json::Value>>(black_box(&body))
+                        .unwrap();
+                operation.decode_response(response).unwrap()
+            })
+        });
+
+        group.bench_function(format!("deserialize/{}", name), |b| {
+            b.iter(|| {
+                operation
+                    .decode_response_slice(black_box(body.as_bytes()))
+                    .unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, decoding, comment_authors_decoding);
+criterion_main!(benches);
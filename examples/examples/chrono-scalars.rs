@@ -1,13 +1,15 @@
-//! An example of querying the starwars API using the reqwest-blocking feature
+//! An example of using chrono types for custom scalars, via the chrono feature
 
 mod schema {
     cynic::use_schema!("../schemas/graphql.jobs.graphql");
+
+    // With the chrono feature enabled, cynic can use chrono types for any
+    // custom scalar that implements IsScalar, without calling `impl_scalar!`.
+    impl cynic::IsScalar for DateTime {}
 }
 
 type DateTime = chrono::DateTime<chrono::Utc>;
 
-cynic::impl_scalar!(DateTime, schema::DateTime);
-
 #[derive(cynic::QueryFragment, Debug)]
 #[cynic(
     schema_path = "../schemas/graphql.jobs.graphql",
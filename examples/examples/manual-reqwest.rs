@@ -36,7 +36,7 @@ fn main() {
     println!("{:?}", result);
 }
 
-fn run_query() -> cynic::GraphQlResponse<FilmDirectorQuery> {
+fn run_query() -> cynic::GraphQlResult<FilmDirectorQuery> {
     let query = build_query();
 
     let response = reqwest::blocking::Client::new()
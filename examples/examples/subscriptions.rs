@@ -0,0 +1,81 @@
+//! An example that shows how to run a GraphQL subscription over a websocket.
+//!
+//! This example requires the `ws` feature to be active, and expects the
+//! books example server from async-graphql to be running on localhost:8000
+
+use futures_util::StreamExt;
+
+mod schema {
+    cynic::use_schema!("../schemas/books.graphql");
+}
+
+#[derive(cynic::FragmentArguments)]
+struct BooksChangedArguments {
+    mutation_type: Option<MutationType>,
+}
+
+#[derive(cynic::Enum, Clone, Copy, Debug)]
+#[cynic(
+    schema_path = "../schemas/books.graphql",
+    query_module = "schema",
+    graphql_type = "MutationType"
+)]
+enum MutationType {
+    Created,
+    Deleted,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/books.graphql",
+    query_module = "schema",
+    graphql_type = "SubscriptionRoot",
+    argument_struct = "BooksChangedArguments"
+)]
+struct BooksChangedSubscription {
+    #[arguments(mutation_type = args.mutation_type)]
+    books: BookChanged,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "../schemas/books.graphql", query_module = "schema")]
+struct BookChanged {
+    id: cynic::Id,
+    mutation_type: MutationType,
+}
+
+#[tokio::main]
+async fn main() {
+    use cynic::ws::SubscriptionClient;
+    use tokio_tungstenite::tungstenite::{client::IntoClientRequest, http::HeaderValue};
+
+    let protocol = cynic::ws::WebsocketProtocol::SubscriptionsTransportWs;
+
+    let mut request = "ws://localhost:8000".into_client_request().unwrap();
+    request.headers_mut().insert(
+        "Sec-WebSocket-Protocol",
+        HeaderValue::from_static(protocol.subprotocol()),
+    );
+
+    let (connection, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+
+    let (client, driver) = SubscriptionClient::builder()
+        .protocol(protocol)
+        .build(connection)
+        .await
+        .unwrap();
+    tokio::spawn(driver);
+
+    let mut stream = client.subscribe(build_subscription());
+    while let Some(response) = stream.next().await {
+        println!("{:?}", response.unwrap().data);
+    }
+}
+
+fn build_subscription() -> cynic::StreamingOperation<'static, BooksChangedSubscription> {
+    use cynic::SubscriptionBuilder;
+
+    BooksChangedSubscription::build(BooksChangedArguments {
+        mutation_type: Some(MutationType::Created),
+    })
+}
@@ -9,13 +9,12 @@ pub fn send<'a, ResponseData: 'a>(
         .send()
         .unwrap();
 
-    let response_data = operation.decode_response(response.json()?)?;
-    if let Some(_errors) = response_data.errors {
-        println!("{:?}", _errors);
-        return Err("GraphQL server returned errors".into());
-        // TODO: Better errors here
-        //Err(errors)?
+    match operation.decode_response(response.json()?)? {
+        Ok(data) => Ok(data),
+        Err((_, errors)) => {
+            println!("{:?}", errors);
+            Err("GraphQL server returned errors".into())
+            // TODO: Better errors here
+        }
     }
-
-    Ok(response_data.data.expect("response data"))
 }
@@ -0,0 +1,26 @@
+fn main() {}
+
+mod schema {
+    cynic::use_schema!(r#"./../../../schemas/starwars.schema.graphql"#);
+}
+
+#[cynic::schema_for_derives(
+    file = r#"./../../../schemas/starwars.schema.graphql"#,
+    module = "schema"
+)]
+mod queries {
+    use super::schema;
+    use cynic::relay::Connection;
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "Root")]
+    pub struct AllPlanetsQuery {
+        // Note: this is the wrong node type, should be Planet
+        pub all_planets: Option<Connection<Film, schema::PlanetsConnection>>,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct Film {
+        pub title: Option<String>,
+    }
+}
@@ -0,0 +1,82 @@
+fn main() {}
+
+mod schema {
+    cynic::use_schema!("../../../cynic/src/bin/simple.graphql");
+}
+
+#[derive(cynic::Enum)]
+#[cynic(
+    schema_path = "../../../cynic/src/bin/simple.graphql",
+    graphql_type = "Dessert"
+)]
+enum TwoFallbacks {
+    Cheesecake,
+    IceCream,
+
+    #[cynic(fallback)]
+    Other(String),
+
+    #[cynic(fallback)]
+    Unknown,
+}
+
+#[derive(cynic::Enum)]
+#[cynic(
+    schema_path = "../../../cynic/src/bin/simple.graphql",
+    graphql_type = "Dessert"
+)]
+enum FieldsWithoutFallback {
+    Cheesecake,
+    IceCream(String),
+}
+
+#[derive(cynic::Enum)]
+#[cynic(
+    schema_path = "../../../cynic/src/bin/simple.graphql",
+    graphql_type = "Dessert"
+)]
+enum FallbackIsntExhaustive {
+    Cheesecake,
+
+    #[cynic(fallback)]
+    Other(String),
+}
+
+#[derive(cynic::Enum)]
+#[cynic(
+    schema_path = "../../../cynic/src/bin/simple.graphql",
+    graphql_type = "Dessert"
+)]
+enum FallbackFieldIsntString {
+    Cheesecake,
+    IceCream,
+
+    #[cynic(fallback)]
+    Other(i32),
+}
+
+#[derive(cynic::Enum)]
+#[cynic(
+    schema_path = "../../../cynic/src/bin/simple.graphql",
+    graphql_type = "Dessert"
+)]
+enum FallbackHasTwoFields {
+    Cheesecake,
+    IceCream,
+
+    #[cynic(fallback)]
+    Other(String, String),
+}
+
+#[derive(cynic::Enum, Clone)]
+#[cynic(
+    schema_path = "../../../cynic/src/bin/simple.graphql",
+    graphql_type = "Dessert"
+)]
+enum OkFallback {
+    Cheesecake,
+    IceCream,
+
+    #[cynic(fallback)]
+    Other(String),
+}
@@ -0,0 +1,30 @@
+fn main() {}
+
+mod schema {
+    cynic::use_schema!("../../../cynic/tests/one-of-schema.graphql");
+}
+
+#[derive(cynic::InputObject)]
+#[cynic(schema_path = "../../../cynic/tests/one-of-schema.graphql")]
+enum UserBy {
+    Id(cynic::Id),
+    Emial(String),
+}
+
+#[derive(cynic::InputObject)]
+#[cynic(
+    schema_path = "../../../cynic/tests/one-of-schema.graphql",
+    graphql_type = "ProfileInput"
+)]
+enum RequiredField {
+    Name(String),
+}
+
+#[derive(cynic::InputObject)]
+#[cynic(
+    schema_path = "../../../cynic/tests/one-of-schema.graphql",
+    graphql_type = "UserBy"
+)]
+enum UnitVariant {
+    Id,
+}
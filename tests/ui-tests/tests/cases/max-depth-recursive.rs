@@ -0,0 +1,23 @@
+fn main() {}
+
+mod schema {
+    cynic::use_schema!("../../../cynic/tests/test-schema.graphql");
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../../../cynic/tests/test-schema.graphql",
+    graphql_type = "Query",
+    max_depth = 3
+)]
+struct AllAuthorsQuery {
+    all_authors: Vec<Author>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "../../../cynic/tests/test-schema.graphql")]
+struct Author {
+    name: Option<String>,
+    #[cynic(recurse = "5")]
+    friends: Option<Vec<Author>>,
+}
@@ -0,0 +1,40 @@
+fn main() {}
+
+mod schema {
+    cynic::use_schema!("../../../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../../../schemas/starwars.schema.graphql",
+    graphql_type = "Root",
+    max_depth = 2
+)]
+struct FilmQuery {
+    #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+    film: Option<Film>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "../../../schemas/starwars.schema.graphql")]
+struct Film {
+    title: Option<String>,
+    planet_connection: Option<FilmPlanetsConnection>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "../../../schemas/starwars.schema.graphql")]
+struct FilmPlanetsConnection {
+    total_count: Option<i32>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../../../schemas/starwars.schema.graphql",
+    graphql_type = "Root",
+    max_depth = 0
+)]
+struct ZeroDepthQuery {
+    #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+    film: Option<Film>,
+}
@@ -2,11 +2,16 @@
 fn ui_test_inlinefragments() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/cases/enum-guess-validation.rs");
+    t.compile_fail("tests/cases/enum-fallback-validation.rs");
     t.compile_fail("tests/cases/fragment-guess-validation.rs");
     t.compile_fail("tests/cases/inline-fragment-exhaustiveness.rs");
     t.compile_fail("tests/cases/inline-fragment-fallback-validation.rs");
     t.compile_fail("tests/cases/rename-failures.rs");
     t.compile_fail("tests/cases/inputobject-guess-validation.rs");
+    t.compile_fail("tests/cases/input-object-oneof-validation.rs");
     t.compile_fail("tests/cases/wrong-enum-type.rs");
+    t.compile_fail("tests/cases/max-depth.rs");
+    t.compile_fail("tests/cases/max-depth-recursive.rs");
+    t.compile_fail("tests/cases/connection-node-type.rs");
     t.pass("tests/cases/input-fragment-no-graphql-type.rs");
 }